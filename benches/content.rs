@@ -0,0 +1,152 @@
+//! Micro-benchmarks for the CPU-bound parts of the content pipeline: slug generation,
+//! response serialization, import payload parsing and session scoring.
+//!
+//! Run with `cargo bench --bench content`. Save a baseline with
+//! `-- --save-baseline main` and compare a branch against it with `-- --baseline main`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use beep_rust::handlers::quiz::{grade_answers, score};
+use beep_rust::models::{
+    generate_slug, ApiResponse, BulkCreateQuestions, BulkQuestionData, Difficulty, Question,
+    QuestionResponse, QuestionType, QuizSession, SessionAnswer, SessionStatus,
+};
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+const QUESTIONS: usize = 100;
+
+fn question(topic_id: Uuid, number: usize) -> Question {
+    let now = Utc::now();
+
+    Question {
+        id: Uuid::new_v4(),
+        topic_id,
+        question_number: number as i32,
+        question: format!("Which storage class is cheapest for data accessed once a year? ({})", number),
+        options: Json(vec![
+            "S3 Standard".to_string(),
+            "S3 Glacier Deep Archive".to_string(),
+            "S3 Intelligent-Tiering".to_string(),
+            "S3 One Zone-IA".to_string(),
+        ]),
+        correct_answer: Json(vec!["B".to_string()]),
+        explanation: "Deep Archive has the lowest storage price and hours-long retrieval.".to_string(),
+        question_type: QuestionType::Single,
+        difficulty: Difficulty::Medium,
+        cognitive_level: None,
+        tags: Some(Json(vec!["storage".to_string(), "cost".to_string()])),
+        option_explanations: Some(Json(HashMap::from([
+            ("A".to_string(), "Priced for frequent access.".to_string()),
+            ("B".to_string(), "Cheapest for archives.".to_string()),
+        ]))),
+        hints: Some(Json(vec!["Think about retrieval time.".to_string()])),
+        metadata: Json(HashMap::new()),
+        variables: None,
+        code_blocks: None,
+        word_count: 0,
+        estimated_secs: 0,
+        explanation_upvotes: 3,
+        explanation_downvotes: 1,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn questions() -> Vec<Question> {
+    let topic_id = Uuid::new_v4();
+    (1..=QUESTIONS).map(|n| question(topic_id, n)).collect()
+}
+
+fn bench_slug(c: &mut Criterion) {
+    let names = [
+        "AWS Solutions Architect",
+        "  Kubernetes: Pods, Services & Ingress!  ",
+        "Ünïcödé Tópic — with dashes -- and spaces",
+        "???",
+    ];
+
+    c.bench_function("slug/generate_slug", |b| {
+        b.iter(|| {
+            for name in names {
+                black_box(generate_slug(black_box(name)));
+            }
+        })
+    });
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    group.throughput(Throughput::Elements(QUESTIONS as u64));
+
+    group.bench_function("question_list_response", |b| {
+        b.iter_batched(
+            || questions().into_iter().map(QuestionResponse::from).collect::<Vec<_>>(),
+            |responses| serde_json::to_vec(&ApiResponse::success(responses)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let payload = serde_json::to_vec(&serde_json::json!({
+        "topic_slug": "aws-solutions-architect",
+        "questions": questions().into_iter().map(BulkQuestionData::from).collect::<Vec<_>>(),
+    }))
+    .unwrap();
+
+    group.bench_function("bulk_import_payload", |b| {
+        b.iter(|| serde_json::from_slice::<BulkCreateQuestions>(black_box(&payload)).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_scoring(c: &mut Criterion) {
+    let questions = questions();
+    let now = Utc::now();
+
+    let session = QuizSession {
+        id: Uuid::new_v4(),
+        topic_id: questions[0].topic_id,
+        question_ids: Json(questions.iter().map(|q| q.id).collect()),
+        status: SessionStatus::InProgress,
+        score: None,
+        seed: Some(42),
+        hint_penalty: 0.0,
+        hints_used: 0,
+        time_limit_secs: None,
+        pause_budget_secs: None,
+        paused_at: None,
+        paused_secs: 0,
+        learner_ref: None,
+        started_at: now,
+        submitted_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let answers: Vec<SessionAnswer> = questions
+        .iter()
+        .map(|q| SessionAnswer {
+            session_id: session.id,
+            question_id: q.id,
+            selected: Json(vec!["B".to_string()]),
+            confidence: None,
+            saved_at: now,
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("scoring");
+    group.throughput(Throughput::Elements(QUESTIONS as u64));
+
+    group.bench_function("grade_and_score", |b| {
+        b.iter(|| score(&grade_answers(black_box(&session), &questions, &answers)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_slug, bench_serialize, bench_scoring);
+criterion_main!(benches);
@@ -0,0 +1,278 @@
+//! Load scenario against a running server: lists, searches and bulk imports in parallel
+//! for a fixed time, then prints one JSON line per scenario.
+//!
+//! ```text
+//! cargo run --release --example load > results.json
+//! LOAD_BASELINE=results.json cargo run --release --example load
+//! ```
+//!
+//! With `LOAD_BASELINE` set, each scenario is compared against the saved run and the
+//! process exits non-zero when throughput drops or p95 latency grows by more than
+//! `LOAD_TOLERANCE` (default 0.2, i.e. 20%), so CI can fail on regressions.
+//!
+//! Other settings: `LOAD_BASE_URL` (default `http://localhost:3000/api`),
+//! `LOAD_DURATION_SECS` (default 10) and `LOAD_CONCURRENCY` workers per scenario
+//! (default 8). A throwaway topic is created for the bulk imports and deleted afterwards.
+
+use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use axum::body::Bytes;
+use axum::http::{header, Method, Request};
+use http_body_util::{BodyExt, Full};
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+type HttpClient = Client<hyper_util::client::legacy::connect::HttpConnector, Full<Bytes>>;
+
+/// Questions per bulk import request
+const BULK_SIZE: i32 = 20;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScenarioResult {
+    scenario: String,
+    requests: usize,
+    errors: usize,
+    requests_per_sec: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+}
+
+#[derive(Clone, Copy)]
+enum Scenario {
+    ListQuestions,
+    Search,
+    BulkImport,
+}
+
+impl Scenario {
+    const ALL: [Scenario; 3] = [Scenario::ListQuestions, Scenario::Search, Scenario::BulkImport];
+
+    fn name(self) -> &'static str {
+        match self {
+            Scenario::ListQuestions => "list_questions",
+            Scenario::Search => "search",
+            Scenario::BulkImport => "bulk_import",
+        }
+    }
+}
+
+struct Target {
+    client: HttpClient,
+    base_url: String,
+    topic_slug: String,
+    next_question_number: AtomicI32,
+}
+
+impl Target {
+    async fn call(&self, method: Method, path: &str, body: Option<serde_json::Value>) -> anyhow::Result<Bytes> {
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))?;
+
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        let bytes = response.into_body().collect().await?.to_bytes();
+
+        if !status.is_success() {
+            bail!("{} {}: {}", status, path, String::from_utf8_lossy(&bytes));
+        }
+
+        Ok(bytes)
+    }
+
+    async fn run_once(&self, scenario: Scenario) -> anyhow::Result<()> {
+        match scenario {
+            Scenario::ListQuestions => {
+                self.call(Method::GET, "/questions?page=1&limit=20", None).await?;
+            }
+            Scenario::Search => {
+                self.call(Method::GET, "/search?q=storage&limit=10", None).await?;
+            }
+            Scenario::BulkImport => {
+                let first = self.next_question_number.fetch_add(BULK_SIZE, Ordering::Relaxed);
+                let questions: Vec<_> = (first..first + BULK_SIZE)
+                    .map(|n| {
+                        json!({
+                            "question_number": n,
+                            "question": format!("Load test question {}: which storage class is cheapest?", n),
+                            "options": ["Standard", "Deep Archive", "Intelligent-Tiering", "One Zone-IA"],
+                            "correct_answer": ["B"],
+                            "explanation": "Deep Archive has the lowest storage price.",
+                            "question_type": "single",
+                        })
+                    })
+                    .collect();
+
+                self.call(
+                    Method::POST,
+                    "/questions/bulk",
+                    Some(json!({ "topic_slug": self.topic_slug, "questions": questions })),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let base_url = env::var("LOAD_BASE_URL").unwrap_or_else(|_| "http://localhost:3000/api".to_string());
+    let duration = Duration::from_secs(env_or("LOAD_DURATION_SECS", 10));
+    let concurrency: usize = env_or("LOAD_CONCURRENCY", 8);
+    let tolerance: f64 = env_or("LOAD_TOLERANCE", 0.2);
+
+    let topic_slug = format!("load-test-{}", chrono::Utc::now().timestamp_millis());
+    let target = Arc::new(Target {
+        client: Client::builder(TokioExecutor::new()).build_http(),
+        base_url,
+        topic_slug: topic_slug.clone(),
+        next_question_number: AtomicI32::new(1),
+    });
+
+    let created = target
+        .call(Method::POST, "/topics", Some(json!({ "name": topic_slug, "slug": topic_slug })))
+        .await
+        .context("Failed to create the load test topic; is the server running?")?;
+    let topic_id = serde_json::from_slice::<serde_json::Value>(&created)?["data"]["id"]
+        .as_str()
+        .context("Topic response has no id")?
+        .to_string();
+
+    let mut results = Vec::new();
+    for scenario in Scenario::ALL {
+        results.push(run_scenario(target.clone(), scenario, duration, concurrency).await);
+    }
+
+    target.call(Method::DELETE, &format!("/topics/{}", topic_id), None).await?;
+
+    for result in &results {
+        println!("{}", serde_json::to_string(result)?);
+    }
+
+    if let Ok(path) = env::var("LOAD_BASELINE") {
+        let baseline = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+        let baseline: Vec<ScenarioResult> = baseline
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+
+        let regressions = compare(&baseline, &results, tolerance);
+        for regression in &regressions {
+            eprintln!("REGRESSION {}", regression);
+        }
+
+        if !regressions.is_empty() {
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_scenario(target: Arc<Target>, scenario: Scenario, duration: Duration, concurrency: usize) -> ScenarioResult {
+    let started = Instant::now();
+
+    let workers: Vec<_> = (0..concurrency)
+        .map(|_| {
+            let target = target.clone();
+            tokio::spawn(async move {
+                let mut latencies = Vec::new();
+                let mut errors = 0;
+
+                while started.elapsed() < duration {
+                    let sent = Instant::now();
+                    match target.run_once(scenario).await {
+                        Ok(()) => latencies.push(sent.elapsed()),
+                        Err(e) => {
+                            errors += 1;
+                            if errors == 1 {
+                                eprintln!("{}: {}", scenario.name(), e);
+                            }
+                        }
+                    }
+                }
+
+                (latencies, errors)
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::new();
+    let mut errors = 0;
+    for worker in workers {
+        if let Ok((worker_latencies, worker_errors)) = worker.await {
+            latencies.extend(worker_latencies);
+            errors += worker_errors;
+        }
+    }
+
+    let elapsed = started.elapsed().as_secs_f64();
+    latencies.sort();
+
+    ScenarioResult {
+        scenario: scenario.name().to_string(),
+        requests: latencies.len() + errors,
+        errors,
+        requests_per_sec: latencies.len() as f64 / elapsed,
+        p50_ms: percentile(&latencies, 0.50),
+        p95_ms: percentile(&latencies, 0.95),
+        p99_ms: percentile(&latencies, 0.99),
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index].as_secs_f64() * 1000.0
+}
+
+/// Scenarios that got slower than the baseline by more than `tolerance`
+fn compare(baseline: &[ScenarioResult], current: &[ScenarioResult], tolerance: f64) -> Vec<String> {
+    let mut regressions = Vec::new();
+
+    for now in current {
+        let Some(before) = baseline.iter().find(|b| b.scenario == now.scenario) else {
+            continue;
+        };
+
+        if now.requests_per_sec < before.requests_per_sec * (1.0 - tolerance) {
+            regressions.push(format!(
+                "{}: {:.1} req/s, baseline {:.1} req/s",
+                now.scenario, now.requests_per_sec, before.requests_per_sec
+            ));
+        }
+
+        if now.p95_ms > before.p95_ms * (1.0 + tolerance) {
+            regressions.push(format!(
+                "{}: p95 {:.1} ms, baseline {:.1} ms",
+                now.scenario, now.p95_ms, before.p95_ms
+            ));
+        }
+
+        if now.errors > before.errors {
+            regressions.push(format!("{}: {} errors, baseline {}", now.scenario, now.errors, before.errors));
+        }
+    }
+
+    regressions
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key).ok().and_then(|s| s.parse().ok()).unwrap_or(default)
+}
@@ -0,0 +1,32 @@
+-- Create enum for quiz session lifecycle
+CREATE TYPE session_status AS ENUM ('in_progress', 'submitted');
+
+-- Create quiz sessions table
+CREATE TABLE quiz_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    topic_id UUID NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    question_ids JSONB NOT NULL DEFAULT '[]',
+    status session_status NOT NULL DEFAULT 'in_progress',
+    score INTEGER,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Create per-question answer drafts (one row per session/question, last write wins)
+CREATE TABLE quiz_session_answers (
+    session_id UUID NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    selected JSONB NOT NULL DEFAULT '[]',
+    saved_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, question_id)
+);
+
+CREATE INDEX idx_quiz_sessions_topic_id ON quiz_sessions(topic_id);
+CREATE INDEX idx_quiz_sessions_status ON quiz_sessions(status);
+
+CREATE TRIGGER update_quiz_sessions_updated_at
+BEFORE UPDATE ON quiz_sessions
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at_column();
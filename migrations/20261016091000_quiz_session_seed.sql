@@ -0,0 +1,3 @@
+-- Seed used to deal question order and shuffle options, so a session can be reproduced.
+-- Sessions created before seeding existed keep a NULL seed and are shown unshuffled.
+ALTER TABLE quiz_sessions ADD COLUMN seed BIGINT;
@@ -0,0 +1,30 @@
+-- Create enum for background job states
+CREATE TYPE job_status AS ENUM ('pending', 'running', 'completed', 'failed');
+
+-- Create regrade jobs table
+CREATE TABLE regrade_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    status job_status NOT NULL DEFAULT 'pending',
+    sessions_checked INTEGER NOT NULL DEFAULT 0,
+    sessions_adjusted INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Create score adjustments table, one row per session whose score changed
+CREATE TABLE score_adjustments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL REFERENCES regrade_jobs(id) ON DELETE CASCADE,
+    session_id UUID NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    previous_score INTEGER,
+    new_score INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_regrade_jobs_question_id ON regrade_jobs(question_id);
+CREATE INDEX idx_score_adjustments_job_id ON score_adjustments(job_id);
+CREATE INDEX idx_score_adjustments_session_id ON score_adjustments(session_id);
+CREATE INDEX idx_quiz_sessions_question_ids ON quiz_sessions USING GIN (question_ids jsonb_path_ops);
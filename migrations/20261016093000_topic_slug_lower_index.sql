@@ -0,0 +1,2 @@
+-- Slug lookups are case-insensitive
+CREATE INDEX idx_topics_slug_lower ON topics(LOWER(slug));
@@ -0,0 +1,33 @@
+-- Create deletion log (tombstones) for delta sync and cache invalidation
+CREATE TABLE deleted_records (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    entity_type VARCHAR(50) NOT NULL,
+    entity_id UUID NOT NULL,
+    deleted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_deleted_records_type_deleted_at ON deleted_records(entity_type, deleted_at);
+
+-- Create tombstone trigger function; the entity type is passed as a trigger argument
+CREATE OR REPLACE FUNCTION record_deletion()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO deleted_records (entity_type, entity_id) VALUES (TG_ARGV[0], OLD.id);
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Create triggers for deletions (also fire for cascaded deletes)
+CREATE TRIGGER record_topics_deletion
+AFTER DELETE ON topics
+FOR EACH ROW
+EXECUTE FUNCTION record_deletion('topic');
+
+CREATE TRIGGER record_questions_deletion
+AFTER DELETE ON questions
+FOR EACH ROW
+EXECUTE FUNCTION record_deletion('question');
+
+-- Index updated_at for delta queries
+CREATE INDEX idx_topics_updated_at ON topics(updated_at);
+CREATE INDEX idx_questions_updated_at ON questions(updated_at);
@@ -0,0 +1,40 @@
+-- Per-answer correctness, recorded when a session is graded. Labels in stored answers are
+-- relative to the session's option shuffle, so correctness cannot be derived in SQL.
+-- Sessions submitted before this migration stay NULL until they are regraded.
+ALTER TABLE quiz_session_answers ADD COLUMN is_correct BOOLEAN;
+
+CREATE INDEX idx_quiz_session_answers_question_id ON quiz_session_answers(question_id);
+
+-- Create per-question answer statistics
+CREATE MATERIALIZED VIEW question_stats AS
+SELECT
+    q.id AS question_id,
+    q.topic_id,
+    q.question_number,
+    COUNT(a.is_correct) AS attempts,
+    COUNT(*) FILTER (WHERE a.is_correct) AS correct,
+    (COUNT(*) FILTER (WHERE a.is_correct)::float8 / NULLIF(COUNT(a.is_correct), 0)) AS accuracy,
+    NOW() AS refreshed_at
+FROM questions q
+LEFT JOIN quiz_session_answers a ON a.question_id = q.id
+GROUP BY q.id, q.topic_id, q.question_number;
+
+CREATE UNIQUE INDEX idx_question_stats_question_id ON question_stats(question_id);
+CREATE INDEX idx_question_stats_topic_id ON question_stats(topic_id);
+
+-- Create per-topic accuracy
+CREATE MATERIALIZED VIEW topic_accuracy AS
+SELECT
+    t.id AS topic_id,
+    t.name AS topic_name,
+    (SELECT COUNT(*) FROM quiz_sessions s WHERE s.topic_id = t.id AND s.status = 'submitted') AS sessions_submitted,
+    COUNT(a.is_correct) AS answers,
+    COUNT(*) FILTER (WHERE a.is_correct) AS correct,
+    (COUNT(*) FILTER (WHERE a.is_correct)::float8 / NULLIF(COUNT(a.is_correct), 0)) AS accuracy,
+    NOW() AS refreshed_at
+FROM topics t
+LEFT JOIN questions q ON q.topic_id = t.id
+LEFT JOIN quiz_session_answers a ON a.question_id = q.id
+GROUP BY t.id, t.name;
+
+CREATE UNIQUE INDEX idx_topic_accuracy_topic_id ON topic_accuracy(topic_id);
@@ -0,0 +1,12 @@
+-- Create scheduled content freezes; questions in a frozen topic cannot be changed while the window is active
+CREATE TABLE content_freezes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    topic_id UUID NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX idx_content_freezes_topic_window ON content_freezes(topic_id, starts_at, ends_at);
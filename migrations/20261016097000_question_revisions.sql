@@ -0,0 +1,35 @@
+-- Create revision history: the previous content of a question each time it changes
+CREATE TABLE question_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    changed_fields TEXT[] NOT NULL,
+    previous JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_question_revisions_question_id ON question_revisions(question_id, created_at);
+
+-- Create revision trigger function; updates that change nothing but updated_at are skipped
+CREATE OR REPLACE FUNCTION record_question_revision()
+RETURNS TRIGGER AS $$
+DECLARE
+    changed TEXT[];
+BEGIN
+    SELECT array_agg(n.key ORDER BY n.key) INTO changed
+    FROM jsonb_each(to_jsonb(NEW)) n
+    WHERE n.key NOT IN ('updated_at', 'created_at')
+      AND to_jsonb(OLD) -> n.key IS DISTINCT FROM n.value;
+
+    IF changed IS NOT NULL THEN
+        INSERT INTO question_revisions (question_id, changed_fields, previous)
+        VALUES (OLD.id, changed, to_jsonb(OLD));
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_questions_revision
+AFTER UPDATE ON questions
+FOR EACH ROW
+EXECUTE FUNCTION record_question_revision();
@@ -0,0 +1,12 @@
+-- Trigram indexes for prefix and fuzzy matching in search suggestions
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_topics_name_trgm ON topics USING GIN (name gin_trgm_ops);
+
+-- Create question term frequencies, refreshed in the background with the analytics views
+CREATE MATERIALIZED VIEW question_terms AS
+SELECT word AS term, ndoc AS question_count
+FROM ts_stat('SELECT to_tsvector(''simple'', question) FROM questions')
+WHERE length(word) >= 3 AND word ~ '^[a-z]';
+
+CREATE UNIQUE INDEX idx_question_terms_term ON question_terms(term text_pattern_ops);
@@ -0,0 +1,6 @@
+-- Full-text indexes for unified search; the expressions must match the queries exactly
+CREATE INDEX idx_questions_search ON questions
+USING GIN (to_tsvector('english', question || ' ' || COALESCE(explanation, '')));
+
+CREATE INDEX idx_topics_search ON topics
+USING GIN (to_tsvector('english', name || ' ' || COALESCE(description, '')));
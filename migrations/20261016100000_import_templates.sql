@@ -0,0 +1,14 @@
+-- Create saved CSV column mappings, one per vendor spreadsheet layout
+CREATE TABLE import_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    mapping JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TRIGGER update_import_templates_updated_at
+BEFORE UPDATE ON import_templates
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,19 @@
+-- Create import history with the rows that failed, so they can be fixed and re-uploaded
+CREATE TYPE import_source AS ENUM ('bulk', 'csv');
+
+CREATE TABLE import_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    topic_id UUID REFERENCES topics(id) ON DELETE SET NULL,
+    source import_source NOT NULL,
+    source_filename TEXT,
+    template_name VARCHAR(255),
+    imported_by VARCHAR(255),
+    total_rows INTEGER NOT NULL,
+    created INTEGER NOT NULL,
+    failed INTEGER NOT NULL,
+    columns JSONB NOT NULL DEFAULT '[]',
+    failed_rows JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_import_runs_created_at ON import_runs(created_at DESC);
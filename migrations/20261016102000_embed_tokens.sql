@@ -0,0 +1,9 @@
+-- Create embed tokens: each lets one external domain iframe a sample quiz for a topic
+CREATE TABLE embed_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    token VARCHAR(32) NOT NULL UNIQUE,
+    topic_id UUID NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    domain VARCHAR(255) NOT NULL,
+    question_count INTEGER NOT NULL DEFAULT 5 CHECK (question_count BETWEEN 1 AND 20),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
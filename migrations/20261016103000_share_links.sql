@@ -0,0 +1,13 @@
+-- Create short share codes for questions and topics
+CREATE TYPE share_target AS ENUM ('question', 'topic');
+
+CREATE TABLE share_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(16) NOT NULL UNIQUE,
+    target_type share_target NOT NULL,
+    target_id UUID NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE,
+    access_count INTEGER NOT NULL DEFAULT 0,
+    last_accessed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,54 @@
+-- Create explanation votes: one thumbs-up or thumbs-down per voter and question
+CREATE TYPE vote_direction AS ENUM ('up', 'down');
+
+CREATE TABLE explanation_votes (
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    voter TEXT NOT NULL,
+    direction vote_direction NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, voter)
+);
+
+-- Aggregated on the question so reads don't need to count votes
+ALTER TABLE questions
+    ADD COLUMN explanation_upvotes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN explanation_downvotes INTEGER NOT NULL DEFAULT 0;
+
+-- Vote tallies are not content edits: they neither bump updated_at nor create revisions
+CREATE OR REPLACE FUNCTION update_questions_updated_at_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF to_jsonb(NEW) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes']
+       IS DISTINCT FROM to_jsonb(OLD) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes'] THEN
+        NEW.updated_at = NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER update_questions_updated_at ON questions;
+
+CREATE TRIGGER update_questions_updated_at
+BEFORE UPDATE ON questions
+FOR EACH ROW
+EXECUTE FUNCTION update_questions_updated_at_column();
+
+CREATE OR REPLACE FUNCTION record_question_revision()
+RETURNS TRIGGER AS $$
+DECLARE
+    changed TEXT[];
+BEGIN
+    SELECT array_agg(n.key ORDER BY n.key) INTO changed
+    FROM jsonb_each(to_jsonb(NEW)) n
+    WHERE n.key NOT IN ('updated_at', 'created_at', 'explanation_upvotes', 'explanation_downvotes')
+      AND to_jsonb(OLD) -> n.key IS DISTINCT FROM n.value;
+
+    IF changed IS NOT NULL THEN
+        INSERT INTO question_revisions (question_id, changed_fields, previous)
+        VALUES (OLD.id, changed, to_jsonb(OLD));
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
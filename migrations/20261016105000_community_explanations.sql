@@ -0,0 +1,33 @@
+-- Create community explanations: alternative explanations contributed per question
+CREATE TABLE question_explanations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    author TEXT NOT NULL,
+    body TEXT NOT NULL,
+    upvotes INTEGER NOT NULL DEFAULT 0,
+    downvotes INTEGER NOT NULL DEFAULT 0,
+    approved BOOLEAN NOT NULL DEFAULT FALSE,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK (approved OR NOT is_primary)
+);
+
+CREATE INDEX idx_question_explanations_question_id ON question_explanations(question_id);
+
+-- At most one primary explanation per question
+CREATE UNIQUE INDEX idx_question_explanations_primary ON question_explanations(question_id) WHERE is_primary;
+
+CREATE TRIGGER update_question_explanations_updated_at
+BEFORE UPDATE ON question_explanations
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE question_explanation_votes (
+    explanation_id UUID NOT NULL REFERENCES question_explanations(id) ON DELETE CASCADE,
+    voter TEXT NOT NULL,
+    direction vote_direction NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (explanation_id, voter)
+);
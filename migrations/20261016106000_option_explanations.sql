@@ -0,0 +1,2 @@
+-- Why each option is right or wrong, keyed by option label: {"A": "...", "C": "..."}
+ALTER TABLE questions ADD COLUMN option_explanations JSONB;
@@ -0,0 +1,15 @@
+-- Ordered hints per question, revealed one at a time during a session
+ALTER TABLE questions ADD COLUMN hints JSONB;
+
+-- Points deducted from the score per hint revealed, and how many were revealed
+ALTER TABLE quiz_sessions
+    ADD COLUMN hint_penalty DOUBLE PRECISION NOT NULL DEFAULT 0,
+    ADD COLUMN hints_used INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE quiz_session_hints (
+    session_id UUID NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    revealed INTEGER NOT NULL DEFAULT 0,
+    revealed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, question_id)
+);
@@ -0,0 +1,19 @@
+-- How sure the learner was of each answer, 1 (guessing) to 5 (certain)
+ALTER TABLE quiz_session_answers
+    ADD COLUMN confidence SMALLINT CHECK (confidence BETWEEN 1 AND 5);
+
+-- Create confidence calibration: accuracy at each confidence level per topic
+CREATE MATERIALIZED VIEW confidence_calibration AS
+SELECT
+    q.topic_id,
+    a.confidence,
+    COUNT(a.is_correct) AS answers,
+    COUNT(*) FILTER (WHERE a.is_correct) AS correct,
+    (COUNT(*) FILTER (WHERE a.is_correct)::float8 / NULLIF(COUNT(a.is_correct), 0)) AS accuracy,
+    NOW() AS refreshed_at
+FROM quiz_session_answers a
+JOIN questions q ON q.id = a.question_id
+WHERE a.confidence IS NOT NULL
+GROUP BY q.topic_id, a.confidence;
+
+CREATE UNIQUE INDEX idx_confidence_calibration_topic_confidence ON confidence_calibration(topic_id, confidence);
@@ -0,0 +1,7 @@
+-- Create per-session question bookmarks ("mark for review")
+CREATE TABLE quiz_session_marks (
+    session_id UUID NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    marked_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, question_id)
+);
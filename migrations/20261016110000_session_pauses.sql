@@ -0,0 +1,7 @@
+-- Timed mocks and pause accounting. Untimed sessions can pause freely; timed ones only
+-- within their pause budget, and pausing past it keeps the clock running.
+ALTER TABLE quiz_sessions
+    ADD COLUMN time_limit_secs INTEGER,
+    ADD COLUMN pause_budget_secs INTEGER,
+    ADD COLUMN paused_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN paused_secs INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,36 @@
+-- Learner id in the launching LMS; only sessions that carry one have grades passed back
+ALTER TABLE quiz_sessions ADD COLUMN learner_ref TEXT;
+
+-- Create grade passback targets: where a topic's session scores are reported
+CREATE TABLE grade_passback_targets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    topic_id UUID NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    endpoint_url TEXT NOT NULL,
+    -- Shared secret for the HMAC-SHA256 payload signature
+    secret TEXT NOT NULL,
+    -- Assignment or line item id on the LMS side
+    assignment_ref TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_grade_passback_targets_topic_id ON grade_passback_targets(topic_id);
+
+CREATE TYPE delivery_status AS ENUM ('pending', 'delivered', 'failed');
+
+-- Create the delivery queue, retried with backoff until delivered or out of attempts
+CREATE TABLE grade_passback_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_id UUID NOT NULL REFERENCES grade_passback_targets(id) ON DELETE CASCADE,
+    session_id UUID NOT NULL REFERENCES quiz_sessions(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    status delivery_status NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    delivered_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (target_id, session_id)
+);
+
+CREATE INDEX idx_grade_passback_deliveries_due ON grade_passback_deliveries(next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,26 @@
+-- Topics approved for the public read-only API
+ALTER TABLE topics ADD COLUMN is_public BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Create API keys for third-party read-only access
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    -- SHA-256 of the key; the key itself is only shown once, at creation
+    key_hash TEXT NOT NULL UNIQUE,
+    -- First characters of the key, to tell keys apart
+    key_prefix TEXT NOT NULL,
+    daily_quota INTEGER NOT NULL CHECK (daily_quota > 0),
+    include_answers BOOLEAN NOT NULL DEFAULT FALSE,
+    include_explanations BOOLEAN NOT NULL DEFAULT FALSE,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Requests per key per UTC day, counted against the daily quota
+CREATE TABLE api_key_usage (
+    key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (key_id, day)
+);
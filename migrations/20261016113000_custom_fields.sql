@@ -0,0 +1,17 @@
+CREATE TYPE custom_field_type AS ENUM ('text', 'number', 'enum');
+
+-- Create custom field definitions; question metadata is validated against them
+CREATE TABLE custom_fields (
+    key TEXT PRIMARY KEY CHECK (key ~ '^[a-z][a-z0-9_]*$'),
+    label TEXT NOT NULL,
+    field_type custom_field_type NOT NULL,
+    -- Allowed values of an enum field
+    options JSONB,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Custom field values per question, keyed by custom_fields.key
+ALTER TABLE questions ADD COLUMN metadata JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_questions_metadata ON questions USING GIN (metadata jsonb_path_ops);
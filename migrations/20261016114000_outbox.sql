@@ -0,0 +1,73 @@
+-- Create the transactional outbox: content changes are recorded by triggers in the same
+-- transaction as the change itself, so a crash after commit cannot lose a notification
+CREATE TABLE outbox_events (
+    id BIGSERIAL PRIMARY KEY,
+    -- Delivery order, assigned by the dispatcher after commit; NULL until then. Consumers
+    -- page by seq, so a transaction that commits late is never skipped.
+    seq BIGINT UNIQUE,
+    event_type TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_outbox_events_unsequenced ON outbox_events(id) WHERE seq IS NULL;
+CREATE INDEX idx_outbox_events_created_at ON outbox_events(created_at);
+
+-- Last assigned seq; its row lock serializes the dispatchers of several instances
+CREATE TABLE outbox_sequence (
+    last_seq BIGINT NOT NULL
+);
+
+INSERT INTO outbox_sequence (last_seq) VALUES (0);
+
+-- Create outbox trigger function; the entity type is passed as a trigger argument.
+-- Vote tallies and timestamp bumps are not content changes.
+CREATE OR REPLACE FUNCTION record_outbox_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO outbox_events (event_type, entity_type, entity_id, payload)
+        VALUES (TG_ARGV[0] || '.deleted', TG_ARGV[0], OLD.id, jsonb_build_object('id', OLD.id));
+        RETURN OLD;
+    END IF;
+
+    IF TG_OP = 'UPDATE'
+       AND to_jsonb(NEW) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes']
+           IS NOT DISTINCT FROM to_jsonb(OLD) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes'] THEN
+        RETURN NEW;
+    END IF;
+
+    INSERT INTO outbox_events (event_type, entity_type, entity_id, payload)
+    VALUES (TG_ARGV[0] || CASE TG_OP WHEN 'INSERT' THEN '.created' ELSE '.updated' END, TG_ARGV[0], NEW.id, to_jsonb(NEW));
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_topics_outbox_event
+AFTER INSERT OR UPDATE OR DELETE ON topics
+FOR EACH ROW
+EXECUTE FUNCTION record_outbox_event('topic');
+
+CREATE TRIGGER record_questions_outbox_event
+AFTER INSERT OR UPDATE OR DELETE ON questions
+FOR EACH ROW
+EXECUTE FUNCTION record_outbox_event('question');
+
+-- Create webhook subscriptions, each reading the outbox from its own cursor
+CREATE TABLE webhook_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    url TEXT NOT NULL,
+    -- Shared secret for the HMAC-SHA256 payload signature
+    secret TEXT NOT NULL,
+    -- Event types to deliver; NULL delivers every type
+    event_types TEXT[],
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Highest seq delivered (or skipped by the type filter)
+    last_seq BIGINT NOT NULL DEFAULT 0,
+    failures INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
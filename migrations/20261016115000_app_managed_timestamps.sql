@@ -0,0 +1,10 @@
+-- updated_at is now set explicitly by the application on every write that should bump it,
+-- so the timestamp no longer depends on triggers existing in the target database
+DROP TRIGGER IF EXISTS update_topics_updated_at ON topics;
+DROP TRIGGER IF EXISTS update_questions_updated_at ON questions;
+DROP TRIGGER IF EXISTS update_quiz_sessions_updated_at ON quiz_sessions;
+DROP TRIGGER IF EXISTS update_import_templates_updated_at ON import_templates;
+DROP TRIGGER IF EXISTS update_question_explanations_updated_at ON question_explanations;
+
+DROP FUNCTION IF EXISTS update_questions_updated_at_column();
+DROP FUNCTION IF EXISTS update_updated_at_column();
@@ -0,0 +1,2 @@
+-- Mark question text served to the key with an invisible per-key tag, to trace leaks
+ALTER TABLE api_keys ADD COLUMN watermark BOOLEAN NOT NULL DEFAULT FALSE;
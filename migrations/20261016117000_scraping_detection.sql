@@ -0,0 +1,30 @@
+-- Keys caught scraping are blocked until this time
+ALTER TABLE api_keys ADD COLUMN blocked_until TIMESTAMP WITH TIME ZONE;
+
+-- Question pages served through the public API, kept for the detection window
+CREATE TABLE api_key_reads (
+    id BIGSERIAL PRIMARY KEY,
+    key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    topic_id UUID NOT NULL REFERENCES topics(id) ON DELETE CASCADE,
+    -- Positions served, in question_number order: [first_position, first_position + served)
+    first_position INTEGER NOT NULL,
+    served INTEGER NOT NULL,
+    -- Whether the page carried correct answers
+    with_answers BOOLEAN NOT NULL,
+    read_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_api_key_reads_key_read_at ON api_key_reads(key_id, read_at);
+
+-- Read patterns that tripped a detector
+CREATE TABLE scraping_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    -- 'bank_scrape' or 'answer_harvest'
+    kind TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    blocked_until TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_scraping_alerts_created_at ON scraping_alerts(created_at DESC);
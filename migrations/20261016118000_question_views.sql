@@ -0,0 +1,14 @@
+-- Questions served through the public API, counted per key and UTC day
+CREATE TABLE question_views (
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    views INTEGER NOT NULL DEFAULT 1,
+    -- Whether the correct answer was served at least once that day
+    with_answers BOOLEAN NOT NULL DEFAULT FALSE,
+    first_viewed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_viewed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, key_id, day)
+);
+
+CREATE INDEX idx_question_views_key_day ON question_views(key_id, day);
@@ -0,0 +1,3 @@
+-- Template questions: placeholder variables and how each is generated. Every quiz
+-- session gets its own variant, derived from the session seed.
+ALTER TABLE questions ADD COLUMN variables JSONB;
@@ -0,0 +1,3 @@
+-- Code snippets shown with a question: language, content and highlighted lines,
+-- kept apart from the question text
+ALTER TABLE questions ADD COLUMN code_blocks JSONB;
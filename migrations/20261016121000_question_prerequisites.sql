@@ -0,0 +1,11 @@
+-- Question B assumes knowledge tested by question A. Both belong to the same topic;
+-- cycles are rejected when prerequisites are set.
+CREATE TABLE question_prerequisites (
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    prerequisite_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, prerequisite_id),
+    CHECK (question_id <> prerequisite_id)
+);
+
+CREATE INDEX idx_question_prerequisites_prerequisite ON question_prerequisites(prerequisite_id);
@@ -0,0 +1,7 @@
+-- Bloom's taxonomy level a question tests; optional, used for filtering, level mixes in
+-- quiz sessions and accuracy by level
+CREATE TYPE cognitive_level AS ENUM ('remember', 'understand', 'apply', 'analyze', 'evaluate', 'create');
+
+ALTER TABLE questions ADD COLUMN cognitive_level cognitive_level;
+
+CREATE INDEX idx_questions_cognitive_level ON questions(cognitive_level) WHERE cognitive_level IS NOT NULL;
@@ -0,0 +1,25 @@
+-- Words in the question, options and explanation and the time to read them at 200 words
+-- per minute. The application sets both on every write; existing rows are backfilled here.
+ALTER TABLE questions
+    ADD COLUMN word_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN estimated_secs INTEGER NOT NULL DEFAULT 0;
+
+-- The backfill is not a content change, so keep it out of the revision history
+ALTER TABLE questions DISABLE TRIGGER record_questions_revision;
+
+UPDATE questions q SET word_count = counted.words, estimated_secs = CEIL(counted.words * 60 / 200.0)
+FROM (
+    SELECT q2.id, (
+        SELECT COUNT(*)
+        FROM regexp_split_to_table(
+            q2.question || ' ' || q2.explanation || ' ' ||
+                COALESCE((SELECT string_agg(o, ' ') FROM jsonb_array_elements_text(q2.options) o), ''),
+            '\s+'
+        ) AS word
+        WHERE word <> ''
+    ) AS words
+    FROM questions q2
+) counted
+WHERE counted.id = q.id;
+
+ALTER TABLE questions ENABLE TRIGGER record_questions_revision;
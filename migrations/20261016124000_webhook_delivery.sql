@@ -0,0 +1,5 @@
+-- How a webhook receives events: in batches, or one request per event in the shape of
+-- the trigger feed, as automation platforms expect
+CREATE TYPE webhook_delivery AS ENUM ('batch', 'event');
+
+ALTER TABLE webhook_subscriptions ADD COLUMN delivery webhook_delivery NOT NULL DEFAULT 'batch';
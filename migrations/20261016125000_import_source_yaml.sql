@@ -0,0 +1,2 @@
+-- Question files: YAML front matter with Markdown bodies
+ALTER TYPE import_source ADD VALUE 'yaml';
@@ -0,0 +1,47 @@
+-- Diagram sources found in question code blocks (`mermaid`, and `graphviz` or `dot`),
+-- keyed by a hash of renderer and source, with the SVG once a renderer has drawn it
+CREATE TABLE diagrams (
+    hash TEXT PRIMARY KEY,
+    renderer TEXT NOT NULL CHECK (renderer IN ('mermaid', 'graphviz')),
+    source TEXT NOT NULL,
+    svg TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    rendered_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_diagrams_pending ON diagrams(next_attempt_at) WHERE svg IS NULL;
+
+-- The diagram blocks of a code_blocks array; the hash matches `diagram_hash` in the app
+CREATE OR REPLACE FUNCTION diagram_blocks(code_blocks JSONB)
+RETURNS TABLE (hash TEXT, renderer TEXT, source TEXT) AS $$
+    SELECT DISTINCT encode(sha256(convert_to(r.renderer || E'\n' || (b->>'content'), 'UTF8')), 'hex'), r.renderer, b->>'content'
+    FROM jsonb_array_elements(COALESCE(code_blocks, '[]'::jsonb)) b
+    CROSS JOIN LATERAL (
+        SELECT CASE b->>'language' WHEN 'mermaid' THEN 'mermaid' WHEN 'graphviz' THEN 'graphviz' WHEN 'dot' THEN 'graphviz' END AS renderer
+    ) r
+    WHERE r.renderer IS NOT NULL
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Queue new diagram sources for rendering whenever a question's code blocks are written
+CREATE OR REPLACE FUNCTION record_question_diagrams()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO diagrams (hash, renderer, source)
+    SELECT * FROM diagram_blocks(NEW.code_blocks)
+    ON CONFLICT (hash) DO NOTHING;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_question_diagrams
+AFTER INSERT OR UPDATE OF code_blocks ON questions
+FOR EACH ROW
+WHEN (NEW.code_blocks IS NOT NULL)
+EXECUTE FUNCTION record_question_diagrams();
+
+INSERT INTO diagrams (hash, renderer, source)
+SELECT d.* FROM questions q CROSS JOIN LATERAL diagram_blocks(q.code_blocks) d
+ON CONFLICT (hash) DO NOTHING;
@@ -0,0 +1,33 @@
+-- Certification hierarchy: provider -> certification -> topic
+CREATE TABLE providers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL UNIQUE,
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+    website_url TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- A provider with certifications can only be deleted together with them, on request
+CREATE TABLE certifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider_id UUID NOT NULL REFERENCES providers(id) ON DELETE RESTRICT,
+    name VARCHAR(100) NOT NULL,
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    -- The provider's exam code, e.g. SAA-C03
+    exam_code VARCHAR(50),
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT certifications_provider_name_key UNIQUE (provider_id, name)
+);
+
+CREATE INDEX idx_certifications_provider_id ON certifications(provider_id);
+CREATE INDEX idx_providers_slug_lower ON providers(LOWER(slug));
+CREATE INDEX idx_certifications_slug_lower ON certifications(LOWER(slug));
+
+-- Topics outlive their certification; deleting one only unlinks its topics
+ALTER TABLE topics ADD COLUMN certification_id UUID REFERENCES certifications(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_topics_certification_id ON topics(certification_id);
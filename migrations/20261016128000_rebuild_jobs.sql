@@ -0,0 +1,62 @@
+-- Derived data that can be recomputed on request
+CREATE TYPE rebuild_target AS ENUM ('search_index', 'stats', 'counts');
+
+-- Create rebuild jobs: one per target, with progress
+CREATE TABLE rebuild_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target rebuild_target NOT NULL,
+    status job_status NOT NULL DEFAULT 'pending',
+    -- Steps or rows to process; NULL until the job has counted them
+    items_total INTEGER,
+    items_done INTEGER NOT NULL DEFAULT 0,
+    -- Rows whose stored value was wrong and has been corrected
+    items_fixed INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_rebuild_jobs_created_at ON rebuild_jobs(created_at);
+
+-- Reading stats are derived from the content, so correcting them is not a content change:
+-- it creates no revision and no outbox event
+CREATE OR REPLACE FUNCTION record_question_revision()
+RETURNS TRIGGER AS $$
+DECLARE
+    changed TEXT[];
+BEGIN
+    SELECT array_agg(n.key ORDER BY n.key) INTO changed
+    FROM jsonb_each(to_jsonb(NEW)) n
+    WHERE n.key NOT IN ('updated_at', 'created_at', 'explanation_upvotes', 'explanation_downvotes', 'word_count', 'estimated_secs')
+      AND to_jsonb(OLD) -> n.key IS DISTINCT FROM n.value;
+
+    IF changed IS NOT NULL THEN
+        INSERT INTO question_revisions (question_id, changed_fields, previous)
+        VALUES (OLD.id, changed, to_jsonb(OLD));
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION record_outbox_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO outbox_events (event_type, entity_type, entity_id, payload)
+        VALUES (TG_ARGV[0] || '.deleted', TG_ARGV[0], OLD.id, jsonb_build_object('id', OLD.id));
+        RETURN OLD;
+    END IF;
+
+    IF TG_OP = 'UPDATE'
+       AND to_jsonb(NEW) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes', 'word_count', 'estimated_secs']
+           IS NOT DISTINCT FROM to_jsonb(OLD) - ARRAY['updated_at', 'explanation_upvotes', 'explanation_downvotes', 'word_count', 'estimated_secs'] THEN
+        RETURN NEW;
+    END IF;
+
+    INSERT INTO outbox_events (event_type, entity_type, entity_id, payload)
+    VALUES (TG_ARGV[0] || CASE TG_OP WHEN 'INSERT' THEN '.created' ELSE '.updated' END, TG_ARGV[0], NEW.id, to_jsonb(NEW));
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,2 @@
+-- Question files of flat rows, uploaded as JSON (CSV uploads record as 'csv')
+ALTER TYPE import_source ADD VALUE 'json';
@@ -0,0 +1,10 @@
+-- Match template names ignoring case: /api paths arrive lowercased, so a name saved as
+-- "Vendor-X" must be found as "vendor-x". Of names that differ only by case, keep the
+-- most recently saved template.
+DELETE FROM import_templates a
+USING import_templates b
+WHERE LOWER(a.name) = LOWER(b.name)
+  AND (a.updated_at, a.id) < (b.updated_at, b.id);
+
+ALTER TABLE import_templates DROP CONSTRAINT import_templates_name_key;
+CREATE UNIQUE INDEX import_templates_name_lower_key ON import_templates (LOWER(name));
@@ -0,0 +1,6 @@
+-- Let a content freeze cover a whole certification: every topic linked to it is frozen
+ALTER TABLE content_freezes ALTER COLUMN topic_id DROP NOT NULL;
+ALTER TABLE content_freezes ADD COLUMN certification_id UUID REFERENCES certifications(id) ON DELETE CASCADE;
+ALTER TABLE content_freezes ADD CONSTRAINT content_freezes_one_target CHECK (num_nonnulls(topic_id, certification_id) = 1);
+
+CREATE INDEX idx_content_freezes_certification_window ON content_freezes(certification_id, starts_at, ends_at);
@@ -0,0 +1,11 @@
+-- The official exam-guide order of a certification's questions. Questions without a
+-- position follow the ordered ones in topic and number order.
+CREATE TABLE certification_question_order (
+    certification_id UUID NOT NULL REFERENCES certifications(id) ON DELETE CASCADE,
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (certification_id, question_id),
+    UNIQUE (certification_id, position)
+);
+
+CREATE INDEX idx_certification_question_order_question ON certification_question_order(question_id);
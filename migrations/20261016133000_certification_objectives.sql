@@ -0,0 +1,21 @@
+-- Objectives from a certification's official exam guide. Codes are dotted ("2", "2.1",
+-- "2.1.3"): an objective sits under the one whose code is its code minus the last part.
+CREATE TABLE certification_objectives (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    certification_id UUID NOT NULL REFERENCES certifications(id) ON DELETE CASCADE,
+    code VARCHAR(50) NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT certification_objectives_code_key UNIQUE (certification_id, code)
+);
+
+-- The objectives a question tests, from the certification of the question's topic
+CREATE TABLE question_objectives (
+    question_id UUID NOT NULL REFERENCES questions(id) ON DELETE CASCADE,
+    objective_id UUID NOT NULL REFERENCES certification_objectives(id) ON DELETE CASCADE,
+    PRIMARY KEY (question_id, objective_id)
+);
+
+CREATE INDEX idx_question_objectives_objective ON question_objectives(objective_id);
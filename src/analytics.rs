@@ -0,0 +1,48 @@
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Materialized views behind the analytics endpoints
+pub const STATS_VIEWS: [&str; 3] = ["question_stats", "topic_accuracy", "confidence_calibration"];
+
+/// Materialized view behind search suggestions
+pub const TERMS_VIEW: &str = "question_terms";
+
+/// Refresh the analytics views every `ANALYTICS_REFRESH_SECS` (default 900)
+pub fn spawn(pool: PgPool) {
+    let interval = env::var("ANALYTICS_REFRESH_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(900);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+
+        loop {
+            ticker.tick().await;
+
+            match refresh_views(&pool).await {
+                Ok(()) => info!("Refreshed analytics views"),
+                Err(e) => error!("Failed to refresh analytics views: {}", e),
+            }
+        }
+    });
+}
+
+/// Recompute the views without blocking readers
+pub async fn refresh_views(pool: &PgPool) -> Result<(), sqlx::Error> {
+    for view in STATS_VIEWS.into_iter().chain([TERMS_VIEW]) {
+        refresh_view(pool, view).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn refresh_view(pool: &PgPool, view: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", view))
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use axum::body::Bytes;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::env;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::models::{ApiResponse, Catalog, CatalogTopic};
+
+/// The serialized catalog response, shared by every anonymous request until it expires
+/// or content changes
+static CACHE: LazyLock<RwLock<Option<Arc<CachedCatalog>>>> = LazyLock::new(|| RwLock::new(None));
+
+pub struct CachedCatalog {
+    pub catalog: Catalog,
+    // The catalog wrapped in the API envelope, ready to send
+    pub body: Bytes,
+    pub etag: String,
+    built_at: Instant,
+}
+
+/// Drop the cached catalog; call after any change to topics or questions
+pub fn invalidate() {
+    *CACHE.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// The cached catalog, rebuilt when missing or older than `CATALOG_CACHE_SECS` (default 300)
+pub async fn load(pool: &PgPool) -> anyhow::Result<Arc<CachedCatalog>> {
+    let cached = CACHE.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(catalog) = cached
+        && catalog.built_at.elapsed() < ttl()
+    {
+        return Ok(catalog);
+    }
+
+    let catalog = Arc::new(build(pool).await?);
+    *CACHE.write().unwrap_or_else(|e| e.into_inner()) = Some(catalog.clone());
+
+    Ok(catalog)
+}
+
+fn ttl() -> Duration {
+    let secs = env::var("CATALOG_CACHE_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(300);
+
+    Duration::from_secs(secs)
+}
+
+async fn build(pool: &PgPool) -> anyhow::Result<CachedCatalog> {
+    let topics = sqlx::query_as::<_, CatalogTopic>(
+        "SELECT t.name, t.slug, t.description,
+            COUNT(q.id) AS question_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'easy') AS easy_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'medium') AS medium_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'hard') AS hard_count,
+            GREATEST(t.updated_at, MAX(q.updated_at)) AS last_modified
+         FROM topics t
+         LEFT JOIN questions q ON q.topic_id = t.id
+         GROUP BY t.id
+         ORDER BY t.name"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // The ETag covers the content only, so a rebuild without changes keeps it
+    let etag = format!("\"{}\"", &hex::encode(Sha256::digest(serde_json::to_vec(&topics)?))[..32]);
+
+    let catalog = Catalog {
+        generated_at: Utc::now(),
+        topic_count: topics.len(),
+        question_count: topics.iter().map(|t| t.question_count).sum(),
+        topics,
+    };
+
+    Ok(CachedCatalog {
+        body: Bytes::from(serde_json::to_vec(&ApiResponse::success(&catalog))?),
+        catalog,
+        etag,
+        built_at: Instant::now(),
+    })
+}
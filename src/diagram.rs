@@ -0,0 +1,138 @@
+use anyhow::bail;
+use axum::{body::Bytes, http::{header, Request}};
+use chrono::{Duration as ChronoDuration, Utc};
+use http_body_util::Full;
+use sqlx::{prelude::FromRow, PgPool};
+use std::env;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::http_client::{self, HttpClient};
+
+/// Diagrams are left unrendered after this many failed attempts
+const MAX_ATTEMPTS: i32 = 5;
+
+/// Diagrams rendered per tick
+const BATCH_SIZE: i64 = 20;
+
+/// Largest SVG accepted from the renderer
+const MAX_SVG_BYTES: usize = 1024 * 1024;
+
+#[derive(FromRow)]
+struct PendingDiagram {
+    hash: String,
+    renderer: String,
+    source: String,
+    attempts: i32,
+}
+
+/// Render queued diagram sources to SVG every `DIAGRAM_INTERVAL_SECS` (default 10) with
+/// the Kroki-compatible service at `DIAGRAM_RENDERER_URL` (e.g. `http://kroki:8000`).
+/// Does nothing when no renderer is configured.
+pub fn spawn(pool: PgPool) {
+    let Ok(renderer_url) = env::var("DIAGRAM_RENDERER_URL") else {
+        return;
+    };
+
+    let interval = env::var("DIAGRAM_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(10);
+
+    info!("Rendering diagrams with {} every {}s", renderer_url, interval);
+
+    tokio::spawn(async move {
+        let client = HttpClient::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+
+        loop {
+            ticker.tick().await;
+
+            match render_pending(&pool, &client, &renderer_url).await {
+                Ok(0) => {}
+                Ok(rendered) => info!("Rendered {} diagrams", rendered),
+                Err(e) => error!("Diagram rendering failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Claim pending diagrams with a lease, so an instance that dies mid-render lets another retry
+async fn render_pending(pool: &PgPool, client: &HttpClient, renderer_url: &str) -> anyhow::Result<usize> {
+    let pending = sqlx::query_as::<_, PendingDiagram>(
+        "UPDATE diagrams SET next_attempt_at = NOW() + INTERVAL '5 minutes'
+         WHERE hash IN (
+            SELECT hash FROM diagrams
+            WHERE svg IS NULL AND attempts < $1 AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+         )
+         RETURNING hash, renderer, source, attempts"
+    )
+    .bind(MAX_ATTEMPTS)
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let mut rendered = 0;
+
+    for diagram in &pending {
+        match render(client, renderer_url, diagram).await {
+            Ok(svg) => {
+                rendered += 1;
+
+                sqlx::query(
+                    "UPDATE diagrams SET svg = $1, attempts = attempts + 1, last_error = NULL, rendered_at = NOW()
+                     WHERE hash = $2"
+                )
+                .bind(svg)
+                .bind(&diagram.hash)
+                .execute(pool)
+                .await?;
+            }
+            Err(e) => {
+                let attempts = diagram.attempts + 1;
+                warn!("Diagram {} attempt {} failed: {}", diagram.hash, attempts, e);
+
+                // Exponential backoff from 30 seconds; invalid sources fail every time
+                let backoff = ChronoDuration::seconds(30i64 << (attempts - 1).min(10));
+
+                sqlx::query(
+                    "UPDATE diagrams SET attempts = $1, last_error = $2, next_attempt_at = $3 WHERE hash = $4"
+                )
+                .bind(attempts)
+                .bind(e.to_string())
+                .bind(Utc::now() + backoff)
+                .bind(&diagram.hash)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    Ok(rendered)
+}
+
+/// `POST {renderer_url}/{renderer}/svg` with the source as the body, as Kroki takes it
+async fn render(client: &HttpClient, renderer_url: &str, diagram: &PendingDiagram) -> anyhow::Result<String> {
+    let url = format!("{}/{}/svg", renderer_url.trim_end_matches('/'), diagram.renderer);
+    let uri = http_client::parse_endpoint(&url)?;
+
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Full::new(Bytes::from(diagram.source.clone())))?;
+
+    let response = client.send_limited(request, MAX_SVG_BYTES).await?;
+    if !response.status.is_success() {
+        bail!("{} returned {}", url, response.status);
+    }
+
+    let svg = String::from_utf8(response.body.to_vec())?;
+
+    if !svg.contains("<svg") {
+        bail!("{} did not return an SVG", url);
+    }
+
+    Ok(svg)
+}
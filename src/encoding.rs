@@ -0,0 +1,132 @@
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+
+// === CSV ===
+
+/// Flatten a list of JSON objects into CSV rows. Nested objects become dotted columns
+/// (`options.A`), arrays of scalars are joined with `;`, and columns appear in the order
+/// they are first seen.
+pub fn to_csv(rows: &[Value]) -> anyhow::Result<String> {
+    let flattened: Vec<Map<String, Value>> = rows
+        .iter()
+        .map(|row| {
+            let mut flat = Map::new();
+            flatten("", row, &mut flat);
+            flat
+        })
+        .collect();
+
+    let mut columns: Vec<&String> = Vec::new();
+    for row in &flattened {
+        for key in row.keys() {
+            if !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+
+    writer.write_record(&columns)?;
+    for row in &flattened {
+        writer.write_record(columns.iter().map(|c| row.get(*c).map(csv_cell).unwrap_or_default()))?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Map<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for key in keys {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&name, &map[key], out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(|i| !i.is_object() && !i.is_array()) => {
+            items.iter().map(csv_cell).collect::<Vec<_>>().join(";")
+        }
+        other => other.to_string(),
+    }
+}
+
+// === MessagePack ===
+
+/// Encode a JSON value as MessagePack, with objects as maps keyed by field name
+pub fn to_msgpack(value: &Value) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    rmp_serde::to_vec_named(value)
+}
+
+// === XML ===
+
+/// Escape text for use in XML content and attribute values
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// === QR codes ===
+
+/// Modules of blank border around a QR code, as the spec requires
+const QR_QUIET_ZONE: usize = 4;
+
+/// Render `data` as a black-on-white QR code PNG, `scale` pixels per module
+pub fn to_qr_png(data: &str, scale: usize) -> anyhow::Result<Vec<u8>> {
+    let code = qrcode::QrCode::new(data.as_bytes())?;
+    let modules = code.width();
+    let colors = code.to_colors();
+
+    let size = (modules + 2 * QR_QUIET_ZONE) * scale;
+    let mut pixels = vec![0xffu8; size * size];
+    for (index, color) in colors.iter().enumerate() {
+        if *color != qrcode::Color::Dark {
+            continue;
+        }
+
+        let x = (index % modules + QR_QUIET_ZONE) * scale;
+        let y = (index / modules + QR_QUIET_ZONE) * scale;
+        for row in y..y + scale {
+            pixels[row * size + x..row * size + x + scale].fill(0);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels)?;
+
+    Ok(out)
+}
+
+// === Signatures ===
+
+/// Hex HMAC-SHA256 of a request body, sent to webhook receivers as `X-Signature: sha256=<hex>`
+pub fn hmac_sha256_hex(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+
+    hex::encode(mac.finalize().into_bytes())
+}
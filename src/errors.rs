@@ -0,0 +1,37 @@
+use axum::{http::StatusCode, Json};
+use std::env;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use tracing::error;
+
+use crate::middleware::current_request_id;
+use crate::models::{ApiResponse, ErrorCode};
+
+/// Whether internal error details (SQL errors, constraint names) are passed through to
+/// clients. Off unless `ERROR_DETAILS=verbose`, which is meant for local development.
+static VERBOSE: LazyLock<bool> =
+    LazyLock::new(|| env::var("ERROR_DETAILS").is_ok_and(|v| v.eq_ignore_ascii_case("verbose")));
+
+/// Log an unexpected failure with the request ID and return the message to show the
+/// client: `context` (e.g. "Failed to fetch topics") and the request ID to quote when
+/// reporting it, plus the error itself in verbose mode.
+pub fn sanitize(context: &str, e: impl Display) -> String {
+    let request_id = current_request_id();
+    let reference = request_id.as_deref().unwrap_or("unknown");
+
+    error!(request_id = reference, "{}: {}", context, e);
+
+    if *VERBOSE {
+        format!("{}: {} (request {})", context, e, reference)
+    } else {
+        format!("{}. Please retry; if it keeps failing, report request {}", context, reference)
+    }
+}
+
+/// Map an unexpected failure to a 500 with a sanitized message
+pub fn internal(context: &str, e: impl Display) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::error(ErrorCode::InternalError, sanitize(context, e))),
+    )
+}
@@ -0,0 +1,558 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json
+};
+use chrono::Utc;
+use sqlx::{PgPool, types::Json as SqlxJson};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    Question, QuizSession, SessionAnswer, Topic,
+    RegradeJob, RegradeJobResponse, ScoreAdjustment,
+    RebuildJob, RebuildQuery, parse_rebuild_targets,
+    DiffSource, ExportManifest, ManifestDiff, QuestionChecksum, TopicManifest, TopicExport, ExportFormat, ExportQuery,
+    write_question_file,
+    BulkQuestionData, TimestampCheck, TimestampReport,
+    AccessibilityQuery, AccessibilityReport, PaginatedResponse, PaginationMeta, PageQuery,
+    ApplyAction, ApplyQuery, ApplyResult, ContentManifest, TopicSpec, plan_topics, validate_manifest,
+    ApiResponse, ApiWarning, ErrorCode,
+};
+use crate::handlers::freeze::ensure_not_frozen;
+use crate::catalog;
+use crate::http_client::{self, HttpClient};
+use crate::rebuild;
+use crate::sync;
+use crate::handlers::quiz::{grade_answers, record_grades, score};
+use crate::telemetry::{self, SlowQuery};
+
+/// Tables with an app-managed updated_at, and what else makes a row's timestamp stale
+const TIMESTAMP_RULES: &[(&str, &str)] = &[
+    ("topics", "FALSE"),
+    (
+        "questions",
+        "t.updated_at < (SELECT MAX(r.created_at) FROM question_revisions r WHERE r.question_id = t.id)",
+    ),
+    ("quiz_sessions", "t.updated_at < GREATEST(t.submitted_at, t.paused_at)"),
+    ("question_explanations", "FALSE"),
+    ("import_templates", "FALSE"),
+    ("providers", "FALSE"),
+    ("certifications", "FALSE"),
+];
+
+// Regrade handlers
+pub async fn regrade_question(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ApiResponse<RegradeJob>>), (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    }
+
+    let job = sqlx::query_as::<_, RegradeJob>(
+        "INSERT INTO regrade_jobs (question_id) VALUES ($1) RETURNING *"
+    )
+    .bind(id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create regrade job", e))?;
+
+    tokio::spawn(run_regrade_job(pool.clone(), job.id, id));
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(job))))
+}
+
+pub async fn get_regrade_job(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<RegradeJobResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let job = sqlx::query_as::<_, RegradeJob>("SELECT * FROM regrade_jobs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch regrade job", e))?;
+
+    let Some(job) = job else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::RegradeJobNotFound, "Regrade job not found".to_string())),
+        ));
+    };
+
+    let adjustments = sqlx::query_as::<_, ScoreAdjustment>(
+        "SELECT * FROM score_adjustments WHERE job_id = $1 ORDER BY created_at"
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch score adjustments", e))?;
+
+    Ok(Json(ApiResponse::success(RegradeJobResponse { job, adjustments })))
+}
+
+// Rebuild handlers
+/// Queue a job per target to recompute derived data, e.g. after editing rows by hand.
+/// No-op targets get a warning instead of a job. Not paged: there is one job per
+/// requested target.
+pub async fn rebuild_derived(
+    State(pool): State<PgPool>,
+    Query(query): Query<RebuildQuery>,
+) -> Result<(StatusCode, Json<ApiResponse<Vec<RebuildJob>>>), (StatusCode, Json<ApiResponse<()>>)> {
+    let (targets, skipped) = parse_rebuild_targets(query.targets.as_deref()).map_err(|message| {
+        (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, message)))
+    })?;
+    let warnings = skipped
+        .into_iter()
+        .map(|target| ApiWarning::new(
+            "NOTHING_TO_REBUILD",
+            "targets",
+            format!("No {} are stored, so no job was queued for them", target),
+        ))
+        .collect();
+
+    let jobs = sqlx::query_as::<_, RebuildJob>(
+        "INSERT INTO rebuild_jobs (target) SELECT * FROM UNNEST($1::rebuild_target[]) RETURNING *"
+    )
+    .bind(&targets)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create rebuild jobs", e))?;
+
+    for job in &jobs {
+        tokio::spawn(rebuild::run(pool.clone(), job.id, job.target));
+    }
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(jobs).with_warnings(warnings))))
+}
+
+pub async fn get_rebuild_job(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<RebuildJob>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let job = sqlx::query_as::<_, RebuildJob>("SELECT * FROM rebuild_jobs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch rebuild job", e))?;
+
+    match job {
+        Some(job) => Ok(Json(ApiResponse::success(job))),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::RebuildJobNotFound, "Rebuild job not found".to_string())),
+        )),
+    }
+}
+
+// Diagnostics handlers
+/// Recent slow queries. Not paged: only the last 100 are kept in memory.
+pub async fn get_slow_queries() -> Json<ApiResponse<Vec<SlowQuery>>> {
+    Json(ApiResponse::success(telemetry::recent_slow_queries()))
+}
+
+/// Report rows whose updated_at cannot be right: missing, before creation, in the
+/// future, or older than a later change recorded elsewhere
+pub async fn check_timestamps(
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<TimestampReport>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut checks = Vec::with_capacity(TIMESTAMP_RULES.len());
+
+    for &(table, extra) in TIMESTAMP_RULES {
+        let stale_rows = format!(
+            "SELECT id FROM {table} t
+             WHERE t.updated_at IS NULL OR t.updated_at < t.created_at OR t.updated_at > NOW() OR {extra}"
+        );
+
+        let (checked, stale, sample_ids): (i64, i64, Vec<Uuid>) = sqlx::query_as(&format!(
+            "SELECT
+                (SELECT COUNT(*) FROM {table}),
+                (SELECT COUNT(*) FROM ({stale_rows}) s),
+                ARRAY(SELECT id FROM ({stale_rows}) s ORDER BY id LIMIT 20)"
+        ))
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal(&format!("Failed to check {} timestamps", table), e))?;
+
+        checks.push(TimestampCheck { table, checked, stale, sample_ids });
+    }
+
+    let consistent = checks.iter().all(|c| c.stale == 0);
+
+    Ok(Json(ApiResponse::success(TimestampReport { consistent, checks })))
+}
+
+/// Questions with accessibility issues in their content, in topic and question order
+/// Questions with accessibility issues, a page at a time. Issues are found in Rust, so
+/// every question is checked and the flagged ones are paged afterwards.
+pub async fn get_accessibility_report(
+    State(pool): State<PgPool>,
+    Query(query): Query<AccessibilityQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<AccessibilityReport>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions WHERE ($1::uuid IS NULL OR topic_id = $1) ORDER BY topic_id, question_number"
+    )
+    .bind(query.topic_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let reports: Vec<AccessibilityReport> = questions
+        .iter()
+        .map(|q| AccessibilityReport {
+            question_id: q.id,
+            topic_id: q.topic_id,
+            question_number: q.question_number,
+            issues: q.accessibility_issues(),
+        })
+        .filter(|report| !report.issues.is_empty())
+        .collect();
+
+    let total_count = reports.len() as i64;
+    let items = reports.into_iter().skip(offset as usize).take(limit as usize).collect();
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+// Manifest and diff handlers
+pub async fn get_manifest(
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<ExportManifest>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let manifest = build_manifest(&pool).await?;
+
+    Ok(Json(ApiResponse::success(manifest)))
+}
+
+/// Diff this instance against a supplied manifest, or against the one fetched from another
+/// instance's `GET /api/admin/manifest`
+pub async fn diff_manifest(
+    State(pool): State<PgPool>,
+    Json(source): Json<DiffSource>,
+) -> Result<Json<ApiResponse<ManifestDiff>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let other = match source {
+        DiffSource::Manifest(manifest) => manifest,
+        DiffSource::Remote { url, api_key } => {
+            let manifest_url = format!("{}/api/admin/manifest", url.trim_end_matches('/'));
+            if http_client::parse_endpoint(&manifest_url).is_err() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationFailed, format!(
+                        "'{}' is not a valid instance URL; use an https:// or http:// URL",
+                        url
+                    ))),
+                ));
+            }
+
+            sync::fetch(&HttpClient::new(), &manifest_url, api_key.as_deref()).await.map_err(|e| (
+                StatusCode::BAD_GATEWAY,
+                Json(ApiResponse::error(ErrorCode::UpstreamFailed, format!("Failed to fetch the manifest: {}", e))),
+            ))?
+        }
+    };
+
+    let manifest = build_manifest(&pool).await?;
+
+    Ok(Json(ApiResponse::success(manifest.diff(&other))))
+}
+
+/// Reconcile topics with a declarative manifest in one transaction: create what is
+/// missing, update what differs and, only with `prune`, delete what is not declared
+pub async fn apply_manifest(
+    State(pool): State<PgPool>,
+    Query(query): Query<ApplyQuery>,
+    Json(manifest): Json<ContentManifest>,
+) -> Result<Json<ApiResponse<ApplyResult>>, (StatusCode, Json<ApiResponse<()>>)> {
+    validate_manifest(&manifest)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Blocks other topic writes, so the plan still holds when it is carried out
+    sqlx::query("LOCK TABLE topics IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to lock topics", e))?;
+
+    let current = sqlx::query_as::<_, Topic>("SELECT * FROM topics ORDER BY slug")
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    let (changes, unmanaged) = plan_topics(&current, &manifest.topics, query.prune);
+
+    if query.plan {
+        return Ok(Json(ApiResponse::success(ApplyResult::new(false, changes, unmanaged))));
+    }
+
+    // Deleting a topic deletes its questions, so a freeze blocks it too
+    let deleted: Vec<Uuid> = changes
+        .iter()
+        .filter(|c| c.action == ApplyAction::Delete)
+        .filter_map(|c| c.topic_id)
+        .collect();
+    ensure_not_frozen(&pool, &deleted).await?;
+
+    let specs: HashMap<String, &TopicSpec> = manifest.topics.iter().map(|s| (s.slug.trim().to_lowercase(), s)).collect();
+
+    // Deletions first, so a declared topic can take over a name that a pruned one held
+    for change in changes.iter().filter(|c| c.action == ApplyAction::Delete) {
+        sqlx::query("DELETE FROM topics WHERE id = $1")
+            .bind(change.topic_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| errors::internal("Failed to delete topic", e))?;
+    }
+
+    for change in &changes {
+        let Some(spec) = specs.get(&change.slug.to_lowercase()) else {
+            continue;
+        };
+
+        let sql = match change.action {
+            ApplyAction::Create => "INSERT INTO topics (slug, name, description, is_public) VALUES ($1, $2, $3, $4)",
+            ApplyAction::Update => {
+                "UPDATE topics SET slug = $1, name = $2, description = $3, is_public = $4, updated_at = NOW()
+                 WHERE id = $5"
+            }
+            ApplyAction::Delete | ApplyAction::Unchanged => continue,
+        };
+
+        let mut statement = sqlx::query(sql)
+            .bind(&change.slug)
+            .bind(spec.name.trim())
+            .bind(&spec.description)
+            .bind(spec.is_public);
+        if let Some(topic_id) = change.topic_id {
+            statement = statement.bind(topic_id);
+        }
+
+        statement
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| match e.as_database_error() {
+                Some(db) if db.constraint() == Some("topics_name_key") => (
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        ErrorCode::DuplicateTopicName,
+                        format!("Topic '{}': a topic named '{}' already exists", change.slug, spec.name.trim()),
+                    )),
+                ),
+                _ => errors::internal("Failed to apply topic", e),
+            })?;
+    }
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    let result = ApplyResult::new(true, changes, unmanaged);
+    if result.created + result.updated + result.deleted > 0 {
+        catalog::invalidate();
+    }
+
+    Ok(Json(ApiResponse::success(result)))
+}
+
+pub async fn export_topic(
+    State(pool): State<PgPool>,
+    Path(slug): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE LOWER(slug) = LOWER($1)")
+        .bind(&slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let Some(topic) = topic else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, format!("Topic with slug '{}' not found", slug))),
+        ));
+    };
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number"
+    )
+    .bind(topic.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let questions: Vec<BulkQuestionData> = questions.into_iter().map(BulkQuestionData::from).collect();
+
+    if query.format == ExportFormat::Yaml {
+        let file = write_question_file(&questions).map_err(|e| errors::internal("Failed to write question file", e))?;
+        let disposition = format!("attachment; filename=\"{}.md\"", topic.slug);
+
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/markdown; charset=utf-8".to_string()),
+                (header::CONTENT_DISPOSITION, disposition),
+            ],
+            file,
+        )
+            .into_response());
+    }
+
+    let export = TopicExport {
+        name: topic.name,
+        slug: topic.slug,
+        description: topic.description,
+        questions,
+    };
+
+    Ok(Json(ApiResponse::success(export)).into_response())
+}
+
+// Helper functions
+pub async fn build_manifest(pool: &PgPool) -> Result<ExportManifest, (StatusCode, Json<ApiResponse<()>>)> {
+    let topics = sqlx::query_as::<_, Topic>("SELECT * FROM topics ORDER BY slug")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions ORDER BY topic_id, question_number"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let mut by_topic: HashMap<_, Vec<QuestionChecksum>> = HashMap::new();
+    for question in &questions {
+        by_topic.entry(question.topic_id).or_default().push(QuestionChecksum {
+            question_number: question.question_number,
+            checksum: question.checksum(),
+        });
+    }
+
+    let topics = topics
+        .into_iter()
+        .map(|t| TopicManifest {
+            questions: by_topic.remove(&t.id).unwrap_or_default(),
+            slug: t.slug,
+            name: t.name,
+        })
+        .collect();
+
+    Ok(ExportManifest {
+        generated_at: Utc::now(),
+        topics,
+    })
+}
+
+// Background jobs
+async fn run_regrade_job(pool: PgPool, job_id: Uuid, question_id: Uuid) {
+    tracing::info!("Starting regrade job {} for question {}", job_id, question_id);
+
+    let result = regrade_sessions(&pool, job_id, question_id).await;
+
+    let update = match &result {
+        Ok(_) => sqlx::query(
+            "UPDATE regrade_jobs SET status = 'completed', completed_at = NOW() WHERE id = $1"
+        )
+        .bind(job_id)
+        .execute(&pool)
+        .await,
+        Err(e) => sqlx::query(
+            "UPDATE regrade_jobs SET status = 'failed', error = $1, completed_at = NOW() WHERE id = $2"
+        )
+        .bind(e.to_string())
+        .bind(job_id)
+        .execute(&pool)
+        .await,
+    };
+
+    match (result, update) {
+        (Ok(adjusted), Ok(_)) => tracing::info!("Regrade job {} adjusted {} sessions", job_id, adjusted),
+        (Err(e), _) => tracing::error!("Regrade job {} failed: {}", job_id, e),
+        (_, Err(e)) => tracing::error!("Failed to record status of regrade job {}: {}", job_id, e),
+    }
+}
+
+/// Recompute every submitted session containing the question, recording an adjustment
+/// for each score that changed. All adjustments for a job commit together.
+async fn regrade_sessions(pool: &PgPool, job_id: Uuid, question_id: Uuid) -> Result<i32, sqlx::Error> {
+    sqlx::query("UPDATE regrade_jobs SET status = 'running' WHERE id = $1")
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    let sessions = sqlx::query_as::<_, QuizSession>(
+        "SELECT * FROM quiz_sessions WHERE status = 'submitted' AND question_ids @> $1"
+    )
+    .bind(SqlxJson(vec![question_id]))
+    .fetch_all(pool)
+    .await?;
+
+    let mut transaction = pool.begin().await?;
+    let mut adjusted = 0;
+
+    for session in &sessions {
+        let questions = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = ANY($1)")
+            .bind(&session.question_ids.0)
+            .fetch_all(&mut *transaction)
+            .await?;
+
+        let answers = sqlx::query_as::<_, SessionAnswer>(
+            "SELECT * FROM quiz_session_answers WHERE session_id = $1"
+        )
+        .bind(session.id)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        let grades = grade_answers(session, &questions, &answers);
+        record_grades(&mut *transaction, session.id, &grades).await?;
+
+        let new_score = score(&grades);
+        if session.score == Some(new_score) {
+            continue;
+        }
+
+        sqlx::query("UPDATE quiz_sessions SET score = $1, updated_at = NOW() WHERE id = $2")
+            .bind(new_score)
+            .bind(session.id)
+            .execute(&mut *transaction)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO score_adjustments (job_id, session_id, question_id, previous_score, new_score)
+             VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(job_id)
+        .bind(session.id)
+        .bind(question_id)
+        .bind(session.score)
+        .bind(new_score)
+        .execute(&mut *transaction)
+        .await?;
+
+        adjusted += 1;
+    }
+
+    sqlx::query("UPDATE regrade_jobs SET sessions_checked = $1, sessions_adjusted = $2 WHERE id = $3")
+        .bind(sessions.len() as i32)
+        .bind(adjusted)
+        .bind(job_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+
+    Ok(adjusted)
+}
@@ -0,0 +1,134 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json
+};
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::errors;
+use crate::analytics;
+use crate::models::{
+    QuestionStats, QuestionStatsQuery, TopicAccuracy, RefreshResponse, CalibrationPoint, CalibrationQuery,
+    CognitiveLevelAccuracy, PaginatedResponse, PaginationMeta, PageQuery,
+    ApiResponse,
+};
+
+// Analytics handlers
+pub async fn get_topic_accuracy(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<TopicAccuracy>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, topics) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM topic_accuracy").fetch_one(&pool),
+        sqlx::query_as::<_, TopicAccuracy>(
+            "SELECT * FROM topic_accuracy ORDER BY topic_name, topic_id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch topic accuracy", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: topics,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn get_question_stats(
+    State(pool): State<PgPool>,
+    Query(query): Query<QuestionStatsQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<QuestionStats>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    // Hardest questions first; unanswered questions last
+    let (total_count, stats) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM question_stats WHERE ($1::uuid IS NULL OR topic_id = $1)")
+            .bind(query.topic_id)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, QuestionStats>(
+            "SELECT * FROM question_stats
+             WHERE ($1::uuid IS NULL OR topic_id = $1)
+             ORDER BY accuracy ASC NULLS LAST, attempts DESC, question_id
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(query.topic_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch question stats", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: stats,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// Confidence vs correctness for answers saved with a confidence level, across all
+/// topics unless one is given. Not paged: there is one point per confidence level (1-5).
+pub async fn get_calibration(
+    State(pool): State<PgPool>,
+    Query(query): Query<CalibrationQuery>,
+) -> Result<Json<ApiResponse<Vec<CalibrationPoint>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    // Confidence 1-5 maps onto 20%-100%
+    let points = sqlx::query_as::<_, CalibrationPoint>(
+        "SELECT
+            confidence,
+            SUM(answers)::bigint AS answers,
+            SUM(correct)::bigint AS correct,
+            SUM(correct)::float8 / NULLIF(SUM(answers), 0) AS accuracy,
+            confidence / 5.0::float8 AS expected_accuracy,
+            MIN(refreshed_at) AS refreshed_at
+         FROM confidence_calibration
+         WHERE ($1::uuid IS NULL OR topic_id = $1)
+         GROUP BY confidence
+         ORDER BY confidence"
+    )
+    .bind(query.topic_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch calibration", e))?;
+
+    Ok(Json(ApiResponse::success(points)))
+}
+
+/// Accuracy per cognitive level, from the per-question stats joined with each question's
+/// current level; across all topics unless one is given. Not paged: there is one row
+/// per cognitive level.
+pub async fn get_cognitive_level_accuracy(
+    State(pool): State<PgPool>,
+    Query(query): Query<CalibrationQuery>,
+) -> Result<Json<ApiResponse<Vec<CognitiveLevelAccuracy>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let levels = sqlx::query_as::<_, CognitiveLevelAccuracy>(
+        "SELECT
+            q.cognitive_level,
+            COUNT(*) AS questions,
+            SUM(s.attempts)::bigint AS attempts,
+            SUM(s.correct)::bigint AS correct,
+            SUM(s.correct)::float8 / NULLIF(SUM(s.attempts), 0) AS accuracy,
+            MIN(s.refreshed_at) AS refreshed_at
+         FROM question_stats s
+         JOIN questions q ON q.id = s.question_id
+         WHERE q.cognitive_level IS NOT NULL AND ($1::uuid IS NULL OR s.topic_id = $1)
+         GROUP BY q.cognitive_level
+         ORDER BY q.cognitive_level"
+    )
+    .bind(query.topic_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch cognitive level accuracy", e))?;
+
+    Ok(Json(ApiResponse::success(levels)))
+}
+
+pub async fn refresh_analytics(
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<RefreshResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    analytics::refresh_views(&pool).await.map_err(|e| errors::internal("Failed to refresh analytics", e))?;
+
+    Ok(Json(ApiResponse::success(RefreshResponse { refreshed_at: Utc::now() })))
+}
@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::PgPool;
+use std::env;
+
+use crate::errors;
+use crate::catalog::{self, CachedCatalog};
+use crate::encoding::escape_xml;
+use crate::middleware::etag_matches;
+use crate::models::{Feed, FeedItem, Question, Topic, ApiResponse, ErrorCode};
+
+/// How many questions the per-topic Atom feed lists
+const FEED_ENTRIES: i64 = 50;
+
+// Catalog handlers
+pub async fn get_catalog(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    let catalog = load_catalog(&pool).await?;
+
+    let cache_headers = [
+        (header::ETAG, catalog.etag.clone()),
+        (header::CACHE_CONTROL, "public, max-age=60".to_string()),
+    ];
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| etag_matches(v, &catalog.etag));
+
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "application/json")],
+        catalog.body.clone(),
+    )
+        .into_response())
+}
+
+// Feed handlers
+pub async fn get_feed(
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<Feed>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let cached = load_catalog(&pool).await?;
+
+    let items = cached
+        .catalog
+        .topics
+        .iter()
+        .map(|topic| FeedItem {
+            kind: "topic",
+            name: topic.name.clone(),
+            slug: topic.slug.clone(),
+            url: public_url(&format!("/topics/{}", topic.slug)),
+            last_modified: topic.last_modified,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(Feed {
+        generated_at: Utc::now(),
+        items,
+    })))
+}
+
+pub async fn get_sitemap(
+    State(pool): State<PgPool>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let cached = load_catalog(&pool).await?;
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for topic in &cached.catalog.topics {
+        xml.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n    <lastmod>{}</lastmod>\n  </url>\n",
+            escape_xml(&public_url(&format!("/topics/{}", topic.slug))),
+            xml_date(topic.last_modified),
+        ));
+    }
+    xml.push_str("</urlset>\n");
+
+    Ok(([(header::CONTENT_TYPE, "application/xml; charset=utf-8")], xml))
+}
+
+/// Atom feed of the newest questions in a topic: question text and a link, never answers
+pub async fn get_topic_feed(
+    State(pool): State<PgPool>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE LOWER(slug) = LOWER($1)")
+        .bind(&slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let Some(topic) = topic else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, format!("Topic with slug '{}' not found", slug))),
+        ));
+    };
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions WHERE topic_id = $1 ORDER BY created_at DESC LIMIT $2"
+    )
+    .bind(topic.id)
+    .bind(FEED_ENTRIES)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let topic_url = public_url(&format!("/topics/{}", topic.slug));
+    let updated = questions
+        .iter()
+        .map(|q| q.updated_at)
+        .chain([topic.updated_at])
+        .max()
+        .unwrap_or(topic.updated_at);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <id>urn:uuid:{}</id>\n", topic.id));
+    xml.push_str(&format!("  <title>{} - new practice questions</title>\n", escape_xml(&topic.name)));
+    xml.push_str(&format!("  <link href=\"{}\"/>\n", escape_xml(&topic_url)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", xml_date(updated)));
+
+    for question in &questions {
+        let link = format!("{}/questions/{}", topic_url, question.question_number);
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>urn:uuid:{}</id>\n", question.id));
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&entry_title(&question.question))));
+        xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(&link)));
+        xml.push_str(&format!("    <published>{}</published>\n", xml_date(question.created_at)));
+        xml.push_str(&format!("    <updated>{}</updated>\n", xml_date(question.updated_at)));
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+
+    Ok(([(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")], xml))
+}
+
+// Helper functions
+async fn load_catalog(pool: &PgPool) -> Result<std::sync::Arc<CachedCatalog>, (StatusCode, Json<ApiResponse<()>>)> {
+    catalog::load(pool).await.map_err(|e| errors::internal("Failed to build catalog", e))
+}
+
+/// Absolute URL on the public site (`PUBLIC_SITE_URL`, default `http://localhost:3000`)
+pub fn public_url(path: &str) -> String {
+    let base = env::var("PUBLIC_SITE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string());
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+fn xml_date(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// The question text, shortened to a readable entry title
+fn entry_title(question: &str) -> String {
+    const MAX_CHARS: usize = 120;
+
+    let question = question.trim();
+    match question.char_indices().nth(MAX_CHARS) {
+        Some((cut, _)) => format!("{}…", question[..cut].trim_end()),
+        None => question.to_string(),
+    }
+}
@@ -0,0 +1,430 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::env;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    generate_slug, ApiResponse, ErrorCode, Certification, CertificationDependencies, CertificationOrder,
+    CertificationQuestion, CertificationWithCounts, CreateCertification, DeleteCertificationQuery, OrderedQuestion,
+    QuestionResponse, RedactionPolicy, SetCertificationOrder, UpdateCertification, TopicWithCounts, PaginatedResponse,
+    PaginationMeta, PageQuery, ViewQuery,
+};
+use crate::handlers::topic::slug_generated;
+
+// Certification handlers
+pub async fn get_certifications(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<CertificationWithCounts>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, certifications) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM certifications").fetch_one(&pool),
+        sqlx::query_as::<_, CertificationWithCounts>(
+            "SELECT c.*, COUNT(t.id) AS topic_count
+             FROM certifications c
+             LEFT JOIN topics t ON t.certification_id = c.id
+             GROUP BY c.id
+             ORDER BY c.name
+             LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch certifications", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: certifications,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn get_certification(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Certification>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let certification = sqlx::query_as::<_, Certification>("SELECT * FROM certifications WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    match certification {
+        Some(certification) => Ok(Json(ApiResponse::success(certification))),
+        None => Err(not_found()),
+    }
+}
+
+pub async fn get_certification_topics(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<TopicWithCounts>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_exists(&pool, id).await?;
+
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, topics) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM topics WHERE certification_id = $1")
+            .bind(id)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, TopicWithCounts>(
+            "SELECT t.*,
+                COUNT(q.id) AS question_count,
+                COUNT(q.id) FILTER (WHERE q.difficulty = 'easy') AS easy_count,
+                COUNT(q.id) FILTER (WHERE q.difficulty = 'medium') AS medium_count,
+                COUNT(q.id) FILTER (WHERE q.difficulty = 'hard') AS hard_count
+             FROM topics t
+             LEFT JOIN questions q ON q.topic_id = t.id
+             WHERE t.certification_id = $1
+             GROUP BY t.id
+             ORDER BY t.name
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: topics,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// The questions of a certification's topics in official exam-guide order. Questions not
+/// placed in the order follow, by topic name and question number.
+pub async fn get_certification_questions(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(view): Query<ViewQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<CertificationQuestion>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_exists(&pool, id).await?;
+
+    let (page, limit, offset) = page_query.resolve();
+
+    // Order rows of questions since moved out of the certification are skipped, so the
+    // positions stay contiguous
+    let (total_count, questions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM questions q
+             JOIN topics t ON q.topic_id = t.id
+             WHERE t.certification_id = $1"
+        )
+        .bind(id)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, OrderedQuestion>(
+            "SELECT q.*, o.official_position
+             FROM questions q
+             JOIN topics t ON q.topic_id = t.id
+             LEFT JOIN (
+                SELECT co.question_id, ROW_NUMBER() OVER (ORDER BY co.position) AS official_position
+                FROM certification_question_order co
+                JOIN questions oq ON co.question_id = oq.id
+                JOIN topics ot ON oq.topic_id = ot.id AND ot.certification_id = co.certification_id
+                WHERE co.certification_id = $1
+             ) o ON o.question_id = q.id
+             WHERE t.certification_id = $1
+             ORDER BY o.official_position NULLS LAST, t.name, t.id, q.question_number
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let policy = RedactionPolicy::for_audience(view.view);
+    let items = questions
+        .into_iter()
+        .map(|q| CertificationQuestion {
+            question: QuestionResponse::from(q.question).redact(&policy).render_math(view.math).render_code(view.code),
+            official_position: q.official_position,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// Replace the official exam-guide order of a certification's questions
+pub async fn set_certification_order(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetCertificationOrder>,
+) -> Result<Json<ApiResponse<CertificationOrder>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = payload.question_ids.iter().find(|q| !seen.insert(**q)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("Question {} is listed more than once", duplicate))),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Lock the certification so concurrent reorders are applied one after the other
+    let exists: Option<Uuid> = sqlx::query_scalar("SELECT id FROM certifications WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    if exists.is_none() {
+        return Err(not_found());
+    }
+
+    let outside: Option<Uuid> = sqlx::query_scalar(
+        "SELECT question_id FROM UNNEST($2::uuid[]) AS question_id
+         WHERE NOT EXISTS (
+            SELECT 1 FROM questions q
+            JOIN topics t ON q.topic_id = t.id
+            WHERE q.id = question_id AND t.certification_id = $1
+         )
+         LIMIT 1"
+    )
+    .bind(id)
+    .bind(&payload.question_ids)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    if let Some(question_id) = outside {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationFailed,
+                format!("Question {} is not in a topic of this certification", question_id),
+            )),
+        ));
+    }
+
+    sqlx::query("DELETE FROM certification_question_order WHERE certification_id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to update question order", e))?;
+
+    sqlx::query(
+        "INSERT INTO certification_question_order (certification_id, question_id, position)
+         SELECT $1, question_id, position FROM UNNEST($2::uuid[]) WITH ORDINALITY AS o(question_id, position)"
+    )
+    .bind(id)
+    .bind(&payload.question_ids)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to update question order", e))?;
+
+    sqlx::query("UPDATE certifications SET updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to update certification", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(CertificationOrder { certification_id: id, question_ids: payload.question_ids })))
+}
+
+pub async fn create_certification(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateCertification>,
+) -> Result<Json<ApiResponse<Certification>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "name must not be empty".to_string())),
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let slug = match payload.slug.as_deref().map(str::trim) {
+        Some(slug) if !slug.is_empty() => slug.to_string(),
+        _ => {
+            let slug = generate_slug(&payload.name);
+            warnings.push(slug_generated(&slug));
+            slug
+        }
+    };
+
+    let certification = sqlx::query_as::<_, Certification>(
+        "INSERT INTO certifications (provider_id, name, slug, exam_code, description)
+         VALUES ($1, $2, $3, $4, $5) RETURNING *"
+    )
+    .bind(payload.provider_id)
+    .bind(payload.name.trim())
+    .bind(slug)
+    .bind(payload.exam_code)
+    .bind(payload.description)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| conflict_or_internal(e, "Failed to create certification"))?;
+
+    Ok(Json(ApiResponse::success(certification).with_warnings(warnings)))
+}
+
+pub async fn update_certification(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateCertification>,
+) -> Result<Json<ApiResponse<Certification>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let certification = sqlx::query_as::<_, Certification>(
+        "UPDATE certifications SET
+            provider_id = COALESCE($1, provider_id),
+            name = COALESCE($2, name),
+            slug = COALESCE($3, slug),
+            exam_code = COALESCE($4, exam_code),
+            description = COALESCE($5, description),
+            updated_at = NOW()
+         WHERE id = $6 RETURNING *"
+    )
+    .bind(payload.provider_id)
+    .bind(payload.name.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .bind(payload.slug.as_deref().map(str::trim).filter(|s| !s.is_empty()))
+    .bind(payload.exam_code)
+    .bind(payload.description)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| conflict_or_internal(e, "Failed to update certification"))?;
+
+    match certification {
+        Some(certification) => Ok(Json(ApiResponse::success(certification))),
+        None => Err(not_found()),
+    }
+}
+
+pub async fn get_certification_dependencies(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<CertificationDependencies>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let dependencies = get_dependencies(&pool, id).await?;
+
+    Ok(Json(ApiResponse::success(dependencies)))
+}
+
+/// Delete a certification. Its topics, and their questions, are kept and unlinked.
+pub async fn delete_certification(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeleteCertificationQuery>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let require_confirmation = env::var("REQUIRE_DELETE_CONFIRMATION").is_ok_and(|v| v == "true");
+
+    match query.confirm {
+        Some(token) => {
+            let dependencies = get_dependencies(&pool, id).await?;
+            if token != dependencies.confirmation_token {
+                return Err((
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(ErrorCode::DependenciesChanged,
+                        "Certification dependencies changed since the report was generated; fetch a new confirmation token".to_string(),
+                    )),
+                ));
+            }
+        }
+        None if require_confirmation => {
+            return Err((
+                StatusCode::PRECONDITION_REQUIRED,
+                Json(ApiResponse::error(ErrorCode::ConfirmationRequired,
+                    "Deleting a certification requires ?confirm= with the token from GET /certifications/{id}/dependencies".to_string(),
+                )),
+            ));
+        }
+        None => {}
+    }
+
+    let result = sqlx::query("DELETE FROM certifications WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete certification", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err(not_found());
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Helper functions
+/// Count everything a certification delete would unlink or remove
+async fn get_dependencies(pool: &PgPool, id: Uuid) -> Result<CertificationDependencies, (StatusCode, Json<ApiResponse<()>>)> {
+    let dependencies = sqlx::query_as::<_, CertificationDependencies>(
+        "SELECT
+            c.id AS certification_id,
+            c.updated_at AS certification_updated_at,
+            (SELECT COUNT(*) FROM topics WHERE certification_id = c.id) AS topic_count,
+            (SELECT COUNT(*) FROM questions q
+                JOIN topics t ON q.topic_id = t.id
+                WHERE t.certification_id = c.id) AS question_count,
+            (SELECT COUNT(*) FROM content_freezes WHERE certification_id = c.id) AS freeze_count,
+            (SELECT COUNT(*) FROM certification_question_order WHERE certification_id = c.id) AS ordered_question_count,
+            (SELECT COUNT(*) FROM certification_objectives WHERE certification_id = c.id) AS objective_count
+         FROM certifications c WHERE c.id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch certification dependencies", e))?;
+
+    let Some(mut dependencies) = dependencies else {
+        return Err(not_found());
+    };
+
+    dependencies.confirmation_token = dependencies.token();
+
+    Ok(dependencies)
+}
+
+async fn ensure_exists(pool: &PgPool, id: Uuid) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM certifications WHERE id = $1)")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    if !exists {
+        return Err(not_found());
+    }
+
+    Ok(())
+}
+
+fn not_found() -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+    )
+}
+
+fn conflict_or_internal(e: sqlx::Error, context: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    match e.as_database_error() {
+        Some(db) if db.constraint() == Some("certifications_provider_name_key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateCertificationName, "The provider already has a certification with this name".to_string())),
+        ),
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateSlug, "A certification with this slug already exists".to_string())),
+        ),
+        Some(db) if db.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ProviderNotFound, "Provider not found".to_string())),
+        ),
+        _ => errors::internal(context, e),
+    }
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use serde_json::Value;
+use sqlx::{PgExecutor, PgPool, types::Json as SqlxJson};
+use std::collections::HashMap;
+
+use crate::errors;
+use crate::models::{
+    CustomField, CustomFieldType, CreateCustomField, PaginatedResponse, PaginationMeta, PageQuery,
+    ApiResponse, ErrorCode, validate_metadata,
+};
+
+// Custom field handlers
+pub async fn get_custom_fields(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<CustomField>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, fields) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM custom_fields").fetch_one(&pool),
+        sqlx::query_as::<_, CustomField>("SELECT * FROM custom_fields ORDER BY key LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch custom fields", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: fields,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_custom_field(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateCustomField>,
+) -> Result<Json<ApiResponse<CustomField>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let key = payload.key.trim();
+    let valid_key = key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if !valid_key {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!(
+                "'{}' is not a valid key; use lowercase letters, digits and underscores, starting with a letter",
+                payload.key
+            ))),
+        ));
+    }
+
+    let options = match payload.field_type {
+        CustomFieldType::Enum => match payload.options {
+            Some(options) if !options.is_empty() => Some(options),
+            _ => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationFailed, "Enum fields need at least one option".to_string())),
+                ));
+            }
+        },
+        _ => None,
+    };
+
+    let field = sqlx::query_as::<_, CustomField>(
+        "INSERT INTO custom_fields (key, label, field_type, options, required)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (key) DO NOTHING
+         RETURNING *"
+    )
+    .bind(key)
+    .bind(payload.label.trim())
+    .bind(&payload.field_type)
+    .bind(options.as_ref().map(SqlxJson))
+    .bind(payload.required)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create custom field", e))?;
+
+    match field {
+        Some(field) => Ok(Json(ApiResponse::success(field))),
+        None => Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateCustomField, format!("Custom field '{}' already exists", key))),
+        )),
+    }
+}
+
+/// Fields still set on any question cannot be deleted
+pub async fn delete_custom_field(
+    State(pool): State<PgPool>,
+    Path(key): Path<String>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let in_use: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM questions WHERE metadata ? $1")
+        .bind(&key)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to count questions", e))?;
+
+    if in_use > 0 {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::CustomFieldInUse, format!(
+                "Custom field '{}' is set on {} questions; remove it from them first",
+                key, in_use
+            ))),
+        ));
+    }
+
+    let result = sqlx::query("DELETE FROM custom_fields WHERE key = $1")
+        .bind(&key)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete custom field", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::CustomFieldNotFound, "Custom field not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Helper functions
+pub async fn load_custom_fields(executor: impl PgExecutor<'_>) -> Result<Vec<CustomField>, sqlx::Error> {
+    sqlx::query_as::<_, CustomField>("SELECT * FROM custom_fields ORDER BY key")
+        .fetch_all(executor)
+        .await
+}
+
+/// Validate a question's metadata against the current field definitions
+pub async fn ensure_valid_metadata(
+    pool: &PgPool,
+    metadata: &HashMap<String, Value>,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let fields = load_custom_fields(pool).await.map_err(|e| errors::internal("Failed to fetch custom fields", e))?;
+
+    validate_metadata(&fields, metadata).map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json
+};
+use sqlx::PgPool;
+
+use crate::errors;
+use crate::models::{ApiResponse, ErrorCode};
+
+// Diagram handlers
+/// The rendered SVG of a diagram code block. URLs are content-addressed, so the image
+/// never changes and may be cached for good.
+pub async fn get_diagram_svg(
+    State(pool): State<PgPool>,
+    Path(hash): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let diagram: Option<(Option<String>, Option<String>)> = sqlx::query_as("SELECT svg, last_error FROM diagrams WHERE hash = $1")
+        .bind(&hash)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch diagram", e))?;
+
+    let svg = match diagram {
+        Some((Some(svg), _)) => svg,
+        Some((None, last_error)) => {
+            let message = match last_error {
+                Some(error) => format!("Diagram could not be rendered: {}", error),
+                None => "Diagram has not been rendered yet".to_string(),
+            };
+            return Err((StatusCode::NOT_FOUND, Json(ApiResponse::error(ErrorCode::DiagramNotFound, message))));
+        }
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::DiagramNotFound, "Diagram not found".to_string())),
+            ));
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            // The SVG comes from an external renderer; never let it run scripts
+            (header::CONTENT_SECURITY_POLICY, "default-src 'none'; style-src 'unsafe-inline'"),
+        ],
+        svg,
+    ))
+}
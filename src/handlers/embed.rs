@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse},
+    Json
+};
+use rand::{distributions::Alphanumeric, Rng};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    EmbedToken, CreateEmbedToken, EmbedConfig, PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode,
+};
+
+/// The widget page; `{{TOKEN}}` is replaced with the embed token
+const WIDGET_HTML: &str = include_str!("embed_widget.html");
+
+// Embed token handlers
+pub async fn get_embed_tokens(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<EmbedToken>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, tokens) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM embed_tokens").fetch_one(&pool),
+        sqlx::query_as::<_, EmbedToken>(
+            "SELECT * FROM embed_tokens ORDER BY created_at DESC, id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch embed tokens", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: tokens,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_embed_token(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateEmbedToken>,
+) -> Result<Json<ApiResponse<EmbedToken>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let domain = payload.domain.trim().to_lowercase();
+    if !is_valid_domain(&domain) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!(
+                "'{}' is not a valid domain; use a bare host such as blog.example.com",
+                payload.domain
+            ))),
+        ));
+    }
+
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(24)
+        .map(char::from)
+        .collect();
+
+    let embed = sqlx::query_as::<_, EmbedToken>(
+        "INSERT INTO embed_tokens (token, topic_id, domain, question_count)
+         VALUES ($1, $2, $3, $4) RETURNING *"
+    )
+    .bind(token)
+    .bind(payload.topic_id)
+    .bind(domain)
+    .bind(payload.question_count.unwrap_or(5).clamp(1, 20))
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create embed token", e))?;
+
+    Ok(Json(ApiResponse::success(embed)))
+}
+
+pub async fn delete_embed_token(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM embed_tokens WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete embed token", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::EmbedTokenNotFound, "Embed token not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Widget handlers
+pub async fn get_embed_config(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<Json<ApiResponse<EmbedConfig>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (embed, topic_name) = get_embed_by_token(&pool, &token).await?;
+
+    Ok(Json(ApiResponse::success(EmbedConfig {
+        topic_id: embed.topic_id,
+        topic_name,
+        question_count: embed.question_count,
+        api_base: "/api".to_string(),
+    })))
+}
+
+/// Self-contained quiz page for iframes; only the token's domain may frame it
+pub async fn get_embed_widget(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let (embed, _) = get_embed_by_token(&pool, &token).await?;
+
+    let csp = format!(
+        "frame-ancestors https://{domain} http://{domain}; default-src 'self'; script-src 'unsafe-inline'; style-src 'unsafe-inline'",
+        domain = embed.domain
+    );
+
+    Ok((
+        [(header::CONTENT_SECURITY_POLICY, csp)],
+        Html(WIDGET_HTML.replace("{{TOKEN}}", &embed.token)),
+    ))
+}
+
+// Helper functions
+async fn get_embed_by_token(pool: &PgPool, token: &str) -> Result<(EmbedToken, String), (StatusCode, Json<ApiResponse<()>>)> {
+    let embed = sqlx::query_as::<_, EmbedToken>("SELECT * FROM embed_tokens WHERE token = $1")
+        .bind(token)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch embed token", e))?;
+
+    let Some(embed) = embed else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::EmbedTokenNotFound, "Embed token not found".to_string())),
+        ));
+    };
+
+    let topic_name: String = sqlx::query_scalar("SELECT name FROM topics WHERE id = $1")
+        .bind(embed.topic_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    Ok((embed, topic_name))
+}
+
+/// A bare host name with an optional port, safe to put in a CSP header
+fn is_valid_domain(domain: &str) -> bool {
+    let host = domain.split_once(':').map_or(domain, |(host, port)| {
+        if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+            ""
+        } else {
+            host
+        }
+    });
+
+    !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
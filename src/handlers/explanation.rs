@@ -0,0 +1,426 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json
+};
+use sqlx::{PgExecutor, PgPool, types::Json as SqlxJson};
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    ExplanationVote, ExplanationVoteSummary, ExplanationRating, ExplanationReportQuery, VoteDirection,
+    ExplanationEntry, CreateExplanationEntry, ReviewExplanationEntry, ExplanationEntryQuery,
+    PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode, ReadingStats,
+};
+use crate::handlers::freeze::ensure_question_not_frozen;
+use crate::catalog;
+
+// Explanation vote handlers
+pub async fn vote_explanation(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<ExplanationVote>,
+) -> Result<Json<ApiResponse<ExplanationVoteSummary>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let voter = voter_id(&headers)?;
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Lock the question so concurrent votes tally in order
+    let exists: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM questions WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO explanation_votes (question_id, voter, direction) VALUES ($1, $2, $3)
+         ON CONFLICT (question_id, voter) DO UPDATE SET
+            direction = EXCLUDED.direction,
+            updated_at = NOW()"
+    )
+    .bind(id)
+    .bind(&voter)
+    .bind(&payload.vote)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to record vote", e))?;
+
+    let (upvotes, downvotes) = tally_votes(&mut *transaction, id).await.map_err(|e| errors::internal("Failed to tally votes", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(ExplanationVoteSummary {
+        question_id: id,
+        upvotes,
+        downvotes,
+        score: upvotes - downvotes,
+        your_vote: Some(payload.vote),
+    })))
+}
+
+pub async fn retract_explanation_vote(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<ExplanationVoteSummary>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let voter = voter_id(&headers)?;
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    let removed: Option<(VoteDirection,)> = sqlx::query_as(
+        "DELETE FROM explanation_votes WHERE question_id = $1 AND voter = $2 RETURNING direction"
+    )
+    .bind(id)
+    .bind(&voter)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to retract vote", e))?;
+
+    if removed.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::VoteNotFound, "Vote not found".to_string())),
+        ));
+    }
+
+    let (upvotes, downvotes) = tally_votes(&mut *transaction, id).await.map_err(|e| errors::internal("Failed to tally votes", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(ExplanationVoteSummary {
+        question_id: id,
+        upvotes,
+        downvotes,
+        score: upvotes - downvotes,
+        your_vote: None,
+    })))
+}
+
+// Community explanation handlers
+pub async fn get_explanations(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExplanationEntryQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ExplanationEntry>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_question_exists(&pool, id).await?;
+
+    let (page, limit, offset) = page_query.resolve();
+    let include_pending = query.include_pending.unwrap_or(false);
+
+    let (total_count, entries) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM question_explanations WHERE question_id = $1 AND (approved OR $2)"
+        )
+        .bind(id)
+        .bind(include_pending)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, ExplanationEntry>(
+            "SELECT * FROM question_explanations
+             WHERE question_id = $1 AND (approved OR $2)
+             ORDER BY is_primary DESC, upvotes - downvotes DESC, created_at, id
+             LIMIT $3 OFFSET $4"
+        )
+        .bind(id)
+        .bind(include_pending)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch explanations", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: entries,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_explanation(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateExplanationEntry>,
+) -> Result<Json<ApiResponse<ExplanationEntry>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.author.trim().is_empty() || payload.body.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "Author and body are required".to_string())),
+        ));
+    }
+
+    ensure_question_exists(&pool, id).await?;
+
+    let entry = sqlx::query_as::<_, ExplanationEntry>(
+        "INSERT INTO question_explanations (question_id, author, body) VALUES ($1, $2, $3) RETURNING *"
+    )
+    .bind(id)
+    .bind(payload.author.trim())
+    .bind(payload.body.trim())
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create explanation", e))?;
+
+    Ok(Json(ApiResponse::success(entry)))
+}
+
+pub async fn vote_explanation_entry(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<ExplanationVote>,
+) -> Result<Json<ApiResponse<ExplanationEntry>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let voter = voter_id(&headers)?;
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    let exists: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM question_explanations WHERE id = $1 AND approved FOR UPDATE"
+    )
+    .bind(id)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch explanation", e))?;
+
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ExplanationNotFound, "Explanation not found".to_string())),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO question_explanation_votes (explanation_id, voter, direction) VALUES ($1, $2, $3)
+         ON CONFLICT (explanation_id, voter) DO UPDATE SET
+            direction = EXCLUDED.direction,
+            updated_at = NOW()"
+    )
+    .bind(id)
+    .bind(&voter)
+    .bind(&payload.vote)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to record vote", e))?;
+
+    let entry = sqlx::query_as::<_, ExplanationEntry>(
+        "UPDATE question_explanations SET
+            upvotes = (SELECT COUNT(*) FROM question_explanation_votes WHERE explanation_id = $1 AND direction = 'up'),
+            downvotes = (SELECT COUNT(*) FROM question_explanation_votes WHERE explanation_id = $1 AND direction = 'down'),
+            updated_at = NOW()
+         WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to tally votes", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(entry)))
+}
+
+/// Approve, reject or promote a contributed explanation. The primary explanation's body
+/// becomes the question's `explanation`.
+pub async fn review_explanation(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReviewExplanationEntry>,
+) -> Result<Json<ApiResponse<ExplanationEntry>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let entry = sqlx::query_as::<_, ExplanationEntry>("SELECT * FROM question_explanations WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch explanation", e))?;
+
+    let Some(entry) = entry else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ExplanationNotFound, "Explanation not found".to_string())),
+        ));
+    };
+
+    // Rejecting an entry also demotes it; promoting one also approves it
+    let is_primary = payload.is_primary.unwrap_or(entry.is_primary) && payload.approved != Some(false);
+    let approved = payload.approved.unwrap_or(entry.approved) || is_primary;
+    let promoted = is_primary && !entry.is_primary;
+
+    if promoted {
+        ensure_question_not_frozen(&pool, entry.question_id).await?;
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    if promoted {
+        sqlx::query("UPDATE question_explanations SET is_primary = FALSE, updated_at = NOW() WHERE question_id = $1 AND is_primary")
+            .bind(entry.question_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| errors::internal("Failed to demote primary explanation", e))?;
+
+        let (question, options): (String, SqlxJson<Vec<String>>) =
+            sqlx::query_as("SELECT question, options FROM questions WHERE id = $1 FOR UPDATE")
+                .bind(entry.question_id)
+                .fetch_one(&mut *transaction)
+                .await
+                .map_err(|e| errors::internal("Failed to fetch question", e))?;
+        let stats = ReadingStats::of(&question, &options.0, &entry.body);
+
+        sqlx::query(
+            "UPDATE questions SET explanation = $1, word_count = $2, estimated_secs = $3, updated_at = NOW() WHERE id = $4"
+        )
+        .bind(&entry.body)
+        .bind(stats.word_count)
+        .bind(stats.estimated_secs)
+        .bind(entry.question_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to update question", e))?;
+    }
+
+    let entry = sqlx::query_as::<_, ExplanationEntry>(
+        "UPDATE question_explanations SET approved = $1, is_primary = $2, updated_at = NOW() WHERE id = $3 RETURNING *"
+    )
+    .bind(approved)
+    .bind(is_primary)
+    .bind(id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to update explanation", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    if promoted {
+        catalog::invalidate();
+    }
+
+    Ok(Json(ApiResponse::success(entry)))
+}
+
+pub async fn delete_explanation(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM question_explanations WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete explanation", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ExplanationNotFound, "Explanation not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Editor report handlers
+/// Lowest-rated explanations first, to prioritize rewrites
+pub async fn get_explanation_report(
+    State(pool): State<PgPool>,
+    Query(query): Query<ExplanationReportQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ExplanationRating>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let min_votes = query.min_votes.unwrap_or(3).max(1);
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    let (total_count, ratings) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM questions WHERE explanation_upvotes + explanation_downvotes >= $1"
+        )
+        .bind(min_votes)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, ExplanationRating>(
+            "SELECT
+                id AS question_id, topic_id, question_number, question, explanation,
+                explanation_upvotes AS upvotes,
+                explanation_downvotes AS downvotes,
+                explanation_upvotes::float8 / (explanation_upvotes + explanation_downvotes) AS approval
+             FROM questions
+             WHERE explanation_upvotes + explanation_downvotes >= $1
+             ORDER BY approval, explanation_downvotes DESC, question_number, id
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(min_votes)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch explanation ratings", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: ratings,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+// Helper functions
+/// Community explanations for a question: the primary one first, then by vote score
+pub async fn get_explanation_entries(
+    pool: &PgPool,
+    question_id: Uuid,
+    include_pending: bool,
+) -> Result<Vec<ExplanationEntry>, (StatusCode, Json<ApiResponse<()>>)> {
+    sqlx::query_as::<_, ExplanationEntry>(
+        "SELECT * FROM question_explanations
+         WHERE question_id = $1 AND (approved OR $2)
+         ORDER BY is_primary DESC, upvotes - downvotes DESC, created_at"
+    )
+    .bind(question_id)
+    .bind(include_pending)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch explanations", e))
+}
+
+async fn ensure_question_exists(pool: &PgPool, id: Uuid) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    match exists {
+        Some(_) => Ok(()),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        )),
+    }
+}
+
+/// Who is voting, as reported by the client in `X-Voter-Id`
+fn voter_id(headers: &HeaderMap) -> Result<String, (StatusCode, Json<ApiResponse<()>>)> {
+    headers
+        .get("x-voter-id")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationFailed, "X-Voter-Id header is required".to_string())),
+            )
+        })
+}
+
+/// Recount a question's votes and store the totals on the question
+async fn tally_votes<'e>(executor: impl PgExecutor<'e>, question_id: Uuid) -> Result<(i32, i32), sqlx::Error> {
+    sqlx::query_as(
+        "UPDATE questions SET
+            explanation_upvotes = (SELECT COUNT(*) FROM explanation_votes WHERE question_id = $1 AND direction = 'up'),
+            explanation_downvotes = (SELECT COUNT(*) FROM explanation_votes WHERE question_id = $1 AND direction = 'down')
+         WHERE id = $1
+         RETURNING explanation_upvotes, explanation_downvotes"
+    )
+    .bind(question_id)
+    .fetch_one(executor)
+    .await
+}
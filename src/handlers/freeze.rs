@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    ContentFreeze, CreateContentFreeze, PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode,
+};
+
+// Content freeze handlers
+pub async fn get_freezes(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ContentFreeze>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, freezes) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM content_freezes WHERE ends_at > NOW()").fetch_one(&pool),
+        sqlx::query_as::<_, ContentFreeze>(
+            "SELECT * FROM content_freezes WHERE ends_at > NOW() ORDER BY starts_at, id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch content freezes", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: freezes,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_freeze(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateContentFreeze>,
+) -> Result<Json<ApiResponse<ContentFreeze>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.starts_at.is_some_and(|starts_at| starts_at >= payload.ends_at) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "Freeze must end after it starts".to_string())),
+        ));
+    }
+
+    let target_exists: Option<bool> = match (payload.topic_id, payload.certification_id) {
+        (Some(topic_id), None) => Some(
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM topics WHERE id = $1)")
+                .bind(topic_id)
+                .fetch_one(&pool)
+                .await
+                .map_err(|e| errors::internal("Failed to fetch topic", e))?,
+        ),
+        (None, Some(certification_id)) => Some(
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM certifications WHERE id = $1)")
+                .bind(certification_id)
+                .fetch_one(&pool)
+                .await
+                .map_err(|e| errors::internal("Failed to fetch certification", e))?,
+        ),
+        _ => None,
+    };
+
+    match target_exists {
+        None => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationFailed, "Give either topic_id or certification_id".to_string())),
+            ));
+        }
+        Some(false) if payload.topic_id.is_some() => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+            ));
+        }
+        Some(false) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+            ));
+        }
+        Some(true) => {}
+    }
+
+    let freeze = sqlx::query_as::<_, ContentFreeze>(
+        "INSERT INTO content_freezes (topic_id, certification_id, starts_at, ends_at, reason)
+         VALUES ($1, $2, COALESCE($3, NOW()), $4, $5) RETURNING *"
+    )
+    .bind(payload.topic_id)
+    .bind(payload.certification_id)
+    .bind(payload.starts_at)
+    .bind(payload.ends_at)
+    .bind(payload.reason)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create content freeze", e))?;
+
+    Ok(Json(ApiResponse::success(freeze)))
+}
+
+pub async fn delete_freeze(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM content_freezes WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete content freeze", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::FreezeNotFound, "Content freeze not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Helper functions
+/// Reject mutations with 423 Locked while any of the topics has an active freeze
+pub async fn ensure_not_frozen(pool: &PgPool, topic_ids: &[Uuid]) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let freeze = active_freeze(pool, topic_ids)
+        .await
+        .map_err(|e| errors::internal("Failed to check content freezes", e))?;
+
+    match freeze {
+        Some(freeze) => Err((
+            StatusCode::LOCKED,
+            Json(ApiResponse::error(ErrorCode::ContentFrozen, format!(
+                "Content is frozen until {}{}",
+                freeze.ends_at.to_rfc3339(),
+                freeze.reason.map(|r| format!(": {}", r)).unwrap_or_default(),
+            ))),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The active freeze lasting longest over any of the topics, set on the topic itself or
+/// on its certification
+pub async fn active_freeze(executor: impl PgExecutor<'_>, topic_ids: &[Uuid]) -> Result<Option<ContentFreeze>, sqlx::Error> {
+    sqlx::query_as::<_, ContentFreeze>(
+        "SELECT * FROM content_freezes
+         WHERE (topic_id = ANY($1)
+                OR certification_id IN (SELECT certification_id FROM topics WHERE id = ANY($1)))
+           AND starts_at <= NOW() AND ends_at > NOW()
+         ORDER BY ends_at DESC LIMIT 1"
+    )
+    .bind(topic_ids)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Same check for a question, looked up by id. Unknown questions pass so the caller can 404.
+pub async fn ensure_question_not_frozen(pool: &PgPool, question_id: Uuid) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let topic_id: Option<Uuid> = sqlx::query_scalar("SELECT topic_id FROM questions WHERE id = $1")
+        .bind(question_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    match topic_id {
+        Some(topic_id) => ensure_not_frozen(pool, &[topic_id]).await,
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,451 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json
+};
+use serde_json::{Map, Value};
+use sqlx::{PgPool, types::Json as SqlxJson};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::encoding;
+use crate::models::{
+    ImportTemplate, CreateImportTemplate, CsvImportQuery, YamlImportQuery, parse_question_file,
+    ImportRun, ImportRunQuery, ImportSource, NewImportRun, FailedRow, OnError,
+    BulkCreateResponse, PaginatedResponse, PaginationMeta, PageQuery,
+    ApiResponse, ErrorCode,
+};
+use crate::handlers::topic;
+use crate::handlers::freeze::ensure_not_frozen;
+use crate::handlers::question::insert_questions;
+
+// Import template handlers
+pub async fn get_templates(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ImportTemplate>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, templates) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM import_templates").fetch_one(&pool),
+        sqlx::query_as::<_, ImportTemplate>(
+            "SELECT * FROM import_templates ORDER BY name, id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch import templates", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: templates,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn get_template(
+    State(pool): State<PgPool>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<ImportTemplate>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let template = get_template_by_name(&pool, &name).await?;
+
+    Ok(Json(ApiResponse::success(template)))
+}
+
+/// Create a template, or replace the template with the same name, ignoring case
+pub async fn save_template(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateImportTemplate>,
+) -> Result<Json<ApiResponse<ImportTemplate>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "Template name must not be empty".to_string())),
+        ));
+    }
+
+    let template = sqlx::query_as::<_, ImportTemplate>(
+        "INSERT INTO import_templates (name, description, mapping) VALUES ($1, $2, $3)
+         ON CONFLICT (LOWER(name)) DO UPDATE SET
+            name = EXCLUDED.name,
+            description = EXCLUDED.description,
+            mapping = EXCLUDED.mapping,
+            updated_at = NOW()
+         RETURNING *"
+    )
+    .bind(name)
+    .bind(payload.description)
+    .bind(SqlxJson(payload.mapping))
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to save import template", e))?;
+
+    Ok(Json(ApiResponse::success(template)))
+}
+
+pub async fn delete_template(
+    State(pool): State<PgPool>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM import_templates WHERE LOWER(name) = LOWER($1)")
+        .bind(&name)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete import template", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TemplateNotFound, format!("Import template '{}' not found", name))),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// CSV import handlers
+pub async fn import_csv(
+    State(pool): State<PgPool>,
+    Query(query): Query<CsvImportQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<ApiResponse<BulkCreateResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let template = get_template_by_name(&pool, &query.template).await?;
+    let mapping = template.mapping.0;
+
+    let topic_id = topic::get_topic_id_by_slug(&pool, &query.topic_slug).await?;
+    ensure_not_frozen(&pool, &[topic_id]).await?;
+
+    let delimiter = mapping.delimiter.unwrap_or(',');
+    if !delimiter.is_ascii() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("Delimiter '{}' must be an ASCII character", delimiter))),
+        ));
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter as u8)
+        .flexible(true)
+        .from_reader(body.as_bytes());
+
+    let header_record = reader.headers().cloned().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::InvalidCsv, format!("Failed to read CSV header: {}", e))),
+        )
+    })?;
+    let columns: Vec<&str> = header_record.iter().map(str::trim).collect();
+
+    let missing = mapping.missing_columns(&columns);
+    if !missing.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::CsvColumnsMissing, format!(
+                "CSV is missing columns used by template '{}': {}",
+                template.name,
+                missing.join(", ")
+            ))),
+        ));
+    }
+
+    if query.strict {
+        let unmapped = mapping.unmapped_columns(&columns);
+        if !unmapped.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::UnknownFields, format!(
+                    "CSV has columns not mapped by template '{}' (strict mode): {}",
+                    template.name,
+                    unmapped.join(", ")
+                ))),
+            ));
+        }
+    }
+
+    let mut questions = Vec::new();
+    // Row number and cells of each entry in `questions`, to report insert failures
+    let mut sources = Vec::new();
+    let mut failed_rows = Vec::new();
+    let mut total_rows = 0;
+
+    for (index, record) in reader.records().enumerate() {
+        total_rows += 1;
+        let row_number = index + 1;
+
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                failed_rows.push(FailedRow { row: row_number, error: e.to_string(), data: Value::Null });
+                continue;
+            }
+        };
+
+        let row: HashMap<&str, &str> = columns.iter().copied().zip(record.iter()).collect();
+        let data: Map<String, Value> = row.iter().map(|(k, v)| (k.to_string(), Value::from(*v))).collect();
+
+        match mapping.apply(&row, query.numbering) {
+            Ok(question) => {
+                questions.push(question);
+                sources.push((row_number, data));
+            }
+            Err(error) => failed_rows.push(FailedRow { row: row_number, error, data: Value::Object(data) }),
+        }
+    }
+
+    // In abort mode nothing is imported unless every row is valid
+    let mut created = 0;
+    if failed_rows.is_empty() || query.on_error == OnError::Skip {
+        let (inserted, errors) = insert_questions(&pool, topic_id, &questions, query.on_error, query.numbering).await?;
+        created = inserted;
+
+        for e in errors {
+            let (row, data) = sources[e.row - 1].clone();
+            failed_rows.push(FailedRow { row, error: e.error, data: Value::Object(data) });
+        }
+    }
+
+    let errors = failed_rows.iter().map(|f| format!("Row {}: {}", f.row, f.error)).collect();
+    let failed = failed_rows.len();
+
+    let import_id = record_import_run(&pool, NewImportRun {
+        topic_id,
+        source: ImportSource::Csv,
+        source_filename: query.filename,
+        template_name: Some(template.name),
+        imported_by: imported_by(&headers),
+        total_rows,
+        created,
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        failed_rows,
+    })
+    .await;
+
+    Ok(Json(ApiResponse::success(BulkCreateResponse {
+        created,
+        failed,
+        errors,
+        import_id,
+    })))
+}
+
+/// Import a question file: YAML front matter and a Markdown body per question
+pub async fn import_yaml(
+    State(pool): State<PgPool>,
+    Query(query): Query<YamlImportQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<ApiResponse<BulkCreateResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let topic_id = topic::get_topic_id_by_slug(&pool, &query.topic_slug).await?;
+    ensure_not_frozen(&pool, &[topic_id]).await?;
+
+    let parsed = parse_question_file(&body, query.strict)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+
+    let total_rows = parsed.len();
+    let mut questions = Vec::new();
+    // Position in the file of each entry in `questions`, to report insert failures
+    let mut sources = Vec::new();
+    let mut failed_rows = Vec::new();
+
+    for (index, question) in parsed.into_iter().enumerate() {
+        let row = index + 1;
+
+        match question.result {
+            Ok(data) => {
+                questions.push(data);
+                sources.push((row, question.line));
+            }
+            Err(e) => failed_rows.push(FailedRow { row, error: format!("line {}: {}", question.line, e), data: Value::Null }),
+        }
+    }
+
+    // In abort mode nothing is imported unless every question is valid
+    let mut created = 0;
+    if failed_rows.is_empty() || query.on_error == OnError::Skip {
+        let (inserted, errors) = insert_questions(&pool, topic_id, &questions, query.on_error, query.numbering).await?;
+        created = inserted;
+
+        for e in errors {
+            let (row, line) = sources[e.row - 1];
+            failed_rows.push(FailedRow {
+                row,
+                error: format!("line {}: {}", line, e.error),
+                data: serde_json::to_value(&questions[e.row - 1]).unwrap_or_default(),
+            });
+        }
+    }
+
+    failed_rows.sort_by_key(|f| f.row);
+    let errors = failed_rows.iter().map(|f| format!("Question {}: {}", f.row, f.error)).collect();
+    let failed = failed_rows.len();
+
+    let import_id = record_import_run(&pool, NewImportRun {
+        topic_id,
+        source: ImportSource::Yaml,
+        source_filename: query.filename,
+        template_name: None,
+        imported_by: imported_by(&headers),
+        total_rows,
+        created,
+        columns: Vec::new(),
+        failed_rows,
+    })
+    .await;
+
+    Ok(Json(ApiResponse::success(BulkCreateResponse {
+        created,
+        failed,
+        errors,
+        import_id,
+    })))
+}
+
+// Import history handlers
+pub async fn get_imports(
+    State(pool): State<PgPool>,
+    Query(query): Query<ImportRunQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ImportRun>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM import_runs")
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to count imports", e))?;
+
+    let runs = sqlx::query_as::<_, ImportRun>(
+        "SELECT * FROM import_runs ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch imports", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: runs,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// The failed rows of an import as CSV, with `row` and `error` columns in front of the
+/// uploaded columns so the file can be fixed and re-uploaded with the same template
+pub async fn get_import_errors(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let run = sqlx::query_as::<_, ImportRun>("SELECT * FROM import_runs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch import", e))?;
+
+    let Some(run) = run else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ImportNotFound, "Import not found".to_string())),
+        ));
+    };
+
+    let csv = if run.columns.0.is_empty() {
+        // JSON imports: flatten the question objects
+        let rows: Vec<Value> = run
+            .failed_rows
+            .0
+            .into_iter()
+            .map(|f| {
+                let mut row = Map::new();
+                row.insert("row".to_string(), Value::from(f.row));
+                row.insert("error".to_string(), Value::from(f.error));
+                if let Value::Object(data) = f.data {
+                    row.extend(data);
+                }
+                Value::Object(row)
+            })
+            .collect();
+        encoding::to_csv(&rows).map_err(|e| errors::internal("Failed to build error report", e))?
+    } else {
+        errors_with_columns(&run.columns.0, &run.failed_rows.0).map_err(|e| errors::internal("Failed to build error report", e))?
+    };
+
+    let disposition = format!("attachment; filename=\"import-{}-errors.csv\"", run.id);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    ))
+}
+
+// Helper functions
+async fn get_template_by_name(pool: &PgPool, name: &str) -> Result<ImportTemplate, (StatusCode, Json<ApiResponse<()>>)> {
+    let template = sqlx::query_as::<_, ImportTemplate>("SELECT * FROM import_templates WHERE LOWER(name) = LOWER($1)")
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch import template", e))?;
+
+    template.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TemplateNotFound, format!("Import template '{}' not found", name))),
+        )
+    })
+}
+
+/// Who ran an import, as reported by the client in `X-Imported-By`
+pub fn imported_by(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-imported-by")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Store an import in the history. A failure here is logged rather than failing the
+/// import, which has already been committed.
+pub async fn record_import_run(pool: &PgPool, run: NewImportRun) -> Option<Uuid> {
+    let result = sqlx::query_scalar(
+        "INSERT INTO import_runs (
+            topic_id, source, source_filename, template_name, imported_by,
+            total_rows, created, failed, columns, failed_rows
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id"
+    )
+    .bind(run.topic_id)
+    .bind(run.source)
+    .bind(run.source_filename)
+    .bind(run.template_name)
+    .bind(run.imported_by)
+    .bind(run.total_rows as i32)
+    .bind(run.created as i32)
+    .bind(run.failed_rows.len() as i32)
+    .bind(SqlxJson(&run.columns))
+    .bind(SqlxJson(&run.failed_rows))
+    .fetch_one(pool)
+    .await;
+
+    match result {
+        Ok(id) => Some(id),
+        Err(e) => {
+            tracing::error!("Failed to record import run: {}", e);
+            None
+        }
+    }
+}
+
+fn errors_with_columns(columns: &[String], failed_rows: &[FailedRow]) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer.write_record(["row", "error"].into_iter().chain(columns.iter().map(String::as_str)))?;
+    for failed in failed_rows {
+        let cells = columns.iter().map(|column| failed.data.get(column).and_then(Value::as_str).unwrap_or_default());
+        writer.write_record([failed.row.to_string().as_str(), failed.error.as_str()].into_iter().chain(cells))?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
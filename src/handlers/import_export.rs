@@ -0,0 +1,363 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use serde_json::{Map, Value};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    Question, QuestionRow, QuestionFileFormat, QuestionFileImportQuery, QuestionFileExportQuery, QuestionUpload,
+    BulkQuestionData, ImportSource, NewImportRun, FailedRow, OnError, BulkCreateResponse,
+    write_question_rows, unknown_row_keys, QUESTION_ROW_COLUMNS, REQUIRED_QUESTION_ROW_COLUMNS,
+    ApiResponse, ErrorCode,
+};
+use crate::handlers::topic;
+use crate::handlers::freeze::ensure_not_frozen;
+use crate::handlers::question::insert_questions;
+use crate::handlers::import::{imported_by, record_import_run};
+
+/// Questions read per query while an export streams
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+// A parsed row, or why it could not be read, with the row as uploaded for the error report
+struct UploadedRow {
+    result: Result<QuestionRow, String>,
+    data: Value,
+}
+
+struct UploadedFile {
+    // CSV header, empty for JSON
+    columns: Vec<String>,
+    rows: Vec<UploadedRow>,
+}
+
+// Question file handlers
+/// Import a file of question rows, uploaded in the `file` field of a multipart form or
+/// sent as the request body. The format comes from `?format=`, the file's Content-Type
+/// or its extension; every row is validated and failures are reported per row.
+pub async fn import_questions(
+    State(pool): State<PgPool>,
+    Query(query): Query<QuestionFileImportQuery>,
+    headers: HeaderMap,
+    request: Request,
+) -> Result<Json<ApiResponse<BulkCreateResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let upload = read_question_upload(request).await?;
+
+    let Some(format) = query.format.or(upload.format) else {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(ApiResponse::error(
+                ErrorCode::UnsupportedMediaType,
+                "Upload a .csv or .json file, send it as text/csv or application/json, or set ?format=csv|json".to_string(),
+            )),
+        ));
+    };
+
+    let topic_id = topic::get_topic_id_by_slug(&pool, &query.topic_slug).await?;
+    ensure_not_frozen(&pool, &[topic_id]).await?;
+
+    let UploadedFile { columns, rows } = match format {
+        QuestionFileFormat::Csv => read_csv_rows(&upload.body, query.strict)?,
+        QuestionFileFormat::Json => read_json_rows(&upload.body, query.strict)?,
+    };
+
+    let total_rows = rows.len();
+    let mut questions = Vec::new();
+    // Row number and uploaded data of each entry in `questions`, to report insert failures
+    let mut sources = Vec::new();
+    let mut failed_rows = Vec::new();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+
+        match row.result.and_then(|r| r.into_question(query.numbering)) {
+            Ok(question) => {
+                questions.push(question);
+                sources.push((row_number, row.data));
+            }
+            Err(error) => failed_rows.push(FailedRow { row: row_number, error, data: row.data }),
+        }
+    }
+
+    // In abort mode nothing is imported unless every row is valid
+    let mut created = 0;
+    if failed_rows.is_empty() || query.on_error == OnError::Skip {
+        let (inserted, errors) = insert_questions(&pool, topic_id, &questions, query.on_error, query.numbering).await?;
+        created = inserted;
+
+        for e in errors {
+            let (row, data) = sources[e.row - 1].clone();
+            failed_rows.push(FailedRow { row, error: e.error, data });
+        }
+    }
+
+    failed_rows.sort_by_key(|f| f.row);
+    let errors = failed_rows.iter().map(|f| format!("Row {}: {}", f.row, f.error)).collect();
+    let failed = failed_rows.len();
+
+    let import_id = record_import_run(&pool, NewImportRun {
+        topic_id,
+        source: match format {
+            QuestionFileFormat::Csv => ImportSource::Csv,
+            QuestionFileFormat::Json => ImportSource::Json,
+        },
+        source_filename: query.filename.or(upload.filename),
+        template_name: None,
+        imported_by: imported_by(&headers),
+        total_rows,
+        created,
+        columns,
+        failed_rows,
+    })
+    .await;
+
+    Ok(Json(ApiResponse::success(BulkCreateResponse {
+        created,
+        failed,
+        errors,
+        import_id,
+    })))
+}
+
+/// Every question of a topic as a file of question rows, streamed in batches so large
+/// banks are never held in memory. The ETag is known up front, so the response is not
+/// buffered to hash it.
+pub async fn export_questions(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<QuestionFileExportQuery>,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    let topic: Option<(String, i64, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT t.slug, COUNT(q.id), MAX(q.updated_at)
+         FROM topics t
+         LEFT JOIN questions q ON q.topic_id = t.id
+         WHERE t.id = $1
+         GROUP BY t.id"
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let Some((slug, count, last_updated)) = topic else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    };
+
+    let format = query.format;
+    let etag = format!(
+        "W/\"{}-{}-{}\"",
+        format.extension(),
+        count,
+        last_updated.map_or(0, |t| t.timestamp_micros())
+    );
+    let disposition = format!("attachment; filename=\"{}-questions.{}\"", slug, format.extension());
+
+    // Keyset pages by question number; `None` once the file is complete
+    let chunks = stream::unfold(Some((pool, None, true)), move |state| async move {
+        let (pool, after, first) = state?;
+
+        let questions = match fetch_question_batch(&pool, id, after).await {
+            Ok(questions) => questions,
+            Err(e) => return Some((Err(e), None)),
+        };
+
+        let last = (questions.len() as i64) < EXPORT_BATCH_SIZE;
+        let next = questions.last().map(|q| q.question_number).or(after);
+        let rows: Vec<QuestionRow> = questions
+            .into_iter()
+            .map(|q| QuestionRow::from(BulkQuestionData::from(q)))
+            .collect();
+
+        match write_question_rows(format, &rows, first, last) {
+            Ok(chunk) => Some((Ok(chunk), (!last).then_some((pool, next, false)))),
+            Err(e) => Some((Err(anyhow::anyhow!("Failed to write question rows: {}", e)), None)),
+        }
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::ETAG, etag),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response())
+}
+
+// Helper functions
+/// The question file of an import request. A multipart form carries it in the `file`
+/// field (or the first field with a file name); any other request body is the file itself.
+pub async fn read_question_upload(request: Request) -> Result<QuestionUpload, (StatusCode, Json<ApiResponse<()>>)> {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let is_multipart = content_type
+        .split(';')
+        .next()
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("multipart/form-data"));
+
+    if !is_multipart {
+        let format = QuestionFileFormat::from_content_type(content_type);
+        let body = String::from_request(request, &())
+            .await
+            .map_err(|rejection| upload_error(rejection.status(), rejection.body_text()))?;
+
+        return Ok(QuestionUpload { body, format, filename: None });
+    }
+
+    let mut multipart = Multipart::from_request(request, &())
+        .await
+        .map_err(|rejection| upload_error(rejection.status(), rejection.body_text()))?;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| upload_error(e.status(), e.body_text()))? {
+        if field.name() != Some("file") && field.file_name().is_none() {
+            continue;
+        }
+
+        // Browsers send CSV files under several media types, so the extension is the fallback
+        let filename = field.file_name().map(str::to_string);
+        let format = field
+            .content_type()
+            .and_then(QuestionFileFormat::from_content_type)
+            .or_else(|| filename.as_deref().and_then(QuestionFileFormat::from_filename));
+        let body = field.text().await.map_err(|e| upload_error(e.status(), e.body_text()))?;
+
+        return Ok(QuestionUpload { body, format, filename });
+    }
+
+    Err(upload_error(
+        StatusCode::BAD_REQUEST,
+        "The form has no file; upload it in a field named `file`".to_string(),
+    ))
+}
+
+fn upload_error(status: StatusCode, message: String) -> (StatusCode, Json<ApiResponse<()>>) {
+    let code = match status {
+        StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+        _ => ErrorCode::ValidationFailed,
+    };
+
+    (status, Json(ApiResponse::error(code, message)))
+}
+
+/// The next batch of a topic's questions by number, from the first when `after` is `None`
+/// (question numbers are not guaranteed to be positive)
+async fn fetch_question_batch(pool: &PgPool, topic_id: Uuid, after: Option<i32>) -> anyhow::Result<Vec<Question>> {
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions
+         WHERE topic_id = $1 AND ($2::INT IS NULL OR question_number > $2)
+         ORDER BY question_number
+         LIMIT $3"
+    )
+    .bind(topic_id)
+    .bind(after)
+    .bind(EXPORT_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(questions)
+}
+
+/// The header and rows of a CSV question file. Files without the required columns, or
+/// with unknown ones in strict mode, are rejected as a whole.
+fn read_csv_rows(body: &str, strict: bool) -> Result<UploadedFile, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(body.as_bytes());
+
+    let header_record = reader.headers().cloned().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::InvalidCsv, format!("Failed to read CSV header: {}", e))),
+        )
+    })?;
+    let columns: Vec<String> = header_record.iter().map(str::to_string).collect();
+
+    let missing: Vec<&str> = REQUIRED_QUESTION_ROW_COLUMNS
+        .into_iter()
+        .filter(|column| !columns.iter().any(|c| c == column))
+        .collect();
+    if !missing.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::CsvColumnsMissing, format!("CSV is missing columns: {}", missing.join(", ")))),
+        ));
+    }
+
+    if strict {
+        let unknown: Vec<&str> = columns
+            .iter()
+            .map(String::as_str)
+            .filter(|column| !QUESTION_ROW_COLUMNS.contains(column))
+            .collect();
+        if !unknown.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::UnknownFields, format!(
+                    "CSV has columns that are not part of the row schema (strict mode): {}",
+                    unknown.join(", ")
+                ))),
+            ));
+        }
+    }
+
+    let rows = reader
+        .records()
+        .map(|record| match record {
+            Ok(record) => {
+                let cells: HashMap<&str, &str> = columns.iter().map(String::as_str).zip(record.iter()).collect();
+                let data: Map<String, Value> = cells.iter().map(|(k, v)| (k.to_string(), Value::from(*v))).collect();
+
+                UploadedRow {
+                    result: record.deserialize(Some(&header_record)).map_err(|e| e.to_string()),
+                    data: Value::Object(data),
+                }
+            }
+            Err(e) => UploadedRow { result: Err(e.to_string()), data: Value::Null },
+        })
+        .collect();
+
+    Ok(UploadedFile { columns, rows })
+}
+
+/// The rows of a JSON question file, an array of row objects. In strict mode, rows with
+/// keys that are not part of the row schema fail on their own.
+fn read_json_rows(body: &str, strict: bool) -> Result<UploadedFile, (StatusCode, Json<ApiResponse<()>>)> {
+    let values: Vec<Value> = serde_json::from_str(body).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("Expected a JSON array of question rows: {}", e))),
+        )
+    })?;
+
+    let rows = values
+        .into_iter()
+        .map(|value| {
+            let unknown = unknown_row_keys(&value);
+            let result = if strict && !unknown.is_empty() {
+                Err(format!("Unknown fields in strict mode: {}", unknown.join(", ")))
+            } else {
+                serde_json::from_value(value.clone()).map_err(|e| e.to_string())
+            };
+
+            UploadedRow { result, data: value }
+        })
+        .collect();
+
+    Ok(UploadedFile { columns: Vec::new(), rows })
+}
@@ -0,0 +1,165 @@
+use axum::{
+    http::header,
+    response::IntoResponse,
+    Json
+};
+use std::env;
+
+use crate::models::{Capabilities, RouteInfo, ApiResponse};
+
+/// Every route under `/api` with its methods; keep in step with the router in main.rs.
+/// GET routes also answer HEAD. Path normalization matches requests against these.
+pub const ROUTES: &[(&str, &[&str])] = &[
+    ("/", &["OPTIONS"]),
+    ("/health", &["GET"]),
+    ("/providers", &["GET", "POST"]),
+    ("/providers/{id}", &["GET", "PUT", "DELETE"]),
+    ("/providers/{id}/certifications", &["GET"]),
+    ("/certifications", &["GET", "POST"]),
+    ("/certifications/{id}", &["GET", "PUT", "DELETE"]),
+    ("/certifications/{id}/topics", &["GET"]),
+    ("/certifications/{id}/dependencies", &["GET"]),
+    ("/certifications/{id}/questions", &["GET"]),
+    ("/certifications/{id}/questions/order", &["PUT"]),
+    ("/certifications/{id}/objectives", &["GET", "POST"]),
+    ("/certifications/{id}/objectives/coverage", &["GET"]),
+    ("/objectives/{id}", &["GET", "PUT", "DELETE"]),
+    ("/certifications/{id}/bundle", &["GET"]),
+    ("/topics", &["GET", "POST"]),
+    ("/topics/{id}", &["GET", "PUT", "DELETE"]),
+    ("/topics/bulk", &["POST"]),
+    ("/topics/slug/{slug}", &["GET"]),
+    ("/topics/{id}/full", &["GET"]),
+    ("/topics/{id}/dependencies", &["GET"]),
+    ("/topics/{id}/question-graph", &["GET"]),
+    ("/topics/{id}/feed.atom", &["GET"]),
+    ("/topics/{id}/bundle", &["GET"]),
+    ("/topics/{id}/questions/export", &["GET"]),
+    ("/questions", &["GET", "POST"]),
+    ("/questions/bulk", &["POST"]),
+    ("/questions/import", &["POST"]),
+    ("/questions/{id}", &["GET", "PUT", "DELETE"]),
+    ("/questions/{id}/variant", &["GET"]),
+    ("/questions/{id}/timeline", &["GET"]),
+    ("/questions/{id}/prerequisites", &["PUT"]),
+    ("/questions/{id}/objectives", &["GET", "PUT"]),
+    ("/diagrams/{hash}/image.svg", &["GET"]),
+    ("/questions/{id}/explanation/vote", &["POST", "DELETE"]),
+    ("/questions/{id}/explanations", &["GET", "POST"]),
+    ("/explanations/{id}/vote", &["POST"]),
+    ("/custom-fields", &["GET"]),
+    ("/questions/topic/{topic_id}", &["GET"]),
+    ("/questions/type/{question_type}", &["GET"]),
+    ("/questions/search/{query}", &["GET"]),
+    ("/import/templates", &["GET", "POST"]),
+    ("/import/templates/{name}", &["GET", "DELETE"]),
+    ("/import/csv", &["POST"]),
+    ("/import/yaml", &["POST"]),
+    ("/imports", &["GET"]),
+    ("/imports/{id}/errors.csv", &["GET"]),
+    ("/catalog", &["GET"]),
+    ("/share", &["POST"]),
+    ("/share/{code}", &["GET"]),
+    ("/share/{code}/qr.png", &["GET"]),
+    ("/embed/{token}/config", &["GET"]),
+    ("/admin/embeds", &["GET", "POST"]),
+    ("/admin/embeds/{id}", &["DELETE"]),
+    ("/feed.json", &["GET"]),
+    ("/public/topics", &["GET"]),
+    ("/public/topics/{slug}/questions", &["GET"]),
+    ("/search", &["GET"]),
+    ("/search/suggest", &["GET"]),
+    ("/quizzes/generate", &["GET"]),
+    ("/quiz/sessions", &["POST"]),
+    ("/quiz/sessions/{id}", &["GET"]),
+    ("/quiz/sessions/{id}/answers/{question_id}", &["PUT"]),
+    ("/quiz/sessions/{id}/questions/{question_id}/mark", &["POST", "DELETE"]),
+    ("/quiz/sessions/{id}/questions/{question_id}/hint", &["POST"]),
+    ("/quiz/sessions/{id}/answers/batch", &["POST"]),
+    ("/quiz/sessions/{id}/pause", &["POST"]),
+    ("/quiz/sessions/{id}/resume", &["POST"]),
+    ("/quiz/sessions/{id}/submit", &["POST"]),
+    ("/admin/questions/{id}/regrade", &["POST"]),
+    ("/admin/regrade-jobs/{id}", &["GET"]),
+    ("/admin/rebuild", &["POST"]),
+    ("/admin/rebuild-jobs/{id}", &["GET"]),
+    ("/sync/results", &["POST"]),
+    ("/changes/deletions", &["GET"]),
+    ("/analytics/topics", &["GET"]),
+    ("/analytics/questions", &["GET"]),
+    ("/analytics/calibration", &["GET"]),
+    ("/analytics/cognitive-levels", &["GET"]),
+    ("/admin/analytics/refresh", &["POST"]),
+    ("/admin/freezes", &["GET", "POST"]),
+    ("/admin/freezes/{id}", &["DELETE"]),
+    ("/admin/explanations/{id}", &["PUT", "DELETE"]),
+    ("/admin/reports/explanations", &["GET"]),
+    ("/admin/passback/targets", &["GET", "POST"]),
+    ("/admin/passback/targets/{id}", &["DELETE"]),
+    ("/admin/passback/deliveries", &["GET"]),
+    ("/admin/api-keys", &["GET", "POST"]),
+    ("/admin/api-keys/{id}", &["PUT", "DELETE"]),
+    ("/admin/api-keys/{id}/block", &["DELETE"]),
+    ("/admin/alerts", &["GET"]),
+    ("/admin/access-log", &["GET"]),
+    ("/admin/watermarks/trace", &["POST"]),
+    ("/admin/custom-fields", &["POST"]),
+    ("/admin/custom-fields/{key}", &["DELETE"]),
+    ("/admin/events", &["GET"]),
+    ("/admin/events/stream", &["GET"]),
+    ("/admin/webhooks", &["GET", "POST"]),
+    ("/admin/webhooks/{id}", &["DELETE"]),
+    ("/integrations/triggers/{event}", &["GET"]),
+    ("/admin/slow-queries", &["GET"]),
+    ("/admin/consistency/timestamps", &["GET"]),
+    ("/admin/accessibility", &["GET"]),
+    ("/admin/manifest", &["GET"]),
+    ("/admin/diff", &["POST"]),
+    ("/admin/apply", &["POST"]),
+    ("/admin/export/{slug}", &["GET"]),
+];
+
+// Capability handlers
+/// `OPTIONS /api`: what this instance serves, for client auto-configuration
+pub async fn get_capabilities() -> impl IntoResponse {
+    let routes = ROUTES
+        .iter()
+        .map(|(path, methods)| RouteInfo {
+            path,
+            methods: methods.to_vec(),
+        })
+        .collect();
+
+    let features = [
+        ("content_sync", env::var("SYNC_UPSTREAM_URL").is_ok()),
+        ("grade_passback", true),
+        ("public_api", true),
+        ("watermarks", true),
+        ("scraping_detection", true),
+        ("custom_fields", true),
+        ("response_views", true),
+        ("math_mathml", true),
+        ("code_blocks", true),
+        ("diagram_rendering", env::var("DIAGRAM_RENDERER_URL").is_ok()),
+        ("question_prerequisites", true),
+        ("cognitive_levels", true),
+        ("accessibility_audit", true),
+        ("conditional_requests", true),
+        ("webhooks", true),
+        ("event_stream", true),
+        ("integration_triggers", true),
+    ]
+    .into_iter()
+    .collect();
+
+    let capabilities = Capabilities {
+        name: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+        base_path: "/api",
+        response_formats: vec!["application/json", "text/csv", "application/msgpack"],
+        features,
+        routes,
+    };
+
+    ([(header::ALLOW, "OPTIONS")], Json(ApiResponse::success(capabilities)))
+}
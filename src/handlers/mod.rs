@@ -1,5 +1,24 @@
 pub mod provider;
 pub mod certification;
+pub mod objective;
 pub mod topic;
 pub mod question;
-pub mod quiz;
\ No newline at end of file
+pub mod prerequisite;
+pub mod diagram;
+pub mod quiz;
+pub mod admin;
+pub mod sync;
+pub mod analytics;
+pub mod freeze;
+pub mod search;
+pub mod import;
+pub mod import_export;
+pub mod catalog;
+pub mod embed;
+pub mod share;
+pub mod explanation;
+pub mod passback;
+pub mod public;
+pub mod custom_field;
+pub mod meta;
+pub mod outbox;
@@ -0,0 +1,414 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    compare_objective_codes, is_valid_objective_code, parent_objective_code, ApiResponse, CertificationCoverage,
+    CreateObjective, ErrorCode, Objective, ObjectiveCoverage, SetQuestionObjectives, UpdateObjective,
+};
+use crate::handlers::freeze::ensure_question_not_frozen;
+
+/// Most objectives one certification may have; exam guides list far fewer
+const MAX_OBJECTIVES: i64 = 500;
+
+// Objective handlers
+/// A certification's objectives in exam-guide order. Not paged: a certification has at
+/// most `MAX_OBJECTIVES`.
+pub async fn get_objectives(
+    State(pool): State<PgPool>,
+    Path(certification_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Objective>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM certifications WHERE id = $1)")
+        .bind(certification_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    if !exists {
+        return Err(certification_not_found());
+    }
+
+    let mut objectives = sqlx::query_as::<_, Objective>("SELECT * FROM certification_objectives WHERE certification_id = $1")
+        .bind(certification_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch objectives", e))?;
+    objectives.sort_by(|a, b| compare_objective_codes(&a.code, &b.code));
+
+    Ok(Json(ApiResponse::success(objectives)))
+}
+
+pub async fn get_objective(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Objective>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let objective = sqlx::query_as::<_, Objective>("SELECT * FROM certification_objectives WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch objective", e))?;
+
+    match objective {
+        Some(objective) => Ok(Json(ApiResponse::success(objective))),
+        None => Err(not_found()),
+    }
+}
+
+/// Add an objective. Any objective it sits under must exist first.
+pub async fn create_objective(
+    State(pool): State<PgPool>,
+    Path(certification_id): Path<Uuid>,
+    Json(payload): Json<CreateObjective>,
+) -> Result<Json<ApiResponse<Objective>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let code = payload.code.trim();
+    if !is_valid_objective_code(code) {
+        return Err(validation_error(format!(
+            "'{}' is not a valid objective code; use dotted letters and digits such as 2.1",
+            code
+        )));
+    }
+    if payload.title.trim().is_empty() {
+        return Err(validation_error("title must not be empty".to_string()));
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+    lock_certification(&mut transaction, certification_id).await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM certification_objectives WHERE certification_id = $1")
+        .bind(certification_id)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to count objectives", e))?;
+
+    if count >= MAX_OBJECTIVES {
+        return Err(validation_error(format!("A certification may have at most {} objectives", MAX_OBJECTIVES)));
+    }
+
+    if let Some(parent) = parent_objective_code(code) {
+        let parent_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM certification_objectives WHERE certification_id = $1 AND code = $2)"
+        )
+        .bind(certification_id)
+        .bind(parent)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch objective", e))?;
+
+        if !parent_exists {
+            return Err(validation_error(format!("Add objective {} before the objectives under it", parent)));
+        }
+    }
+
+    let objective = sqlx::query_as::<_, Objective>(
+        "INSERT INTO certification_objectives (certification_id, code, title, description)
+         VALUES ($1, $2, $3, $4) RETURNING *"
+    )
+    .bind(certification_id)
+    .bind(code)
+    .bind(payload.title.trim())
+    .bind(payload.description)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateObjectiveCode, format!("The certification already has objective {}", code))),
+        ),
+        _ => errors::internal("Failed to create objective", e),
+    })?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(objective)))
+}
+
+pub async fn update_objective(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateObjective>,
+) -> Result<Json<ApiResponse<Objective>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let objective = sqlx::query_as::<_, Objective>(
+        "UPDATE certification_objectives SET
+            title = COALESCE($1, title),
+            description = COALESCE($2, description),
+            updated_at = NOW()
+         WHERE id = $3 RETURNING *"
+    )
+    .bind(payload.title.as_deref().map(str::trim).filter(|t| !t.is_empty()))
+    .bind(payload.description)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to update objective", e))?;
+
+    match objective {
+        Some(objective) => Ok(Json(ApiResponse::success(objective))),
+        None => Err(not_found()),
+    }
+}
+
+/// Delete an objective and its question mappings. Objectives under it must be deleted first.
+pub async fn delete_objective(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    let objective = sqlx::query_as::<_, Objective>("SELECT * FROM certification_objectives WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch objective", e))?;
+
+    let Some(objective) = objective else {
+        return Err(not_found());
+    };
+
+    // Serializes with objectives being added under this one
+    lock_certification(&mut transaction, objective.certification_id).await?;
+
+    let has_children: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM certification_objectives WHERE certification_id = $1 AND starts_with(code, $2 || '.'))"
+    )
+    .bind(objective.certification_id)
+    .bind(&objective.code)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch objectives", e))?;
+
+    if has_children {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::ObjectiveHasChildren,
+                format!("Delete the objectives under {} first", objective.code),
+            )),
+        ));
+    }
+
+    sqlx::query("DELETE FROM certification_objectives WHERE id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to delete objective", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// The objectives a question tests. Not paged: they come from one certification, which has
+/// at most `MAX_OBJECTIVES`.
+pub async fn get_question_objectives(
+    State(pool): State<PgPool>,
+    Path(question_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Objective>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    question_certification(&pool, question_id).await?;
+
+    let objectives = question_objectives(&pool, question_id).await?;
+
+    Ok(Json(ApiResponse::success(objectives)))
+}
+
+/// Replace the objectives a question tests. They must belong to the certification of the
+/// question's topic.
+pub async fn set_question_objectives(
+    State(pool): State<PgPool>,
+    Path(question_id): Path<Uuid>,
+    Json(payload): Json<SetQuestionObjectives>,
+) -> Result<Json<ApiResponse<Vec<Objective>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_question_not_frozen(&pool, question_id).await?;
+
+    let mut objective_ids = payload.objective_ids;
+    let mut seen = HashSet::new();
+    objective_ids.retain(|o| seen.insert(*o));
+
+    let Some(certification_id) = question_certification(&pool, question_id).await? else {
+        if objective_ids.is_empty() {
+            return Ok(Json(ApiResponse::success(Vec::new())));
+        }
+        return Err(validation_error("The question's topic is not linked to a certification".to_string()));
+    };
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+    lock_certification(&mut transaction, certification_id).await?;
+
+    let outside: Option<Uuid> = sqlx::query_scalar(
+        "SELECT objective_id FROM UNNEST($2::uuid[]) AS objective_id
+         WHERE NOT EXISTS (
+            SELECT 1 FROM certification_objectives WHERE id = objective_id AND certification_id = $1
+         )
+         LIMIT 1"
+    )
+    .bind(certification_id)
+    .bind(&objective_ids)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch objectives", e))?;
+
+    if let Some(objective_id) = outside {
+        return Err(validation_error(format!("Objective {} is not an objective of the question's certification", objective_id)));
+    }
+
+    sqlx::query("DELETE FROM question_objectives WHERE question_id = $1")
+        .bind(question_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to update objectives", e))?;
+
+    sqlx::query(
+        "INSERT INTO question_objectives (question_id, objective_id)
+         SELECT $1, objective_id FROM UNNEST($2::uuid[]) AS objective_id"
+    )
+    .bind(question_id)
+    .bind(&objective_ids)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to update objectives", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    let objectives = question_objectives(&pool, question_id).await?;
+
+    Ok(Json(ApiResponse::success(objectives)))
+}
+
+/// How many questions test each objective and how learners do on them. An objective counts
+/// the questions of the objectives under it. Answers come from the analytics views, as of
+/// their last refresh.
+pub async fn get_objective_coverage(
+    State(pool): State<PgPool>,
+    Path(certification_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<CertificationCoverage>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let totals: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT
+            (SELECT COUNT(*) FROM questions q
+                JOIN topics t ON q.topic_id = t.id
+                WHERE t.certification_id = c.id),
+            (SELECT COUNT(DISTINCT q.id) FROM questions q
+                JOIN topics t ON q.topic_id = t.id
+                JOIN question_objectives qo ON qo.question_id = q.id
+                JOIN certification_objectives o ON o.id = qo.objective_id
+                WHERE t.certification_id = c.id AND o.certification_id = c.id)
+         FROM certifications c WHERE c.id = $1"
+    )
+    .bind(certification_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    let Some((question_count, mapped_question_count)) = totals else {
+        return Err(certification_not_found());
+    };
+
+    // Mappings of questions since moved out of the certification are not counted
+    let mut objectives = sqlx::query_as::<_, ObjectiveCoverage>(
+        "SELECT o.id AS objective_id, o.code, o.title,
+            COUNT(q.id) AS question_count,
+            COALESCE(SUM(s.attempts), 0)::BIGINT AS attempts,
+            COALESCE(SUM(s.correct), 0)::BIGINT AS correct,
+            (SUM(s.correct)::float8 / NULLIF(SUM(s.attempts), 0)::float8) AS accuracy
+         FROM certification_objectives o
+         LEFT JOIN LATERAL (
+            SELECT DISTINCT qo.question_id AS id
+            FROM question_objectives qo
+            JOIN certification_objectives mapped ON mapped.id = qo.objective_id
+            JOIN questions mq ON mq.id = qo.question_id
+            JOIN topics t ON t.id = mq.topic_id AND t.certification_id = o.certification_id
+            WHERE mapped.certification_id = o.certification_id
+              AND (mapped.code = o.code OR starts_with(mapped.code, o.code || '.'))
+         ) q ON TRUE
+         LEFT JOIN question_stats s ON s.question_id = q.id
+         WHERE o.certification_id = $1
+         GROUP BY o.id"
+    )
+    .bind(certification_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch objective coverage", e))?;
+    objectives.sort_by(|a, b| compare_objective_codes(&a.code, &b.code));
+
+    Ok(Json(ApiResponse::success(CertificationCoverage {
+        certification_id,
+        question_count,
+        mapped_question_count,
+        objectives,
+    })))
+}
+
+// Helper functions
+/// The certification of a question's topic, if it has one
+async fn question_certification(pool: &PgPool, question_id: Uuid) -> Result<Option<Uuid>, (StatusCode, Json<ApiResponse<()>>)> {
+    let certification: Option<Option<Uuid>> = sqlx::query_scalar(
+        "SELECT t.certification_id FROM questions q JOIN topics t ON q.topic_id = t.id WHERE q.id = $1"
+    )
+    .bind(question_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    certification.ok_or_else(|| (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+    ))
+}
+
+/// A question's objectives in exam-guide order, leaving out those of a certification the
+/// question has since moved away from
+async fn question_objectives(pool: &PgPool, question_id: Uuid) -> Result<Vec<Objective>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut objectives = sqlx::query_as::<_, Objective>(
+        "SELECT o.* FROM certification_objectives o
+         JOIN question_objectives qo ON qo.objective_id = o.id
+         JOIN questions q ON q.id = qo.question_id
+         JOIN topics t ON t.id = q.topic_id AND t.certification_id = o.certification_id
+         WHERE qo.question_id = $1"
+    )
+    .bind(question_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch objectives", e))?;
+    objectives.sort_by(|a, b| compare_objective_codes(&a.code, &b.code));
+
+    Ok(objectives)
+}
+
+/// Lock a certification's row so changes to its objectives are applied one after the other
+async fn lock_certification(
+    transaction: &mut Transaction<'_, Postgres>,
+    certification_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: Option<Uuid> = sqlx::query_scalar("SELECT id FROM certifications WHERE id = $1 FOR UPDATE")
+        .bind(certification_id)
+        .fetch_optional(&mut **transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    match exists {
+        Some(_) => Ok(()),
+        None => Err(certification_not_found()),
+    }
+}
+
+fn validation_error(message: String) -> (StatusCode, Json<ApiResponse<()>>) {
+    (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, message)))
+}
+
+fn not_found() -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::ObjectiveNotFound, "Objective not found".to_string())),
+    )
+}
+
+fn certification_not_found() -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+    )
+}
@@ -0,0 +1,234 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    Json
+};
+use futures_util::stream::{self, Stream};
+use sqlx::PgPool;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::http_client;
+use crate::models::{
+    OutboxEvent, OutboxEventQuery, WebhookSubscription, CreateWebhookSubscription, EVENT_TYPES,
+    TriggerItem, TriggerQuery, PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode,
+};
+
+/// How often an open event stream checks for new events
+const STREAM_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Event handlers
+/// Events after the `after` sequence number. Not page-numbered: the log is read with
+/// `after` as a cursor, `limit` events at a time.
+pub async fn get_events(
+    State(pool): State<PgPool>,
+    Query(query): Query<OutboxEventQuery>,
+) -> Result<Json<ApiResponse<Vec<OutboxEvent>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let events = fetch_events(&pool, query.after.unwrap_or(0), query.limit.unwrap_or(100).clamp(1, 1000))
+        .await
+        .map_err(|e| errors::internal("Failed to fetch events", e))?;
+
+    Ok(Json(ApiResponse::success(events)))
+}
+
+/// Server-sent events from the outbox. Resumes after `Last-Event-ID` (or `?after=`)
+/// when given, otherwise starts with events sequenced from now on.
+pub async fn stream_events(
+    State(pool): State<PgPool>,
+    Query(query): Query<OutboxEventQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let resume_from = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .or(query.after);
+
+    let after = match resume_from {
+        Some(after) => after,
+        None => sqlx::query_scalar("SELECT last_seq FROM outbox_sequence")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| errors::internal("Failed to fetch event position", e))?,
+    };
+
+    let events = stream::unfold((pool, after, VecDeque::new()), |(pool, mut after, mut pending)| async move {
+        loop {
+            if let Some(event) = pending.pop_front() {
+                let OutboxEvent { seq, ref event_type, .. } = event;
+                after = seq;
+
+                let sse = Event::default()
+                    .id(seq.to_string())
+                    .event(event_type)
+                    .json_data(&event)
+                    .unwrap_or_else(|_| Event::default().comment("unserializable event"));
+
+                return Some((Ok(sse), (pool, after, pending)));
+            }
+
+            match fetch_events(&pool, after, 100).await {
+                Ok(events) if !events.is_empty() => pending.extend(events),
+                Ok(_) => tokio::time::sleep(STREAM_POLL_INTERVAL).await,
+                Err(e) => {
+                    warn!("Event stream failed to fetch events: {}", e);
+                    tokio::time::sleep(STREAM_POLL_INTERVAL).await;
+                }
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+// Webhook subscription handlers
+pub async fn get_webhooks(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<WebhookSubscription>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, subscriptions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM webhook_subscriptions").fetch_one(&pool),
+        sqlx::query_as::<_, WebhookSubscription>(
+            "SELECT * FROM webhook_subscriptions ORDER BY created_at DESC, id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch webhooks", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: subscriptions,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// New subscriptions start at the current end of the log
+pub async fn create_webhook(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateWebhookSubscription>,
+) -> Result<Json<ApiResponse<WebhookSubscription>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let url = payload.url.trim();
+
+    if http_client::parse_endpoint(url).is_err() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("'{}' is not a valid endpoint; use an https:// or http:// URL", payload.url))),
+        ));
+    }
+
+    if payload.secret.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "secret must not be empty".to_string())),
+        ));
+    }
+
+    if let Some(unknown) = payload
+        .event_types
+        .iter()
+        .flatten()
+        .find(|t| !EVENT_TYPES.contains(&t.as_str()))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!(
+                "Unknown event type '{}'; use one of {}",
+                unknown,
+                EVENT_TYPES.join(", ")
+            ))),
+        ));
+    }
+
+    let subscription = sqlx::query_as::<_, WebhookSubscription>(
+        "INSERT INTO webhook_subscriptions (url, secret, event_types, delivery, last_seq)
+         VALUES ($1, $2, $3, $4, (SELECT last_seq FROM outbox_sequence))
+         RETURNING *"
+    )
+    .bind(url)
+    .bind(&payload.secret)
+    .bind(&payload.event_types)
+    .bind(payload.delivery)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create webhook", e))?;
+
+    Ok(Json(ApiResponse::success(subscription)))
+}
+
+pub async fn delete_webhook(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete webhook", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::WebhookNotFound, "Webhook not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Trigger handlers
+/// Polling trigger for automation platforms: events of one type as a bare JSON array,
+/// newest first, each with a unique `id`
+pub async fn get_trigger_events(
+    State(pool): State<PgPool>,
+    Path(event): Path<String>,
+    Query(query): Query<TriggerQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiResponse<()>>)> {
+    if !EVENT_TYPES.contains(&event.as_str()) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::RouteNotFound, format!(
+                "Unknown trigger '{}'; use one of {}",
+                event,
+                EVENT_TYPES.join(", ")
+            ))),
+        ));
+    }
+
+    let events = sqlx::query_as::<_, OutboxEvent>(
+        "SELECT * FROM outbox_events
+         WHERE seq > $1 AND event_type = $2
+         ORDER BY seq DESC
+         LIMIT $3"
+    )
+    .bind(query.since.unwrap_or(0))
+    .bind(&event)
+    .bind(query.limit.unwrap_or(50).clamp(1, 100))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch events", e))?;
+
+    let items: Vec<TriggerItem> = events.iter().map(TriggerItem::from).collect();
+    let items = serde_json::to_value(items).map_err(|e| errors::internal("Failed to encode events", e))?;
+
+    Ok(Json(items))
+}
+
+// Helper functions
+/// Sequenced events after `after`, in delivery order
+async fn fetch_events(pool: &PgPool, after: i64, limit: i64) -> Result<Vec<OutboxEvent>, sqlx::Error> {
+    sqlx::query_as::<_, OutboxEvent>(
+        "SELECT * FROM outbox_events WHERE seq > $1 ORDER BY seq LIMIT $2"
+    )
+    .bind(after)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
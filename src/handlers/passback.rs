@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::http_client;
+use crate::models::{
+    PassbackTarget, PassbackDelivery, CreatePassbackTarget, PassbackDeliveryQuery,
+    PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode,
+};
+
+// Passback target handlers
+pub async fn get_passback_targets(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<PassbackTarget>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, targets) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM grade_passback_targets").fetch_one(&pool),
+        sqlx::query_as::<_, PassbackTarget>(
+            "SELECT * FROM grade_passback_targets ORDER BY created_at DESC, id LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch passback targets", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: targets,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_passback_target(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreatePassbackTarget>,
+) -> Result<Json<ApiResponse<PassbackTarget>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let endpoint_url = payload.endpoint_url.trim();
+
+    if http_client::parse_endpoint(endpoint_url).is_err() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!(
+                "'{}' is not a valid endpoint; use an https:// or http:// URL",
+                payload.endpoint_url
+            ))),
+        ));
+    }
+
+    if payload.secret.is_empty() || payload.assignment_ref.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "secret and assignment_ref must not be empty".to_string())),
+        ));
+    }
+
+    let topic: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM topics WHERE id = $1")
+        .bind(payload.topic_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    if topic.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    }
+
+    let target = sqlx::query_as::<_, PassbackTarget>(
+        "INSERT INTO grade_passback_targets (topic_id, endpoint_url, secret, assignment_ref)
+         VALUES ($1, $2, $3, $4) RETURNING *"
+    )
+    .bind(payload.topic_id)
+    .bind(endpoint_url)
+    .bind(&payload.secret)
+    .bind(payload.assignment_ref.trim())
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create passback target", e))?;
+
+    Ok(Json(ApiResponse::success(target)))
+}
+
+pub async fn delete_passback_target(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM grade_passback_targets WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete passback target", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::PassbackTargetNotFound, "Passback target not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Delivery handlers
+pub async fn get_passback_deliveries(
+    State(pool): State<PgPool>,
+    Query(params): Query<PassbackDeliveryQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<PassbackDelivery>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: params.page, limit: params.limit }.resolve();
+
+    let (total_count, deliveries) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM grade_passback_deliveries WHERE $1::delivery_status IS NULL OR status = $1"
+        )
+        .bind(&params.status)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, PassbackDelivery>(
+            "SELECT * FROM grade_passback_deliveries
+             WHERE $1::delivery_status IS NULL OR status = $1
+             ORDER BY created_at DESC, id
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(&params.status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch passback deliveries", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: deliveries,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
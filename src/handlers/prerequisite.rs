@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::{PgExecutor, PgPool};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    ApiResponse, ErrorCode, PrerequisiteEdge, QuestionGraph, QuestionNode, SetPrerequisites, prerequisite_order,
+};
+use crate::handlers::freeze::ensure_question_not_frozen;
+
+/// Most prerequisites one question may have
+const MAX_PREREQUISITES: usize = 20;
+
+// Prerequisite handlers
+/// Replace a question's prerequisites and return the new edges. Not paged: a question
+/// has at most `MAX_PREREQUISITES`.
+pub async fn set_prerequisites(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetPrerequisites>,
+) -> Result<Json<ApiResponse<Vec<PrerequisiteEdge>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_question_not_frozen(&pool, id).await?;
+
+    let mut prerequisite_ids = payload.prerequisite_ids;
+    let mut seen = HashSet::new();
+    prerequisite_ids.retain(|p| seen.insert(*p));
+
+    if prerequisite_ids.len() > MAX_PREREQUISITES {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("A question may have at most {} prerequisites", MAX_PREREQUISITES))),
+        ));
+    }
+    if prerequisite_ids.contains(&id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "A question cannot be its own prerequisite".to_string())),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    let topic_id: Option<Uuid> = sqlx::query_scalar("SELECT topic_id FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    let Some(topic_id) = topic_id else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    };
+
+    // Lock the topic so concurrent edits cannot close a cycle between them
+    sqlx::query("SELECT id FROM topics WHERE id = $1 FOR UPDATE")
+        .bind(topic_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to lock topic", e))?;
+
+    let topic_questions: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM questions WHERE topic_id = $1 ORDER BY question_number")
+        .bind(topic_id)
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    if let Some(missing) = prerequisite_ids.iter().find(|p| !topic_questions.contains(p)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationFailed,
+                format!("Prerequisite {} is not a question of the same topic", missing),
+            )),
+        ));
+    }
+
+    let mut edges: Vec<PrerequisiteEdge> = topic_edges(&mut *transaction, topic_id)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch prerequisites", e))?;
+    edges.retain(|edge| edge.question_id != id);
+    edges.extend(prerequisite_ids.iter().map(|&prerequisite_id| PrerequisiteEdge { prerequisite_id, question_id: id }));
+
+    if prerequisite_order(&topic_questions, &edges).is_none() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::PrerequisiteCycle,
+                "These prerequisites would make the question depend on itself".to_string(),
+            )),
+        ));
+    }
+
+    sqlx::query("DELETE FROM question_prerequisites WHERE question_id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to update prerequisites", e))?;
+
+    sqlx::query(
+        "INSERT INTO question_prerequisites (question_id, prerequisite_id)
+         SELECT $1, prerequisite_id FROM UNNEST($2::uuid[]) AS prerequisite_id"
+    )
+    .bind(id)
+    .bind(&prerequisite_ids)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to update prerequisites", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    let edges = prerequisite_ids
+        .into_iter()
+        .map(|prerequisite_id| PrerequisiteEdge { prerequisite_id, question_id: id })
+        .collect();
+
+    Ok(Json(ApiResponse::success(edges)))
+}
+
+pub async fn get_question_graph(
+    State(pool): State<PgPool>,
+    Path(topic_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<QuestionGraph>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let exists: Option<Uuid> = sqlx::query_scalar("SELECT id FROM topics WHERE id = $1")
+        .bind(topic_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    }
+
+    let nodes = sqlx::query_as::<_, QuestionNode>(
+        "SELECT id, question_number, question FROM questions WHERE topic_id = $1 ORDER BY question_number"
+    )
+    .bind(topic_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let edges = topic_edges(&pool, topic_id)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch prerequisites", e))?;
+
+    let ids: Vec<Uuid> = nodes.iter().map(|n| n.id).collect();
+    let order = prerequisite_order(&ids, &edges).unwrap_or(ids);
+
+    Ok(Json(ApiResponse::success(QuestionGraph { topic_id, nodes, edges, order })))
+}
+
+/// Every prerequisite edge between questions of a topic
+pub async fn topic_edges<'e>(executor: impl PgExecutor<'e>, topic_id: Uuid) -> Result<Vec<PrerequisiteEdge>, sqlx::Error> {
+    sqlx::query_as::<_, PrerequisiteEdge>(
+        "SELECT p.prerequisite_id, p.question_id
+         FROM question_prerequisites p
+         JOIN questions q ON q.id = p.question_id
+         WHERE q.topic_id = $1
+         ORDER BY p.question_id, p.prerequisite_id"
+    )
+    .bind(topic_id)
+    .fetch_all(executor)
+    .await
+}
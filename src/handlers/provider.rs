@@ -0,0 +1,237 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    generate_slug, ApiResponse, ErrorCode, Provider, ProviderWithCounts, CreateProvider, UpdateProvider,
+    DeleteProviderQuery, CertificationWithCounts, PaginatedResponse, PaginationMeta, PageQuery,
+};
+use crate::handlers::topic::slug_generated;
+
+// Provider handlers
+pub async fn get_providers(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ProviderWithCounts>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, providers) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM providers").fetch_one(&pool),
+        sqlx::query_as::<_, ProviderWithCounts>(
+            "SELECT p.*, COUNT(c.id) AS certification_count
+             FROM providers p
+             LEFT JOIN certifications c ON c.provider_id = p.id
+             GROUP BY p.id
+             ORDER BY p.name
+             LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch providers", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: providers,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn get_provider(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Provider>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let provider = get_provider_by_id(&pool, id).await?;
+
+    Ok(Json(ApiResponse::success(provider)))
+}
+
+pub async fn get_provider_certifications(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<CertificationWithCounts>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    get_provider_by_id(&pool, id).await?;
+
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, certifications) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM certifications WHERE provider_id = $1")
+            .bind(id)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, CertificationWithCounts>(
+            "SELECT c.*, COUNT(t.id) AS topic_count
+             FROM certifications c
+             LEFT JOIN topics t ON t.certification_id = c.id
+             WHERE c.provider_id = $1
+             GROUP BY c.id
+             ORDER BY c.name
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch certifications", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: certifications,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_provider(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateProvider>,
+) -> Result<Json<ApiResponse<Provider>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "name must not be empty".to_string())),
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let slug = match payload.slug.as_deref().map(str::trim) {
+        Some(slug) if !slug.is_empty() => slug.to_string(),
+        _ => {
+            let slug = generate_slug(&payload.name);
+            warnings.push(slug_generated(&slug));
+            slug
+        }
+    };
+
+    let provider = sqlx::query_as::<_, Provider>(
+        "INSERT INTO providers (name, slug, description, website_url) VALUES ($1, $2, $3, $4) RETURNING *"
+    )
+    .bind(payload.name.trim())
+    .bind(slug)
+    .bind(payload.description)
+    .bind(payload.website_url)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| conflict_or_internal(e, "Failed to create provider"))?;
+
+    Ok(Json(ApiResponse::success(provider).with_warnings(warnings)))
+}
+
+pub async fn update_provider(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateProvider>,
+) -> Result<Json<ApiResponse<Provider>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let provider = sqlx::query_as::<_, Provider>(
+        "UPDATE providers SET
+            name = COALESCE($1, name),
+            slug = COALESCE($2, slug),
+            description = COALESCE($3, description),
+            website_url = COALESCE($4, website_url),
+            updated_at = NOW()
+         WHERE id = $5 RETURNING *"
+    )
+    .bind(payload.name.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .bind(payload.slug.as_deref().map(str::trim).filter(|s| !s.is_empty()))
+    .bind(payload.description)
+    .bind(payload.website_url)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| conflict_or_internal(e, "Failed to update provider"))?;
+
+    match provider {
+        Some(provider) => Ok(Json(ApiResponse::success(provider))),
+        None => Err(not_found()),
+    }
+}
+
+/// Delete a provider. One with certifications is only deleted with `?cascade=true`, which
+/// deletes the certifications too; their topics are kept and unlinked.
+pub async fn delete_provider(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeleteProviderQuery>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    if query.cascade {
+        sqlx::query("DELETE FROM certifications WHERE provider_id = $1")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| errors::internal("Failed to delete certifications", e))?;
+    }
+
+    let result = sqlx::query("DELETE FROM providers WHERE id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(e) if e.as_database_error().is_some_and(|db| db.is_foreign_key_violation()) => {
+            let certifications: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM certifications WHERE provider_id = $1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .map_err(|e| errors::internal("Failed to count certifications", e))?;
+
+            return Err((
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::ProviderHasCertifications,
+                    format!(
+                        "Provider has {} certifications; delete them first or pass ?cascade=true to delete them with it",
+                        certifications
+                    ),
+                )),
+            ));
+        }
+        Err(e) => return Err(errors::internal("Failed to delete provider", e)),
+    };
+
+    if result.rows_affected() == 0 {
+        return Err(not_found());
+    }
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+// Helper functions
+pub async fn get_provider_by_id(pool: &PgPool, id: Uuid) -> Result<Provider, (StatusCode, Json<ApiResponse<()>>)> {
+    let provider = sqlx::query_as::<_, Provider>("SELECT * FROM providers WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch provider", e))?;
+
+    provider.ok_or_else(not_found)
+}
+
+fn not_found() -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::ProviderNotFound, "Provider not found".to_string())),
+    )
+}
+
+fn conflict_or_internal(e: sqlx::Error, context: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    match e.as_database_error() {
+        Some(db) if db.constraint() == Some("providers_name_key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateProviderName, "A provider with this name already exists".to_string())),
+        ),
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateSlug, "A provider with this slug already exists".to_string())),
+        ),
+        _ => errors::internal(context, e),
+    }
+}
@@ -0,0 +1,525 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json
+};
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::scraping;
+use crate::watermark;
+use crate::models::{
+    ApiKey, ApiKeyCreated, CreateApiKey, UpdateApiKey, TraceWatermark, WatermarkCandidate, WatermarkReport,
+    ScrapingAlert, ScrapingAlertQuery, QuestionAccess, AccessLogQuery,
+    CatalogTopic, PublicQuestion, PublicQuestionQuery, Question, Topic,
+    PaginatedResponse, PaginationMeta, PageQuery, ApiResponse, ErrorCode,
+};
+
+/// Header carrying the caller's API key
+const API_KEY_HEADER: &str = "x-api-key";
+
+// API key handlers
+pub async fn get_api_keys(
+    State(pool): State<PgPool>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ApiKey>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, keys) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM api_keys").fetch_one(&pool),
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT k.*, COALESCE(u.requests, 0) AS requests_today
+             FROM api_keys k
+             LEFT JOIN api_key_usage u ON u.key_id = k.id AND u.day = (NOW() AT TIME ZONE 'UTC')::date
+             ORDER BY k.created_at DESC, k.id
+             LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch API keys", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: keys,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+pub async fn create_api_key(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateApiKey>,
+) -> Result<Json<ApiResponse<ApiKeyCreated>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "name must not be empty".to_string())),
+        ));
+    }
+
+    let daily_quota = payload.daily_quota.unwrap_or(1000);
+    validate_quota(daily_quota)?;
+
+    let key: String = format!(
+        "bk_{}",
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect::<String>()
+    );
+
+    let api_key = sqlx::query_as::<_, ApiKey>(
+        "INSERT INTO api_keys (name, key_hash, key_prefix, daily_quota, include_answers, include_explanations, watermark)
+         VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *"
+    )
+    .bind(name)
+    .bind(hash_key(&key))
+    .bind(&key[..7])
+    .bind(daily_quota)
+    .bind(payload.include_answers)
+    .bind(payload.include_explanations)
+    .bind(payload.watermark)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create API key", e))?;
+
+    Ok(Json(ApiResponse::success(ApiKeyCreated { api_key, key })))
+}
+
+pub async fn update_api_key(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateApiKey>,
+) -> Result<Json<ApiResponse<ApiKey>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if let Some(daily_quota) = payload.daily_quota {
+        validate_quota(daily_quota)?;
+    }
+
+    let api_key = sqlx::query_as::<_, ApiKey>(
+        "UPDATE api_keys SET
+            daily_quota = COALESCE($1, daily_quota),
+            include_answers = COALESCE($2, include_answers),
+            include_explanations = COALESCE($3, include_explanations),
+            watermark = COALESCE($4, watermark),
+            active = COALESCE($5, active)
+         WHERE id = $6 RETURNING *"
+    )
+    .bind(payload.daily_quota)
+    .bind(payload.include_answers)
+    .bind(payload.include_explanations)
+    .bind(payload.watermark)
+    .bind(payload.active)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to update API key", e))?;
+
+    match api_key {
+        Some(api_key) => Ok(Json(ApiResponse::success(api_key))),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ApiKeyNotFound, "API key not found".to_string())),
+        )),
+    }
+}
+
+pub async fn delete_api_key(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let result = sqlx::query("DELETE FROM api_keys WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to delete API key", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ApiKeyNotFound, "API key not found".to_string())),
+        ));
+    }
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Lift a scraping block before it runs out
+pub async fn unblock_api_key(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApiKey>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let api_key = sqlx::query_as::<_, ApiKey>("UPDATE api_keys SET blocked_until = NULL WHERE id = $1 RETURNING *")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to unblock API key", e))?;
+
+    match api_key {
+        Some(api_key) => Ok(Json(ApiResponse::success(api_key))),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ApiKeyNotFound, "API key not found".to_string())),
+        )),
+    }
+}
+
+/// Scraping alerts, newest first
+pub async fn get_scraping_alerts(
+    State(pool): State<PgPool>,
+    Query(query): Query<ScrapingAlertQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ScrapingAlert>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    let (total_count, alerts) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM scraping_alerts WHERE ($1::uuid IS NULL OR key_id = $1)")
+            .bind(query.key_id)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, ScrapingAlert>(
+            "SELECT a.*, k.name AS key_name
+             FROM scraping_alerts a
+             JOIN api_keys k ON k.id = a.key_id
+             WHERE ($1::uuid IS NULL OR a.key_id = $1)
+             ORDER BY a.created_at DESC, a.id
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(query.key_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch scraping alerts", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: alerts,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// Which keys viewed which questions, most views first
+pub async fn get_access_log(
+    State(pool): State<PgPool>,
+    Query(query): Query<AccessLogQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<QuestionAccess>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    // One entry per question and key over the selected days
+    let (total_count, access) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM (
+                SELECT 1
+                FROM question_views v
+                JOIN questions q ON q.id = v.question_id
+                JOIN topics t ON t.id = q.topic_id
+                WHERE ($1::uuid IS NULL OR v.question_id = $1)
+                  AND ($2::uuid IS NULL OR v.key_id = $2)
+                  AND ($3::text IS NULL OR LOWER(t.slug) = LOWER($3))
+                  AND ($4::date IS NULL OR v.day >= $4)
+                  AND ($5::date IS NULL OR v.day <= $5)
+                GROUP BY v.question_id, v.key_id
+             ) entries"
+        )
+        .bind(query.question_id)
+        .bind(query.key_id)
+        .bind(&query.topic)
+        .bind(query.from)
+        .bind(query.to)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, QuestionAccess>(
+            "SELECT v.question_id, q.question_number, t.slug AS topic_slug, v.key_id, k.name AS key_name,
+                SUM(v.views) AS views,
+                COUNT(*) AS days,
+                BOOL_OR(v.with_answers) AS with_answers,
+                MIN(v.first_viewed_at) AS first_viewed_at,
+                MAX(v.last_viewed_at) AS last_viewed_at
+             FROM question_views v
+             JOIN questions q ON q.id = v.question_id
+             JOIN topics t ON t.id = q.topic_id
+             JOIN api_keys k ON k.id = v.key_id
+             WHERE ($1::uuid IS NULL OR v.question_id = $1)
+               AND ($2::uuid IS NULL OR v.key_id = $2)
+               AND ($3::text IS NULL OR LOWER(t.slug) = LOWER($3))
+               AND ($4::date IS NULL OR v.day >= $4)
+               AND ($5::date IS NULL OR v.day <= $5)
+             GROUP BY v.question_id, q.question_number, t.slug, v.key_id, k.name
+             ORDER BY views DESC, last_viewed_at DESC, v.question_id, v.key_id
+             LIMIT $6 OFFSET $7"
+        )
+        .bind(query.question_id)
+        .bind(query.key_id)
+        .bind(&query.topic)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch access log", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: access,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+/// Find which API keys the watermarks in a leaked dump belong to
+pub async fn trace_watermark(
+    State(pool): State<PgPool>,
+    Json(payload): Json<TraceWatermark>,
+) -> Result<Json<ApiResponse<WatermarkReport>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let marks = watermark::extract(&payload.text);
+
+    let mut candidates = sqlx::query_as::<_, WatermarkCandidate>(
+        "SELECT id AS api_key_id, name, key_prefix, active FROM api_keys"
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch API keys", e))?;
+
+    for candidate in &mut candidates {
+        let tag = watermark::tag(candidate.api_key_id);
+        candidate.marks = marks.iter().filter(|&&mark| mark == tag).count();
+    }
+
+    candidates.retain(|c| c.marks > 0);
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.marks));
+
+    let matched: usize = candidates.iter().map(|c| c.marks).sum();
+    let report = WatermarkReport {
+        marks_found: marks.len(),
+        unmatched: marks.len().saturating_sub(matched),
+        candidates,
+    };
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
+// Public API handlers
+pub async fn get_public_topics(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let (_, quota_headers) = authorize(&pool, &headers).await?;
+
+    let topics = sqlx::query_as::<_, CatalogTopic>(
+        "SELECT t.name, t.slug, t.description,
+            COUNT(q.id) AS question_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'easy') AS easy_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'medium') AS medium_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'hard') AS hard_count,
+            GREATEST(t.updated_at, MAX(q.updated_at)) AS last_modified
+         FROM topics t
+         LEFT JOIN questions q ON q.topic_id = t.id
+         WHERE t.is_public
+         GROUP BY t.id
+         ORDER BY t.name"
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    Ok((quota_headers, Json(ApiResponse::success(topics))))
+}
+
+pub async fn get_public_questions(
+    State(pool): State<PgPool>,
+    Path(slug): Path<String>,
+    Query(query): Query<PublicQuestionQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let (api_key, quota_headers) = authorize(&pool, &headers).await?;
+
+    let throttled = scraping::throttled(&pool, api_key.id)
+        .await
+        .map_err(|e| errors::internal("Failed to check read rate", e))?;
+    if throttled {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ApiResponse::error(ErrorCode::ReadRateExceeded, "Too many question pages this minute; slow down".to_string())),
+        ));
+    }
+
+    // Topics that are not public are reported as missing
+    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE LOWER(slug) = LOWER($1) AND is_public")
+        .bind(&slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let Some(topic) = topic else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, format!("Topic with slug '{}' not found", slug))),
+        ));
+    };
+
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM questions WHERE topic_id = $1")
+        .bind(topic.id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to count questions", e))?;
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number LIMIT $2 OFFSET $3"
+    )
+    .bind(topic.id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let policy = api_key.policy();
+
+    let read = scraping::Read {
+        key_id: api_key.id,
+        topic_id: topic.id,
+        topic_size: total_count,
+        first_position: offset,
+        served: questions.len() as i64,
+        with_answers: policy.correct_answer,
+    };
+    if !questions.is_empty()
+        && let Some(blocked_until) = scraping::record_read(&pool, &read)
+            .await
+            .map_err(|e| errors::internal("Failed to record read", e))?
+    {
+        return Err(blocked(blocked_until));
+    }
+
+    let question_ids: Vec<Uuid> = questions.iter().map(|q| q.id).collect();
+    record_views(&pool, api_key.id, &question_ids, policy.correct_answer)
+        .await
+        .map_err(|e| errors::internal("Failed to record question views", e))?;
+
+    let tag = api_key.watermark.then(|| watermark::tag(api_key.id));
+    let paginated_response = PaginatedResponse {
+        items: questions
+            .into_iter()
+            .map(|q| {
+                let mut question = PublicQuestion::new(q, &policy);
+                if let Some(tag) = tag {
+                    question.question = watermark::apply(&question.question, tag);
+                    question.explanation = question.explanation.map(|e| watermark::apply(&e, tag));
+                }
+                question
+            })
+            .collect(),
+        pagination: PaginationMeta::new(page, limit, total_count),
+    };
+
+    Ok((quota_headers, Json(ApiResponse::success(paginated_response))))
+}
+
+// Helper functions
+/// Resolve the caller's key and count the request against today's quota (UTC days).
+/// Returns the key with rate limit headers for the response.
+async fn authorize(
+    pool: &PgPool,
+    headers: &HeaderMap,
+) -> Result<(ApiKey, [(&'static str, String); 2]), (StatusCode, Json<ApiResponse<()>>)> {
+    let Some(key) = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(ErrorCode::ApiKeyRequired, "An X-Api-Key header is required".to_string())),
+        ));
+    };
+
+    let api_key = sqlx::query_as::<_, ApiKey>(
+        "UPDATE api_keys SET last_used_at = NOW() WHERE key_hash = $1 AND active RETURNING *"
+    )
+    .bind(hash_key(key.trim()))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch API key", e))?;
+
+    let Some(api_key) = api_key else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(ErrorCode::InvalidApiKey, "Invalid or revoked API key".to_string())),
+        ));
+    };
+
+    if let Some(blocked_until) = api_key.blocked_until.filter(|until| *until > Utc::now()) {
+        return Err(blocked(blocked_until));
+    }
+
+    let requests: i32 = sqlx::query_scalar(
+        "INSERT INTO api_key_usage (key_id, day) VALUES ($1, (NOW() AT TIME ZONE 'UTC')::date)
+         ON CONFLICT (key_id, day) DO UPDATE SET requests = api_key_usage.requests + 1
+         RETURNING requests"
+    )
+    .bind(api_key.id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to record API usage", e))?;
+
+    if requests > api_key.daily_quota {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ApiResponse::error(ErrorCode::QuotaExceeded, format!(
+                "Daily quota of {} requests exceeded; it resets at 00:00 UTC",
+                api_key.daily_quota
+            ))),
+        ));
+    }
+
+    let quota_headers = [
+        ("x-ratelimit-limit", api_key.daily_quota.to_string()),
+        ("x-ratelimit-remaining", (api_key.daily_quota - requests).to_string()),
+    ];
+
+    Ok((api_key, quota_headers))
+}
+
+/// Count a view of each question for the key and today (UTC)
+async fn record_views(pool: &PgPool, key_id: Uuid, question_ids: &[Uuid], with_answers: bool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO question_views (question_id, key_id, day, with_answers)
+         SELECT id, $1, (NOW() AT TIME ZONE 'UTC')::date, $3 FROM UNNEST($2::uuid[]) AS id
+         ON CONFLICT (question_id, key_id, day) DO UPDATE SET
+            views = question_views.views + 1,
+            with_answers = question_views.with_answers OR EXCLUDED.with_answers,
+            last_viewed_at = NOW()"
+    )
+    .bind(key_id)
+    .bind(question_ids)
+    .bind(with_answers)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+fn blocked(until: DateTime<Utc>) -> (StatusCode, Json<ApiResponse<()>>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ApiResponse::error(ErrorCode::ApiKeyBlocked, format!(
+            "API key is blocked until {} after unusual read activity",
+            until.to_rfc3339()
+        ))),
+    )
+}
+
+fn validate_quota(daily_quota: i32) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    if daily_quota < 1 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "daily_quota must be at least 1".to_string())),
+        ));
+    }
+
+    Ok(())
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
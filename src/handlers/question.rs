@@ -1,70 +1,92 @@
 use axum::{
     extract::{Path, Query, State}, 
-    http::StatusCode, 
+    http::{HeaderMap, StatusCode}, 
     Json
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use sqlx::{PgPool, types::Json as SqlxJson}; // ← Import SqlxJson
+use serde_json::json;
+use sqlx::{Acquire, PgPool, types::Json as SqlxJson}; // ← Import SqlxJson
 use uuid::Uuid;
-use crate::database;
 
+use crate::errors;
 use crate::models::{
-    Question, CreateQuestion, UpdateQuestion, QuestionType, Difficulty,
-    BulkCreateQuestions, BulkCreateResponse,
-    QuestionResponse, PaginatedResponse, PaginationMeta,
-    ApiResponse, generate_slug,
+    Question, CreateQuestion, UpdateQuestion, QuestionType, Difficulty, validate_option_explanations, validate_metadata,
+    template_texts, validate_variables, VariantQuery,
+    BulkCreateQuestions, BulkCreateResponse, BulkQuestionData,
+    FailedRow, ImportSource, BulkImportQuery, NewImportRun, Numbering, OnError, RowError, parse_import,
+    QuestionResponse, PaginatedResponse, PaginationMeta, PageQuery, Audience, RedactionPolicy, ViewQuery, MathFormat, validate_math, CognitiveLevel, ReadingStats, validate_alt_text, color_only_options, CodeFormat, validate_code_blocks,
+    QuestionRevision, TimelineEvent, TimelineEventKind, RegradeJob, JobStatus,
+    HighlightQuery, HighlightedQuestion, QuestionMatch,
+    ApiResponse, ErrorCode, ApiWarning, DeltaResponse, ListResponse, QuizSession,
 }; 
 use crate::handlers::topic; 
+use crate::handlers::sync::{begin_delta, get_deletions_since};
+use crate::handlers::freeze::{ensure_not_frozen, ensure_question_not_frozen};
+use crate::handlers::import::{imported_by, record_import_run};
+use crate::handlers::explanation::get_explanation_entries;
+use crate::handlers::custom_field::{ensure_valid_metadata, load_custom_fields};
+use crate::handlers::search::HEADLINE_OPTIONS;
+use crate::catalog;
 
 // Question handlers
 #[derive(Debug, Deserialize)]
 pub struct QuestionQuery {
     pub page: Option<i64>,
     pub limit: Option<i64>,
+    // Delta sync: return every question changed after this time plus deletion tombstones
+    pub updated_since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub view: Audience,
+    #[serde(default)]
+    pub math: MathFormat,
+    #[serde(default)]
+    pub code: CodeFormat,
+    // JSON object of custom field values to match, e.g. {"chapter":"3"}
+    pub metadata: Option<String>,
+    pub cognitive_level: Option<CognitiveLevel>,
 }
 
 pub async fn get_questions(
     State(pool): State<PgPool>,
     Query(query): Query<QuestionQuery>,
-) -> Result<Json<ApiResponse<PaginatedResponse<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let page = query.page.unwrap_or(1).max(1);
-    let limit = query.limit.unwrap_or(20).max(1).min(100);
-    let offset = (page - 1) * limit;
+) -> Result<Json<ApiResponse<ListResponse<PaginatedResponse<QuestionResponse>, QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let policy = RedactionPolicy::for_audience(query.view);
+    let metadata = metadata_filter(query.metadata.as_deref())?;
 
-    // Get total count
-    let total_count: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM questions"
-    )
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to count questions: {}", e))),
-        )
-    })?;
+    if let Some(since) = query.updated_since {
+        return get_questions_delta(&pool, since, &policy, &query, metadata.as_ref()).await;
+    }
 
-    // Get paginated questions
-    let questions = sqlx::query_as::<_, Question>(
-        "SELECT q.* FROM questions q 
-         JOIN topics t ON q.topic_id = t.id 
-         ORDER BY t.name, q.question_number 
-         LIMIT $1 OFFSET $2"
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to fetch questions: {}", e))),
+    let (page, limit, offset) = PageQuery { page: query.page, limit: query.limit }.resolve();
+
+    // Count and fetch the page at the same time
+    let (total_count, questions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM questions
+             WHERE ($1::jsonb IS NULL OR metadata @> $1) AND ($2::cognitive_level IS NULL OR cognitive_level = $2)"
         )
-    })?;
+        .bind(metadata.as_ref())
+        .bind(query.cognitive_level)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, Question>(
+            "SELECT q.* FROM questions q 
+             JOIN topics t ON q.topic_id = t.id 
+             WHERE ($3::jsonb IS NULL OR q.metadata @> $3) AND ($4::cognitive_level IS NULL OR q.cognitive_level = $4)
+             ORDER BY t.name, q.question_number 
+             LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .bind(metadata.as_ref())
+        .bind(query.cognitive_level)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
 
     let response_questions: Vec<QuestionResponse> = questions
         .into_iter()
-        .map(QuestionResponse::from)
+        .map(|q| QuestionResponse::from(q).redact(&policy).render_math(query.math).render_code(query.code))
         .collect();
 
     let paginated_response = PaginatedResponse {
@@ -72,43 +94,136 @@ pub async fn get_questions(
         pagination: PaginationMeta::new(page, limit, total_count),
     };
 
-    Ok(Json(ApiResponse::success(paginated_response)))
+    Ok(Json(ApiResponse::success(ListResponse::Full(paginated_response))))
+}
+
+async fn get_questions_delta(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    policy: &RedactionPolicy,
+    query: &QuestionQuery,
+    metadata: Option<&serde_json::Value>,
+) -> Result<Json<ApiResponse<ListResponse<PaginatedResponse<QuestionResponse>, QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (mut transaction, synced_at) = begin_delta(pool).await?;
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions
+         WHERE updated_at > $1 AND ($2::jsonb IS NULL OR metadata @> $2)
+           AND ($3::cognitive_level IS NULL OR cognitive_level = $3)
+         ORDER BY updated_at"
+    )
+    .bind(since)
+    .bind(metadata)
+    .bind(query.cognitive_level)
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let delta = DeltaResponse {
+        items: questions.into_iter().map(|q| QuestionResponse::from(q).redact(policy).render_math(query.math).render_code(query.code)).collect(),
+        deleted: get_deletions_since(&mut *transaction, "question", since).await?,
+        synced_at,
+    };
+
+    Ok(Json(ApiResponse::success(ListResponse::Delta(delta))))
 }
 pub async fn get_question(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
+    Query(view): Query<ViewQuery>,
 ) -> Result<Json<ApiResponse<QuestionResponse>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
     let question = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = $1")
         .bind(id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to fetch question: {}", e))),
-            )
-        })?;
-
-    match question {
-        Some(question) => Ok(Json(ApiResponse::success(QuestionResponse::from(question)))), //  Convert to response
-        None => Err((
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    let Some(question) = question else {
+        return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Question not found".to_string())),
-        )),
-    }
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    };
+
+    let mut response = QuestionResponse::from(question);
+    response.explanations = get_explanation_entries(&pool, id, false).await?;
+
+    Ok(Json(ApiResponse::success(response.redact(&RedactionPolicy::for_audience(view.view)).render_math(view.math).render_code(view.code))))
+}
+
+/// One variant of a template question, as a quiz session with `seed` would deal it
+pub async fn get_question_variant(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<VariantQuery>,
+) -> Result<Json<ApiResponse<QuestionResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let question = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    let Some(question) = question else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    };
+
+    let seed = query.seed.unwrap_or_else(rand::random::<i64>);
+    let variant = question.instantiate(QuizSession::variant_seed(seed, id));
+
+    Ok(Json(ApiResponse::success(QuestionResponse::from(variant))))
 }
 
 pub async fn create_question(
     State(pool): State<PgPool>,
     Json(payload): Json<CreateQuestion>,
 ) -> Result<Json<ApiResponse<QuestionResponse>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
+    ensure_not_frozen(&pool, &[payload.topic_id]).await?;
+
+    if let Some(explanations) = &payload.option_explanations {
+        validate_option_explanations(&payload.options, explanations)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
+    let metadata = payload.metadata.unwrap_or_default();
+    ensure_valid_metadata(&pool, &metadata).await?;
+
+    let texts = template_texts(
+        &payload.question,
+        &payload.explanation,
+        &payload.options,
+        payload.hints.as_ref(),
+        payload.option_explanations.as_ref(),
+    );
+    texts
+        .iter()
+        .try_for_each(|text| validate_math(text).and_then(|_| validate_alt_text(text)))
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+
+    let variables = payload.variables.filter(|v| !v.is_empty());
+    if let Some(variables) = &variables {
+        validate_variables(variables, &texts)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
+    let code_blocks = payload.code_blocks.filter(|c| !c.is_empty());
+    if let Some(code_blocks) = &code_blocks {
+        validate_code_blocks(code_blocks)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
     let difficulty = payload.difficulty.unwrap_or(Difficulty::Medium);
+    let options_reordered = options_reordered(&payload.options);
+    let stats = ReadingStats::of(&payload.question, &payload.options, &payload.explanation);
     
     let question = sqlx::query_as::<_, Question>(
         "INSERT INTO questions (
             topic_id, question_number, question, options, correct_answer, 
-            explanation, question_type, difficulty, tags
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *"
+            explanation, question_type, difficulty, tags, option_explanations, hints, metadata, variables, code_blocks,
+            cognitive_level, word_count, estimated_secs
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, '[]'::jsonb), $10, $11, $12, $13, $14, $15, $16, $17) RETURNING *"
     )
     .bind(payload.topic_id)
     .bind(payload.question_number)
@@ -118,17 +233,32 @@ pub async fn create_question(
     .bind(payload.explanation)
     .bind(payload.question_type)
     .bind(difficulty)
-    .bind(payload.tags.as_ref().map(|t| SqlxJson(t))) //  Fixed: Wrapped in SqlxJson
+    .bind(payload.tags.as_ref().map(SqlxJson)) //  Fixed: Wrapped in SqlxJson
+    .bind(payload.option_explanations.as_ref().map(SqlxJson))
+    .bind(payload.hints.as_ref().map(SqlxJson))
+    .bind(SqlxJson(&metadata))
+    .bind(variables.as_ref().map(SqlxJson))
+    .bind(code_blocks.as_ref().map(SqlxJson))
+    .bind(payload.cognitive_level)
+    .bind(stats.word_count)
+    .bind(stats.estimated_secs)
     .fetch_one(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to create question: {}", e))),
-        )
+    .map_err(|e| match e.as_database_error() {
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateQuestionNumber, "The topic already has a question with this number".to_string())),
+        ),
+        _ => errors::internal("Failed to create question", e),
     })?;
 
-    Ok(Json(ApiResponse::success(QuestionResponse::from(question)))) //  Convert to response
+    catalog::invalidate();
+
+    let mut warnings: Vec<ApiWarning> = options_reordered.into_iter().collect();
+    warnings.extend(color_only_warning(&question.options.0));
+    warnings.extend(suspected_duplicate(&pool, &question).await?);
+
+    Ok(Json(ApiResponse::success(QuestionResponse::from(question)).with_warnings(warnings))) //  Convert to response
 }
 
 pub async fn update_question(
@@ -136,6 +266,55 @@ pub async fn update_question(
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateQuestion>,
 ) -> Result<Json<ApiResponse<QuestionResponse>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
+    ensure_question_not_frozen(&pool, id).await?;
+    if let Some(topic_id) = payload.topic_id {
+        ensure_not_frozen(&pool, &[topic_id]).await?;
+    }
+
+    if let Some(explanations) = &payload.option_explanations {
+        let options = match &payload.options {
+            Some(options) => options.clone(),
+            None => sqlx::query_scalar::<_, SqlxJson<Vec<String>>>("SELECT options FROM questions WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| errors::internal("Failed to fetch question", e))?
+                .map(|options| options.0)
+                .unwrap_or_default(),
+        };
+
+        validate_option_explanations(&options, explanations)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
+    if let Some(metadata) = &payload.metadata {
+        ensure_valid_metadata(&pool, metadata).await?;
+    }
+
+    let updated_texts = payload.question.iter()
+        .chain(&payload.explanation)
+        .chain(payload.options.iter().flatten())
+        .chain(payload.hints.iter().flatten())
+        .chain(payload.option_explanations.iter().flat_map(|e| e.values()));
+    for text in updated_texts {
+        validate_math(text)
+            .and_then(|_| validate_alt_text(text))
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
+    ensure_valid_template(&pool, id, &payload).await?;
+
+    if let Some(code_blocks) = &payload.code_blocks {
+        validate_code_blocks(code_blocks)
+            .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))?;
+    }
+
+    let stats = updated_reading_stats(&pool, id, &payload).await?;
+
+    let mut warnings: Vec<ApiWarning> = payload.options.as_deref().and_then(options_reordered).into_iter().collect();
+    warnings.extend(payload.options.as_deref().and_then(color_only_warning));
+    let question_changed = payload.question.is_some();
+
     let question = sqlx::query_as::<_, Question>(
         "UPDATE questions SET 
             topic_id = COALESCE($1, topic_id),
@@ -146,227 +325,656 @@ pub async fn update_question(
             explanation = COALESCE($6, explanation),
             question_type = COALESCE($7, question_type),
             difficulty = COALESCE($8, difficulty),
-            tags = COALESCE($9, tags)
-         WHERE id = $10 RETURNING *"
+            tags = COALESCE($9, tags),
+            option_explanations = COALESCE($10, option_explanations),
+            hints = COALESCE($11, hints),
+            metadata = COALESCE($12, metadata),
+            variables = NULLIF(COALESCE($13, variables), '{}'::jsonb),
+            code_blocks = NULLIF(COALESCE($14, code_blocks), '[]'::jsonb),
+            cognitive_level = COALESCE($15, cognitive_level),
+            word_count = COALESCE($16, word_count),
+            estimated_secs = COALESCE($17, estimated_secs),
+            updated_at = NOW()
+         WHERE id = $18 RETURNING *"
     )
     .bind(payload.topic_id)
     .bind(payload.question_number)
     .bind(payload.question)
-    .bind(payload.options.as_ref().map(|o| SqlxJson(o)))        //  Fixed: Wrapped in SqlxJson
-    .bind(payload.correct_answer.as_ref().map(|c| SqlxJson(c))) //  Fixed: Wrapped in SqlxJson
+    .bind(payload.options.as_ref().map(SqlxJson))        //  Fixed: Wrapped in SqlxJson
+    .bind(payload.correct_answer.as_ref().map(SqlxJson)) //  Fixed: Wrapped in SqlxJson
     .bind(payload.explanation)
     .bind(payload.question_type)
     .bind(payload.difficulty)
-    .bind(payload.tags.as_ref().map(|t| SqlxJson(t)))           //  Fixed: Wrapped in SqlxJson
+    .bind(payload.tags.as_ref().map(SqlxJson))           //  Fixed: Wrapped in SqlxJson
+    .bind(payload.option_explanations.as_ref().map(SqlxJson))
+    .bind(payload.hints.as_ref().map(SqlxJson))
+    .bind(payload.metadata.as_ref().map(SqlxJson))
+    .bind(payload.variables.as_ref().map(SqlxJson))
+    .bind(payload.code_blocks.as_ref().map(SqlxJson))
+    .bind(payload.cognitive_level)
+    .bind(stats.map(|s| s.word_count))
+    .bind(stats.map(|s| s.estimated_secs))
     .bind(id)
     .fetch_optional(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to update question: {}", e))),
-        )
+    .map_err(|e| match e.as_database_error() {
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateQuestionNumber, "The topic already has a question with this number".to_string())),
+        ),
+        _ => errors::internal("Failed to update question", e),
     })?;
 
-    match question {
-        Some(question) => Ok(Json(ApiResponse::success(QuestionResponse::from(question)))), //  Convert to response
-        None => Err((
+    catalog::invalidate();
+
+    let Some(question) = question else {
+        return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Question not found".to_string())),
-        )),
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    };
+
+    if question_changed {
+        warnings.extend(suspected_duplicate(&pool, &question).await?);
+    }
+
+    // Prerequisites only link questions of one topic
+    if payload.topic_id.is_some() {
+        sqlx::query(
+            "DELETE FROM question_prerequisites p
+             USING questions a, questions b
+             WHERE a.id = p.question_id AND b.id = p.prerequisite_id
+               AND a.topic_id <> b.topic_id AND $1 IN (p.question_id, p.prerequisite_id)"
+        )
+        .bind(id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to update prerequisites", e))?;
     }
+
+    Ok(Json(ApiResponse::success(QuestionResponse::from(question)).with_warnings(warnings))) //  Convert to response
 }
 
 pub async fn delete_question(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_question_not_frozen(&pool, id).await?;
+
     let result = sqlx::query("DELETE FROM questions WHERE id = $1")
         .bind(id)
         .execute(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to delete question: {}", e))),
-            )
-        })?;
+        .map_err(|e| errors::internal("Failed to delete question", e))?;
 
     if result.rows_affected() == 0 {
         return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Question not found".to_string())),
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
         ));
     }
 
+    catalog::invalidate();
+
     Ok(Json(ApiResponse::success(())))
 }
 
-// Specialized question handlers
-pub async fn get_questions_by_topic(
+// Question history handlers
+pub async fn get_question_timeline(
     State(pool): State<PgPool>,
-    Path(topic_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<Vec<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
-    let questions = sqlx::query_as::<_, Question>(
-        "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number"
+    Path(id): Path<Uuid>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<TimelineEvent>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let question = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    let Some(question) = question else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string())),
+        ));
+    };
+
+    let revisions = sqlx::query_as::<_, QuestionRevision>(
+        "SELECT * FROM question_revisions WHERE question_id = $1"
     )
-    .bind(topic_id)
+    .bind(id)
     .fetch_all(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to fetch questions: {}", e))),
+    .map_err(|e| errors::internal("Failed to fetch revisions", e))?;
+
+    let jobs = sqlx::query_as::<_, RegradeJob>("SELECT * FROM regrade_jobs WHERE question_id = $1")
+        .bind(id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch regrade jobs", e))?;
+
+    let mut events = vec![TimelineEvent {
+        at: question.created_at,
+        kind: TimelineEventKind::Created,
+        details: json!({ "question_number": question.question_number }),
+    }];
+
+    for revision in revisions {
+        events.push(TimelineEvent {
+            at: revision.created_at,
+            kind: TimelineEventKind::Revised,
+            details: json!({
+                "revision_id": revision.id,
+                "changed_fields": revision.changed_fields,
+                "previous": revision.previous,
+            }),
+        });
+    }
+
+    for job in jobs {
+        events.push(TimelineEvent {
+            at: job.created_at,
+            kind: TimelineEventKind::RegradeRequested,
+            details: json!({ "job_id": job.id }),
+        });
+
+        if let Some(completed_at) = job.completed_at {
+            let (kind, details) = match job.status {
+                JobStatus::Failed => (
+                    TimelineEventKind::RegradeFailed,
+                    json!({ "job_id": job.id, "error": job.error }),
+                ),
+                _ => (
+                    TimelineEventKind::RegradeCompleted,
+                    json!({
+                        "job_id": job.id,
+                        "sessions_checked": job.sessions_checked,
+                        "sessions_adjusted": job.sessions_adjusted,
+                    }),
+                ),
+            };
+            events.push(TimelineEvent { at: completed_at, kind, details });
+        }
+    }
+
+    // Stable sort keeps "requested" ahead of "completed" when they share a timestamp
+    events.sort_by_key(|event| event.at);
+
+    // Events come from several tables, so they are merged before paging
+    let total_count = events.len() as i64;
+    let items = events.into_iter().skip(offset as usize).take(limit as usize).collect();
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+// Specialized question handlers
+pub async fn get_questions_by_topic(
+    State(pool): State<PgPool>,
+    Path(topic_id): Path<Uuid>,
+    Query(view): Query<ViewQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, questions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM questions WHERE topic_id = $1")
+            .bind(topic_id)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, Question>(
+            "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number LIMIT $2 OFFSET $3"
         )
-    })?;
+        .bind(topic_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
 
     //  Fixed: Convert to response
+    let policy = RedactionPolicy::for_audience(view.view);
     let response_questions: Vec<QuestionResponse> = questions
         .into_iter()
-        .map(QuestionResponse::from)
+        .map(|q| QuestionResponse::from(q).redact(&policy).render_math(view.math).render_code(view.code))
         .collect();
 
-    Ok(Json(ApiResponse::success(response_questions)))
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: response_questions,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
 }
 
 pub async fn get_questions_by_type(
     State(pool): State<PgPool>,
     Path(question_type): Path<String>,
-) -> Result<Json<ApiResponse<Vec<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
+    Query(view): Query<ViewQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> {
     let q_type = match question_type.to_lowercase().as_str() {
         "single" => QuestionType::Single,
         "multiple" => QuestionType::Multiple,
         _ => {
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("Invalid question type. Use 'single' or 'multiple'".to_string())),
+                Json(ApiResponse::error(ErrorCode::ValidationFailed, "Invalid question type. Use 'single' or 'multiple'".to_string())),
             ));
         }
     };
     
-    let questions = sqlx::query_as::<_, Question>(
-        "SELECT q.* FROM questions q 
-         JOIN topics t ON q.topic_id = t.id 
-         WHERE q.question_type = $1 
-         ORDER BY t.name, q.question_number"
-    )
-    .bind(q_type)
-    .fetch_all(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to fetch questions: {}", e))),
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, questions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM questions WHERE question_type = $1")
+            .bind(&q_type)
+            .fetch_one(&pool),
+        sqlx::query_as::<_, Question>(
+            "SELECT q.* FROM questions q 
+             JOIN topics t ON q.topic_id = t.id 
+             WHERE q.question_type = $1 
+             ORDER BY t.name, q.question_number
+             LIMIT $2 OFFSET $3"
         )
-    })?;
+        .bind(&q_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
 
     //  Fixed: Convert to response
+    let policy = RedactionPolicy::for_audience(view.view);
     let response_questions: Vec<QuestionResponse> = questions
         .into_iter()
-        .map(QuestionResponse::from)
+        .map(|q| QuestionResponse::from(q).redact(&policy).render_math(view.math).render_code(view.code))
         .collect();
 
-    Ok(Json(ApiResponse::success(response_questions)))
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: response_questions,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
 }
 
+/// Questions whose text, explanation or topic name contains `query`. With `highlight`, each
+/// question carries `ts_headline` excerpts like `GET /search`; substring matches inside a
+/// word have no excerpt.
 pub async fn search_questions(
     State(pool): State<PgPool>,
     Path(query): Path<String>,
-) -> Result<Json<ApiResponse<Vec<QuestionResponse>>>, (StatusCode, Json<ApiResponse<()>>)> { //  Changed return type
+    Query(view): Query<ViewQuery>,
+    Query(page_query): Query<PageQuery>,
+    Query(highlight): Query<HighlightQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<QuestionMatch>>>, (StatusCode, Json<ApiResponse<()>>)> {
     let search_pattern = format!("%{}%", query);
-    
-    let questions = sqlx::query_as::<_, Question>(
-        "SELECT q.* FROM questions q 
-         JOIN topics t ON q.topic_id = t.id 
-         WHERE q.question ILIKE $1 OR q.explanation ILIKE $1 OR t.name ILIKE $1
-         ORDER BY t.name, q.question_number"
-    )
-    .bind(search_pattern)
-    .fetch_all(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to search questions: {}", e))),
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, questions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM questions q 
+             JOIN topics t ON q.topic_id = t.id 
+             WHERE q.question ILIKE $1 OR q.explanation ILIKE $1 OR t.name ILIKE $1"
         )
-    })?;
+        .bind(&search_pattern)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, HighlightedQuestion>(
+            "SELECT q.*,
+                CASE WHEN $4 THEN ts_headline('english', q.question, websearch_to_tsquery('english', $6), $5) END
+                    AS question_headline,
+                CASE WHEN $4 THEN ts_headline('english', q.explanation, websearch_to_tsquery('english', $6), $5) END
+                    AS explanation_headline
+             FROM questions q 
+             JOIN topics t ON q.topic_id = t.id 
+             WHERE q.question ILIKE $1 OR q.explanation ILIKE $1 OR t.name ILIKE $1
+             ORDER BY t.name, q.question_number
+             LIMIT $2 OFFSET $3"
+        )
+        .bind(&search_pattern)
+        .bind(limit)
+        .bind(offset)
+        .bind(highlight.highlight)
+        .bind(HEADLINE_OPTIONS)
+        .bind(&query)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to search questions", e))?;
 
-    //  Fixed: Convert to response
-    let response_questions: Vec<QuestionResponse> = questions
+    let policy = RedactionPolicy::for_audience(view.view);
+    let response_questions: Vec<QuestionMatch> = questions
         .into_iter()
-        .map(QuestionResponse::from)
+        .map(|mut highlighted| {
+            // Explanation fragments would leak what the policy hides
+            if !policy.explanation {
+                highlighted.explanation_headline = None;
+            }
+            let mut found = QuestionMatch::from(highlighted);
+            found.question = found.question.redact(&policy).render_math(view.math).render_code(view.code);
+            found
+        })
         .collect();
 
-    Ok(Json(ApiResponse::success(response_questions)))
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: response_questions,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
 }
 
 // Bulk create questions
 pub async fn bulk_create_questions(
     State(pool): State<PgPool>,
-    Json(payload): Json<BulkCreateQuestions>,
+    Query(options): Query<BulkImportQuery>,
+    headers: HeaderMap,
+    Json(payload): Json<serde_json::Value>,
 ) -> Result<Json<ApiResponse<BulkCreateResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let payload: BulkCreateQuestions = parse_import(payload, options.strict)
+        .map_err(|(code, e)| (StatusCode::UNPROCESSABLE_ENTITY, Json(ApiResponse::error(code, e))))?;
+
     let topic_id = topic::get_topic_id_by_slug(&pool, &payload.topic_slug).await?;
+    ensure_not_frozen(&pool, &[topic_id]).await?;
+
+    let (created, errors) = insert_questions(&pool, topic_id, &payload.questions, options.on_error, options.numbering).await?;
+
+    let failed_rows: Vec<FailedRow> = errors
+        .iter()
+        .map(|e| FailedRow {
+            row: e.row,
+            error: e.error.clone(),
+            data: serde_json::to_value(&payload.questions[e.row - 1]).unwrap_or_default(),
+        })
+        .collect();
+
+    let import_id = record_import_run(&pool, NewImportRun {
+        topic_id,
+        source: ImportSource::Bulk,
+        source_filename: options.filename,
+        template_name: None,
+        imported_by: imported_by(&headers),
+        total_rows: payload.questions.len(),
+        created,
+        columns: Vec::new(),
+        failed_rows,
+    })
+    .await;
 
+    let response = BulkCreateResponse {
+        created,
+        failed: errors.len(),
+        errors: errors.iter().map(|e| format!("Question {}: {}", e.row, e.error)).collect(),
+        import_id,
+    };
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+// Helper functions
+/// Responses list options sorted, so labels move when they were given in another order
+fn options_reordered(options: &[String]) -> Option<ApiWarning> {
+    if options.is_sorted() {
+        return None;
+    }
+
+    Some(ApiWarning::new(
+        "OPTIONS_REORDERED",
+        "options",
+        "Options are returned in alphabetical order, so their labels differ from the order given".to_string(),
+    ))
+}
+
+fn color_only_warning(options: &[String]) -> Option<ApiWarning> {
+    color_only_options(options).then(|| ApiWarning::new(
+        "COLOR_ONLY_OPTIONS",
+        "options",
+        "Options differ only by color, which learners with color vision deficiency cannot tell apart".to_string(),
+    ))
+}
+
+/// Another question of the same topic with the same text, ignoring case and outer whitespace
+async fn suspected_duplicate(
+    pool: &PgPool,
+    question: &Question,
+) -> Result<Option<ApiWarning>, (StatusCode, Json<ApiResponse<()>>)> {
+    let duplicate: Option<i32> = sqlx::query_scalar(
+        "SELECT question_number FROM questions
+         WHERE topic_id = $1 AND id <> $2 AND LOWER(TRIM(question)) = LOWER(TRIM($3))
+         ORDER BY question_number
+         LIMIT 1"
+    )
+    .bind(question.topic_id)
+    .bind(question.id)
+    .bind(&question.question)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to check for duplicates", e))?;
+
+    Ok(duplicate.map(|number| {
+        ApiWarning::new(
+            "DUPLICATE_SUSPECTED",
+            "question",
+            format!("Question {} of this topic has the same text", number),
+        )
+    }))
+}
+
+/// Validate the template a question will have after an update: the new or stored
+/// variables against the new or stored texts
+async fn ensure_valid_template(pool: &PgPool, id: Uuid, payload: &UpdateQuestion) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let touches_texts = payload.question.is_some()
+        || payload.explanation.is_some()
+        || payload.options.is_some()
+        || payload.hints.is_some()
+        || payload.option_explanations.is_some();
+    if payload.variables.is_none() && !touches_texts {
+        return Ok(());
+    }
+
+    let current = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    // A missing question is reported by the update itself
+    let Some(current) = current else {
+        return Ok(());
+    };
+
+    let variables = match &payload.variables {
+        Some(variables) => variables,
+        None => match &current.variables {
+            Some(variables) => &variables.0,
+            None => return Ok(()),
+        },
+    };
+
+    let texts = template_texts(
+        payload.question.as_ref().unwrap_or(&current.question),
+        payload.explanation.as_ref().unwrap_or(&current.explanation),
+        payload.options.as_ref().unwrap_or(&current.options.0),
+        payload.hints.as_ref().or(current.hints.as_ref().map(|h| &h.0)),
+        payload.option_explanations.as_ref().or(current.option_explanations.as_ref().map(|e| &e.0)),
+    );
+
+    validate_variables(variables, &texts)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(ErrorCode::ValidationFailed, e))))
+}
+
+/// Reading stats for the texts a question will have after an update, or `None` when the
+/// update leaves its question, options and explanation alone
+async fn updated_reading_stats(pool: &PgPool, id: Uuid, payload: &UpdateQuestion) -> Result<Option<ReadingStats>, (StatusCode, Json<ApiResponse<()>>)> {
+    if let (Some(question), Some(options), Some(explanation)) = (&payload.question, &payload.options, &payload.explanation) {
+        return Ok(Some(ReadingStats::of(question, options, explanation)));
+    }
+    if payload.question.is_none() && payload.options.is_none() && payload.explanation.is_none() {
+        return Ok(None);
+    }
+
+    let current: Option<(String, SqlxJson<Vec<String>>, String)> =
+        sqlx::query_as("SELECT question, options, explanation FROM questions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| errors::internal("Failed to fetch question", e))?;
+
+    // A missing question is reported by the update itself
+    Ok(current.map(|(question, options, explanation)| {
+        ReadingStats::of(
+            payload.question.as_ref().unwrap_or(&question),
+            payload.options.as_ref().unwrap_or(&options.0),
+            payload.explanation.as_ref().unwrap_or(&explanation),
+        )
+    }))
+}
+
+/// Insert questions into a topic in one transaction. Each row runs in its own savepoint, so
+/// with `OnError::Skip` the valid rows still commit; with `OnError::Abort` any failure rolls
+/// back the whole batch. Question numbers are assigned according to `numbering` while the
+/// topic is locked. Returns how many rows were committed and the failures by 1-based
+/// position in `questions`.
+pub async fn insert_questions(
+    pool: &PgPool,
+    topic_id: Uuid,
+    questions: &[BulkQuestionData],
+    on_error: OnError,
+    numbering: Numbering,
+) -> Result<(usize, Vec<RowError>), (StatusCode, Json<ApiResponse<()>>)> {
     let mut created = 0;
-    let mut failed = 0;
     let mut errors = Vec::new();
 
-    let mut transaction = pool.begin().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to start transaction: {}", e))),
-        )
-    })?;
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Lock the topic so concurrent imports cannot claim the same numbers
+    sqlx::query("SELECT id FROM topics WHERE id = $1 FOR UPDATE")
+        .bind(topic_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to lock topic", e))?;
+
+    let existing: Vec<i32> = sqlx::query_scalar("SELECT question_number FROM questions WHERE topic_id = $1")
+    .bind(topic_id)
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch question numbers", e))?;
+
+    let requested: Vec<i32> = questions.iter().map(|q| q.question_number).collect();
+    let numbers = numbering.assign(&existing, &requested);
+
+    let custom_fields = load_custom_fields(&mut *transaction).await.map_err(|e| errors::internal("Failed to fetch custom fields", e))?;
+
+    for (index, question_data) in questions.iter().enumerate() {
+        if let Some(explanations) = &question_data.option_explanations
+            && let Err(e) = validate_option_explanations(&question_data.options, explanations)
+        {
+            errors.push(RowError { row: index + 1, error: e });
+            continue;
+        }
+
+        let metadata = question_data.metadata.clone().unwrap_or_default();
+        if let Err(e) = validate_metadata(&custom_fields, &metadata) {
+            errors.push(RowError { row: index + 1, error: e });
+            continue;
+        }
+
+        let texts = template_texts(
+            &question_data.question,
+            &question_data.explanation,
+            &question_data.options,
+            question_data.hints.as_ref(),
+            question_data.option_explanations.as_ref(),
+        );
+        if let Err(e) = texts.iter().try_for_each(|text| validate_math(text).and_then(|_| validate_alt_text(text))) {
+            errors.push(RowError { row: index + 1, error: e });
+            continue;
+        }
+
+        let variables = question_data.variables.as_ref().filter(|v| !v.is_empty());
+        if let Some(variables) = variables
+            && let Err(e) = validate_variables(variables, &texts)
+        {
+            errors.push(RowError { row: index + 1, error: e });
+            continue;
+        }
+
+        let code_blocks = question_data.code_blocks.as_ref().filter(|c| !c.is_empty());
+        if let Some(code_blocks) = code_blocks
+            && let Err(e) = validate_code_blocks(code_blocks)
+        {
+            errors.push(RowError { row: index + 1, error: e });
+            continue;
+        }
+
+        let stats = ReadingStats::of(&question_data.question, &question_data.options, &question_data.explanation);
+        let mut savepoint = Acquire::begin(&mut *transaction).await.map_err(|e| errors::internal("Failed to create savepoint", e))?;
 
-    for (index, question_data) in payload.questions.iter().enumerate() {
         let result = sqlx::query(
             "INSERT INTO questions (
                 topic_id, question_number, question, options, correct_answer, 
-                explanation, question_type, difficulty, tags
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+                explanation, question_type, difficulty, tags, option_explanations, hints, metadata, variables, code_blocks,
+                cognitive_level, word_count, estimated_secs
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, '[]'::jsonb), $10, $11, $12, $13, $14, $15, $16, $17)"
         )
         .bind(topic_id)
-        .bind(question_data.question_number)
+        .bind(numbers[index])
         .bind(&question_data.question)
         .bind(SqlxJson(&question_data.options))           //  Fixed: Wrapped in SqlxJson
         .bind(SqlxJson(&question_data.correct_answer))    //  Fixed: Wrapped in SqlxJson
         .bind(&question_data.explanation)
         .bind(&question_data.question_type)
         .bind(question_data.difficulty.as_ref().unwrap_or(&Difficulty::Medium))
-        .bind(question_data.tags.as_ref().map(|t| SqlxJson(t))) //  Fixed: Wrapped in SqlxJson
-        .execute(&mut *transaction)
+        .bind(question_data.tags.as_ref().map(SqlxJson)) //  Fixed: Wrapped in SqlxJson
+        .bind(question_data.option_explanations.as_ref().map(SqlxJson))
+        .bind(question_data.hints.as_ref().map(SqlxJson))
+        .bind(SqlxJson(&metadata))
+        .bind(variables.map(SqlxJson))
+        .bind(code_blocks.map(SqlxJson))
+        .bind(question_data.cognitive_level)
+        .bind(stats.word_count)
+        .bind(stats.estimated_secs)
+        .execute(&mut *savepoint)
         .await;
 
+        // Dropping the savepoint on error rolls back just this row
         match result {
-            Ok(_) => created += 1,
-            Err(e) => {
-                failed += 1;
-                errors.push(format!("Question {}: {}", index + 1, e));
+            Ok(_) => {
+                savepoint.commit().await.map_err(|e| errors::internal("Failed to release savepoint", e))?;
+                created += 1;
             }
+            Err(e) => errors.push(RowError { row: index + 1, error: row_insert_error(e, numbers[index]) }),
         }
     }
 
-    if failed == 0 {
-        transaction.commit().await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to commit transaction: {}", e))),
-            )
-        })?;
+    if errors.is_empty() || on_error == OnError::Skip {
+        transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+        catalog::invalidate();
     } else {
-        transaction.rollback().await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to rollback transaction: {}", e))),
-            )
-        })?;
+        transaction.rollback().await.map_err(|e| errors::internal("Failed to rollback transaction", e))?;
+        created = 0;
     }
 
-    let response = BulkCreateResponse {
-        created,
-        failed,
-        errors,
+    Ok((created, errors))
+}
+
+/// Why a row could not be inserted, without database details: these messages end up in
+/// import responses and stored error reports
+fn row_insert_error(e: sqlx::Error, question_number: i32) -> String {
+    match e.as_database_error() {
+        Some(db) if db.is_unique_violation() => format!("The topic already has a question with number {}", question_number),
+        Some(db) if db.is_foreign_key_violation() => "The topic no longer exists".to_string(),
+        _ => errors::sanitize("Failed to insert question", e),
+    }
+}
+
+/// Parse the `metadata` query parameter, which must be a JSON object
+fn metadata_filter(raw: Option<&str>) -> Result<Option<serde_json::Value>, (StatusCode, Json<ApiResponse<()>>)> {
+    let Some(raw) = raw else {
+        return Ok(None);
     };
 
-    Ok(Json(ApiResponse::success(response)))
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(value) if value.is_object() => Ok(Some(value)),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "metadata must be a JSON object, e.g. {\"chapter\":\"3\"}".to_string())),
+        )),
+    }
 }
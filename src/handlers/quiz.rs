@@ -0,0 +1,760 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json
+};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, types::Json as SqlxJson};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    CognitiveLevel, Difficulty, Question, QuizSession, SessionAnswer, SessionStatus,
+    CreateQuizSession, SaveAnswer,
+    QuizSessionResponse, QuizSessionState, SavedAnswerResponse, HintResponse,
+    QuestionMark, SessionNavigation,
+    BatchSaveAnswers, BatchAnswerResult, BatchAnswerStatus,
+    GenerateQuizQuery, GeneratedQuiz, GeneratedQuestion,
+    ApiResponse, ApiWarning, ErrorCode, deal_questions, deal_questions_with_mix, deal_questions_with_level_mix, fit_to_time_limit, order_dealt,
+};
+use crate::handlers::prerequisite::topic_edges;
+use crate::passback;
+
+/// Most answers accepted in one batch
+const MAX_BATCH_ANSWERS: usize = 200;
+
+// Quiz session handlers
+pub async fn create_session(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateQuizSession>,
+) -> Result<Json<ApiResponse<QuizSessionState>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let count = payload.question_count.unwrap_or(20).clamp(1, 100) as usize;
+    let seed = payload.seed.unwrap_or_else(rand::random::<i64>);
+
+    let hint_penalty = payload.hint_penalty.unwrap_or(0.0);
+    if !(0.0..=1.0).contains(&hint_penalty) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "hint_penalty must be between 0 and 1".to_string())),
+        ));
+    }
+
+    if payload.time_limit_secs.is_some_and(|limit| limit <= 0)
+        || payload.pause_budget_secs.is_some_and(|budget| budget < 0)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "time_limit_secs must be positive and pause_budget_secs not negative".to_string())),
+        ));
+    }
+
+    // A pause budget only applies to timed mocks, which get none unless asked for
+    let pause_budget_secs = payload.time_limit_secs.map(|_| payload.pause_budget_secs.unwrap_or(0));
+
+    if let Some(mix) = &payload.difficulty_mix
+        && mix.values().all(|&weight| weight == 0)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "difficulty_mix needs at least one non-zero weight".to_string())),
+        ));
+    }
+
+    if let Some(mix) = &payload.cognitive_level_mix {
+        let message = if payload.difficulty_mix.is_some() {
+            Some("difficulty_mix and cognitive_level_mix cannot be combined")
+        } else if mix.values().all(|&weight| weight == 0) {
+            Some("cognitive_level_mix needs at least one non-zero weight")
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationFailed, message.to_string())),
+            ));
+        }
+    }
+
+    // Deal from the full pool in a stable order so the same seed always yields the same questions
+    let pool_questions: Vec<(Uuid, Difficulty, Option<CognitiveLevel>, i32)> = sqlx::query_as(
+        "SELECT id, difficulty, cognitive_level, estimated_secs FROM questions WHERE topic_id = $1 ORDER BY question_number"
+    )
+    .bind(payload.topic_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    if pool_questions.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::TopicHasNoQuestions, "Topic has no questions to build a session from".to_string())),
+        ));
+    }
+
+    let topic_questions: Vec<Uuid> = pool_questions.iter().map(|(id, _, _, _)| *id).collect();
+    let (question_ids, mut warnings) = match (&payload.difficulty_mix, &payload.cognitive_level_mix) {
+        (Some(mix), _) => deal_questions_with_mix(pool_questions.iter().map(|(id, d, _, _)| (*id, d.clone())).collect(), mix, seed, count),
+        (None, Some(mix)) => deal_questions_with_level_mix(pool_questions.iter().map(|(id, _, l, _)| (*id, *l)).collect(), mix, seed, count),
+        (None, None) => (deal_questions(topic_questions.clone(), seed, count), Vec::new()),
+    };
+
+    // A timed mock gets questions whose reading time fits the limit, within the same mix
+    let question_ids = match payload.time_limit_secs {
+        Some(limit) => {
+            let candidates: Vec<(Uuid, i32, u8)> = pool_questions
+                .iter()
+                .map(|(id, difficulty, level, secs)| {
+                    let group = match (&payload.difficulty_mix, &payload.cognitive_level_mix) {
+                        (Some(_), _) => Difficulty::ALL.iter().position(|d| d == difficulty).unwrap_or_default() as u8 + 1,
+                        (None, Some(_)) => level.map_or(0, |l| CognitiveLevel::ALL.iter().position(|&c| c == l).unwrap_or_default() as u8 + 1),
+                        (None, None) => 0,
+                    };
+                    (*id, *secs, group)
+                })
+                .collect();
+
+            let (question_ids, warning) = fit_to_time_limit(question_ids, &candidates, limit as i64);
+            warnings.extend(warning);
+            question_ids
+        }
+        None => question_ids,
+    };
+
+    // Ask prerequisites before the questions that build on them
+    let edges = topic_edges(&pool, payload.topic_id)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch prerequisites", e))?;
+    let question_ids = order_dealt(question_ids, &topic_questions, &edges);
+
+    let session = sqlx::query_as::<_, QuizSession>(
+        "INSERT INTO quiz_sessions (
+            topic_id, question_ids, seed, hint_penalty, time_limit_secs, pause_budget_secs, learner_ref
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *"
+    )
+    .bind(payload.topic_id)
+    .bind(SqlxJson(&question_ids))
+    .bind(seed)
+    .bind(hint_penalty)
+    .bind(payload.time_limit_secs)
+    .bind(pause_budget_secs)
+    .bind(payload.learner_ref.as_deref().map(str::trim).filter(|r| !r.is_empty()))
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to create session", e))?;
+
+    let questions = get_session_questions(&pool, &question_ids).await?;
+
+    let state = QuizSessionState {
+        questions: questions.into_iter().map(|q| session.present(session.instantiate(q))).collect(),
+        navigation: SessionNavigation::new(&question_ids, &[], &[]),
+        session: QuizSessionResponse::from(session),
+        answers: Vec::new(),
+    };
+
+    Ok(Json(ApiResponse::success(state).with_warnings(warnings)))
+}
+
+pub async fn get_session(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<QuizSessionState>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(&pool, id).await?;
+    let questions = get_session_questions(&pool, &session.question_ids.0).await?;
+
+    let answers = sqlx::query_as::<_, SessionAnswer>(
+        "SELECT * FROM quiz_session_answers WHERE session_id = $1 ORDER BY saved_at"
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch saved answers", e))?;
+
+    let revealed: HashMap<Uuid, i32> = sqlx::query_as::<_, (Uuid, i32)>(
+        "SELECT question_id, revealed FROM quiz_session_hints WHERE session_id = $1"
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch revealed hints", e))?
+    .into_iter()
+    .collect();
+
+    let marked: Vec<Uuid> = sqlx::query_scalar("SELECT question_id FROM quiz_session_marks WHERE session_id = $1")
+        .bind(id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch marked questions", e))?;
+
+    let answered: Vec<Uuid> = answers.iter().map(|a| a.question_id).collect();
+    let navigation = SessionNavigation::new(&session.question_ids.0, &answered, &marked);
+
+    let questions = questions
+        .into_iter()
+        .map(|q| {
+            let q = session.instantiate(q);
+            let hints = q.hints.as_ref().map(|h| h.0.clone()).unwrap_or_default();
+            let count = revealed.get(&q.id).copied().unwrap_or(0) as usize;
+            let mut presented = session.present(q);
+            presented.hints_revealed = hints.into_iter().take(count).collect();
+            presented
+        })
+        .collect();
+
+    let state = QuizSessionState {
+        questions,
+        navigation,
+        session: QuizSessionResponse::from(session),
+        answers: answers.into_iter().map(SavedAnswerResponse::from).collect(),
+    };
+
+    Ok(Json(ApiResponse::success(state)))
+}
+
+pub async fn save_answer(
+    State(pool): State<PgPool>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SaveAnswer>,
+) -> Result<Json<ApiResponse<SavedAnswerResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(&pool, id).await?;
+
+    if session.status != SessionStatus::InProgress {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    }
+
+    if !session.question_ids.0.contains(&question_id) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotInSession, "Question is not part of this session".to_string())),
+        ));
+    }
+
+    ensure_clock_running(&session)?;
+
+    if payload.confidence.is_some_and(|c| !(1..=5).contains(&c)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "confidence must be between 1 and 5".to_string())),
+        ));
+    }
+
+    let saved = upsert_answer(&pool, id, question_id, &payload).await.map_err(|e| errors::internal("Failed to save answer", e))?;
+
+    let answer = match saved {
+        Some(answer) => answer,
+        None => sqlx::query_as::<_, SessionAnswer>(
+            "SELECT * FROM quiz_session_answers WHERE session_id = $1 AND question_id = $2"
+        )
+        .bind(id)
+        .bind(question_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch saved answer", e))?,
+    };
+
+    Ok(Json(ApiResponse::success(SavedAnswerResponse::from(answer))))
+}
+
+/// Save many answers at once, for clients that buffer offline or submit at the end. Each
+/// answer is checked on its own and reported as accepted, stale or rejected. Results are
+/// not paged; there is one per submitted answer, at most `MAX_BATCH_ANSWERS`.
+pub async fn save_answers_batch(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<BatchSaveAnswers>,
+) -> Result<Json<ApiResponse<Vec<BatchAnswerResult>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.answers.len() > MAX_BATCH_ANSWERS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("A batch can hold at most {} answers", MAX_BATCH_ANSWERS))),
+        ));
+    }
+
+    let session = get_session_by_id(&pool, id).await?;
+
+    if session.status != SessionStatus::InProgress {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    }
+
+    ensure_clock_running(&session)?;
+
+    let mut results = Vec::with_capacity(payload.answers.len());
+    for item in &payload.answers {
+        let rejected = if !session.question_ids.0.contains(&item.question_id) {
+            Some("Question is not part of this session".to_string())
+        } else if item.answer.confidence.is_some_and(|c| !(1..=5).contains(&c)) {
+            Some("confidence must be between 1 and 5".to_string())
+        } else {
+            None
+        };
+
+        let status = match rejected {
+            Some(error) => BatchAnswerStatus::Rejected { error },
+            None => match upsert_answer(&pool, id, item.question_id, &item.answer).await {
+                Ok(Some(_)) => BatchAnswerStatus::Accepted,
+                Ok(None) => BatchAnswerStatus::Stale,
+                Err(e) => BatchAnswerStatus::Rejected { error: errors::sanitize("Failed to save answer", e) },
+            },
+        };
+
+        results.push(BatchAnswerResult { question_id: item.question_id, status });
+    }
+
+    Ok(Json(ApiResponse::success(results)))
+}
+
+/// Stop the clock. Untimed sessions can pause freely; timed mocks only while pause
+/// budget remains.
+pub async fn pause_session(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<QuizSessionResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(&pool, id).await?;
+
+    if session.status != SessionStatus::InProgress {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    }
+
+    ensure_clock_running(&session)?;
+
+    if session.pause_budget_secs.is_some_and(|budget| session.paused_secs >= budget) {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::PauseBudgetExhausted, "Pause budget has been used up".to_string())),
+        ));
+    }
+
+    let session = sqlx::query_as::<_, QuizSession>(
+        "UPDATE quiz_sessions SET paused_at = NOW(), updated_at = NOW()
+         WHERE id = $1 AND status = 'in_progress' AND paused_at IS NULL RETURNING *"
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to pause session", e))?;
+
+    match session {
+        Some(session) => Ok(Json(ApiResponse::success(QuizSessionResponse::from(session)))),
+        None => Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionPaused, "Session is already paused".to_string())),
+        )),
+    }
+}
+
+pub async fn resume_session(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<QuizSessionResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(&pool, id).await?;
+
+    let Some(paused_at) = session.paused_at else {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionNotPaused, "Session is not paused".to_string())),
+        ));
+    };
+
+    // Matching on paused_at keeps a concurrent resume from crediting the pause twice
+    let session = sqlx::query_as::<_, QuizSession>(
+        "UPDATE quiz_sessions SET paused_secs = paused_secs + $1, paused_at = NULL, updated_at = NOW()
+         WHERE id = $2 AND paused_at = $3 RETURNING *"
+    )
+    .bind(session.current_pause_credit(Utc::now()))
+    .bind(id)
+    .bind(paused_at)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to resume session", e))?;
+
+    match session {
+        Some(session) => Ok(Json(ApiResponse::success(QuizSessionResponse::from(session)))),
+        None => Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionNotPaused, "Session is not paused".to_string())),
+        )),
+    }
+}
+
+/// Mark a question to come back to it later, as in real exam UIs
+pub async fn mark_question(
+    State(pool): State<PgPool>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<QuestionMark>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_session_question(&pool, id, question_id).await?;
+
+    sqlx::query(
+        "INSERT INTO quiz_session_marks (session_id, question_id) VALUES ($1, $2)
+         ON CONFLICT (session_id, question_id) DO NOTHING"
+    )
+    .bind(id)
+    .bind(question_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to mark question", e))?;
+
+    Ok(Json(ApiResponse::success(QuestionMark { question_id, marked: true })))
+}
+
+pub async fn unmark_question(
+    State(pool): State<PgPool>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<QuestionMark>>, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_session_question(&pool, id, question_id).await?;
+
+    sqlx::query("DELETE FROM quiz_session_marks WHERE session_id = $1 AND question_id = $2")
+        .bind(id)
+        .bind(question_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to unmark question", e))?;
+
+    Ok(Json(ApiResponse::success(QuestionMark { question_id, marked: false })))
+}
+
+/// Reveal the next hint for a question. Each hint counts towards the session's hint
+/// penalty, so revealing is recorded before the hint is returned.
+pub async fn reveal_hint(
+    State(pool): State<PgPool>,
+    Path((id, question_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<HintResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = ensure_session_question(&pool, id, question_id).await?;
+
+    let question = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = $1")
+        .bind(question_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch hints", e))?;
+    let hints = question
+        .map(|q| session.instantiate(q))
+        .and_then(|q| q.hints)
+        .map(|h| h.0)
+        .unwrap_or_default();
+
+    if hints.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NoHints, "Question has no hints".to_string())),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Only advances while hints remain, so a rejected request is not counted
+    let revealed: Option<i32> = sqlx::query_scalar(
+        "INSERT INTO quiz_session_hints (session_id, question_id, revealed) VALUES ($1, $2, 1)
+         ON CONFLICT (session_id, question_id) DO UPDATE SET
+            revealed = quiz_session_hints.revealed + 1,
+            revealed_at = NOW()
+         WHERE quiz_session_hints.revealed < $3
+         RETURNING revealed"
+    )
+    .bind(id)
+    .bind(question_id)
+    .bind(hints.len() as i32)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to record hint", e))?;
+
+    let Some(revealed) = revealed else {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::HintsExhausted, "All hints for this question have been revealed".to_string())),
+        ));
+    };
+
+    let session_hints_used: i32 = sqlx::query_scalar(
+        "UPDATE quiz_sessions SET hints_used = hints_used + 1, updated_at = NOW() WHERE id = $1 RETURNING hints_used"
+    )
+    .bind(id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to record hint", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    Ok(Json(ApiResponse::success(HintResponse {
+        question_id,
+        hint: hints[revealed as usize - 1].clone(),
+        hint_number: revealed,
+        hints_remaining: hints.len() as i32 - revealed,
+        session_hints_used,
+    })))
+}
+
+pub async fn submit_session(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<QuizSessionResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(&pool, id).await?;
+
+    if session.status != SessionStatus::InProgress {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    }
+
+    let questions = get_session_questions(&pool, &session.question_ids.0).await?;
+
+    let answers = sqlx::query_as::<_, SessionAnswer>(
+        "SELECT * FROM quiz_session_answers WHERE session_id = $1"
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch saved answers", e))?;
+
+    let grades = grade_answers(&session, &questions, &answers);
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    // Submitting ends any pause in progress, crediting it within the pause budget
+    let session = sqlx::query_as::<_, QuizSession>(
+        "UPDATE quiz_sessions SET
+            status = 'submitted',
+            score = $1,
+            submitted_at = NOW(),
+            paused_secs = paused_secs + $2,
+            paused_at = NULL,
+            updated_at = NOW()
+         WHERE id = $3 AND status = 'in_progress' RETURNING *"
+    )
+    .bind(score(&grades))
+    .bind(session.current_pause_credit(Utc::now()))
+    .bind(id)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to submit session", e))?;
+
+    let Some(session) = session else {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    };
+
+    record_grades(&mut *transaction, id, &grades).await.map_err(|e| errors::internal("Failed to record grades", e))?;
+
+    transaction.commit().await.map_err(|e| errors::internal("Failed to commit transaction", e))?;
+
+    passback::enqueue(&pool, &session).await;
+
+    Ok(Json(ApiResponse::success(QuizSessionResponse::from(session))))
+}
+
+/// A random, non-repeating set of a topic's questions matching the filters, with options
+/// shuffled and no answers. Nothing is stored; every call draws a new set.
+pub async fn generate_quiz(
+    State(pool): State<PgPool>,
+    Query(query): Query<GenerateQuizQuery>,
+) -> Result<Json<ApiResponse<GeneratedQuiz>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let count = query.count.unwrap_or(20).clamp(1, 100);
+
+    let topic_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM topics WHERE id = $1)")
+        .bind(query.topic_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    if !topic_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    }
+
+    // Topics are small enough to sort whole; the limit keeps the response bounded
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions
+         WHERE topic_id = $1
+           AND ($2::difficulty_level IS NULL OR difficulty = $2)
+           AND ($3::question_type IS NULL OR question_type = $3)
+         ORDER BY random()
+         LIMIT $4"
+    )
+    .bind(query.topic_id)
+    .bind(&query.difficulty)
+    .bind(&query.question_type)
+    .bind(count)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    if questions.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::TopicHasNoQuestions, "No questions in the topic match the filters".to_string())),
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    if (questions.len() as i64) < count {
+        warnings.push(ApiWarning::new(
+            "INSUFFICIENT_QUESTIONS",
+            "count",
+            format!("Only {} of the {} requested questions match the filters", questions.len(), count),
+        ));
+    }
+
+    let mut rng = rand::thread_rng();
+    let quiz = GeneratedQuiz {
+        topic_id: query.topic_id,
+        questions: questions.into_iter().map(|q| GeneratedQuestion::generate(q, &mut rng)).collect(),
+    };
+
+    Ok(Json(ApiResponse::success(quiz).with_warnings(warnings)))
+}
+
+// Helper functions
+pub async fn get_session_by_id(pool: &PgPool, id: Uuid) -> Result<QuizSession, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = sqlx::query_as::<_, QuizSession>("SELECT * FROM quiz_sessions WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch session", e))?;
+
+    match session {
+        Some(session) => Ok(session),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::SessionNotFound, "Session not found".to_string())),
+        )),
+    }
+}
+
+/// Check that a session is still in progress and that the question was dealt in it
+async fn ensure_session_question(
+    pool: &PgPool,
+    id: Uuid,
+    question_id: Uuid,
+) -> Result<QuizSession, (StatusCode, Json<ApiResponse<()>>)> {
+    let session = get_session_by_id(pool, id).await?;
+
+    if session.status != SessionStatus::InProgress {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionSubmitted, "Session has already been submitted".to_string())),
+        ));
+    }
+
+    if !session.question_ids.0.contains(&question_id) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::QuestionNotInSession, "Question is not part of this session".to_string())),
+        ));
+    }
+
+    Ok(session)
+}
+
+/// Answers and pauses need a running clock: not paused, and time left on a timed mock
+fn ensure_clock_running(session: &QuizSession) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    if session.paused_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::SessionPaused, "Session is paused".to_string())),
+        ));
+    }
+
+    if session.is_out_of_time(Utc::now()) {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::TimeLimitReached, "Time limit has been reached".to_string())),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Store an answer draft. Last write wins: an older draft arriving late never overwrites
+/// a newer one, in which case nothing is returned.
+async fn upsert_answer(
+    pool: &PgPool,
+    session_id: Uuid,
+    question_id: Uuid,
+    answer: &SaveAnswer,
+) -> Result<Option<SessionAnswer>, sqlx::Error> {
+    sqlx::query_as::<_, SessionAnswer>(
+        "INSERT INTO quiz_session_answers (session_id, question_id, selected, confidence, saved_at)
+         VALUES ($1, $2, $3, $4, COALESCE($5, NOW()))
+         ON CONFLICT (session_id, question_id) DO UPDATE SET
+            selected = EXCLUDED.selected,
+            confidence = EXCLUDED.confidence,
+            saved_at = EXCLUDED.saved_at
+         WHERE quiz_session_answers.saved_at <= EXCLUDED.saved_at
+         RETURNING *"
+    )
+    .bind(session_id)
+    .bind(question_id)
+    .bind(SqlxJson(&answer.selected))
+    .bind(answer.confidence)
+    .bind(answer.saved_at)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Load the session's questions, keeping the order they were dealt in
+pub async fn get_session_questions(pool: &PgPool, question_ids: &[Uuid]) -> Result<Vec<Question>, (StatusCode, Json<ApiResponse<()>>)> {
+    let questions = sqlx::query_as::<_, Question>("SELECT * FROM questions WHERE id = ANY($1)")
+        .bind(question_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let mut by_id: HashMap<Uuid, Question> = questions.into_iter().map(|q| (q.id, q)).collect();
+
+    Ok(question_ids.iter().filter_map(|id| by_id.remove(id)).collect())
+}
+
+/// Grade each saved answer against its question's correct answer
+pub fn grade_answers(session: &QuizSession, questions: &[Question], answers: &[SessionAnswer]) -> Vec<(Uuid, bool)> {
+    let by_id: HashMap<Uuid, &Question> = questions.iter().map(|q| (q.id, q)).collect();
+
+    answers
+        .iter()
+        .filter_map(|a| {
+            let question = by_id.get(&a.question_id)?;
+            let selected = session.to_canonical_labels(question, &a.selected.0);
+            Some((a.question_id, question.is_correct_answer(&selected)))
+        })
+        .collect()
+}
+
+/// Score of a graded session: the number of correct answers
+pub fn score(grades: &[(Uuid, bool)]) -> i32 {
+    grades.iter().filter(|(_, correct)| *correct).count() as i32
+}
+
+/// Store per-answer correctness, used by the analytics views
+pub async fn record_grades<'e>(
+    executor: impl PgExecutor<'e>,
+    session_id: Uuid,
+    grades: &[(Uuid, bool)],
+) -> Result<(), sqlx::Error> {
+    let (question_ids, correct): (Vec<Uuid>, Vec<bool>) = grades.iter().copied().unzip();
+
+    sqlx::query(
+        "UPDATE quiz_session_answers a SET is_correct = g.correct
+         FROM UNNEST($2::uuid[], $3::bool[]) AS g(question_id, correct)
+         WHERE a.session_id = $1 AND a.question_id = g.question_id"
+    )
+    .bind(session_id)
+    .bind(question_ids)
+    .bind(correct)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json
+};
+use sqlx::PgPool;
+
+use crate::errors;
+use crate::models::{
+    SuggestQuery, SearchSuggestions, TermSuggestion, TopicSuggestion,
+    SearchQuery, SearchResults, TopicSearchHit, ScoredQuestion, QuestionSearchHit, RedactionPolicy,
+    ApiResponse, ErrorCode,
+};
+
+/// Short excerpts around the best match, wrapped in tags the UI can style
+pub const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20, MinWords=8";
+
+// Search handlers
+pub async fn search(
+    State(pool): State<PgPool>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<ApiResponse<SearchResults>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let q = query.q.trim().to_string();
+    if q.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "Query parameter 'q' must not be empty".to_string())),
+        ));
+    }
+
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+
+    // Topic names are short, so fuzzy word similarity catches partial words and typos
+    let topics = sqlx::query_as::<_, TopicSearchHit>(
+        "SELECT t.*, GREATEST(
+                ts_rank(to_tsvector('english', t.name || ' ' || COALESCE(t.description, '')), websearch_to_tsquery('english', $1)),
+                word_similarity($1, t.name)
+            )::float8 AS score
+         FROM topics t
+         WHERE to_tsvector('english', t.name || ' ' || COALESCE(t.description, '')) @@ websearch_to_tsquery('english', $1)
+            OR $1 <% t.name
+         ORDER BY score DESC, t.name
+         LIMIT $2"
+    )
+    .bind(&q)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to search topics", e))?;
+
+    let questions = sqlx::query_as::<_, ScoredQuestion>(
+        "SELECT q.*, ts_rank(
+                to_tsvector('english', q.question || ' ' || COALESCE(q.explanation, '')),
+                websearch_to_tsquery('english', $1)
+            )::float8 AS score,
+            CASE WHEN $3 THEN ts_headline('english', q.question, websearch_to_tsquery('english', $1), $4) END
+                AS question_headline,
+            CASE WHEN $3 THEN ts_headline('english', q.explanation, websearch_to_tsquery('english', $1), $4) END
+                AS explanation_headline
+         FROM questions q
+         WHERE to_tsvector('english', q.question || ' ' || COALESCE(q.explanation, '')) @@ websearch_to_tsquery('english', $1)
+         ORDER BY score DESC, q.question_number
+         LIMIT $2"
+    )
+    .bind(&q)
+    .bind(limit)
+    .bind(query.highlight)
+    .bind(HEADLINE_OPTIONS)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to search questions", e))?;
+
+    let policy = RedactionPolicy::for_audience(query.view);
+
+    Ok(Json(ApiResponse::success(SearchResults {
+        query: q,
+        topics,
+        questions: questions
+            .into_iter()
+            .map(|mut scored| {
+                // Explanation fragments would leak what the policy hides
+                if !policy.explanation {
+                    scored.explanation_headline = None;
+                }
+                let mut hit = QuestionSearchHit::from(scored);
+                hit.question = hit.question.redact(&policy).render_math(query.math).render_code(query.code);
+                hit
+            })
+            .collect(),
+    })))
+}
+
+pub async fn suggest(
+    State(pool): State<PgPool>,
+    Query(query): Query<SuggestQuery>,
+) -> Result<Json<ApiResponse<SearchSuggestions>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let prefix = query.q.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "Query parameter 'q' must not be empty".to_string())),
+        ));
+    }
+
+    let limit = query.limit.unwrap_or(5).clamp(1, 20);
+    let pattern = format!("{}%", escape_like(&prefix));
+
+    // Topics match on any word of the name, prefix matches first
+    let topics = sqlx::query_as::<_, TopicSuggestion>(
+        "SELECT id, name, slug FROM topics
+         WHERE name ILIKE $1 OR name ILIKE '% ' || $1
+         ORDER BY name NOT ILIKE $1, similarity(name, $2) DESC, name
+         LIMIT $3"
+    )
+    .bind(&pattern)
+    .bind(&prefix)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to suggest topics", e))?;
+
+    let tags = sqlx::query_as::<_, TermSuggestion>(
+        "SELECT tag AS value, COUNT(*) AS question_count
+         FROM questions, jsonb_array_elements_text(tags) AS tag
+         WHERE LOWER(tag) LIKE $1
+         GROUP BY tag
+         ORDER BY question_count DESC, tag
+         LIMIT $2"
+    )
+    .bind(&pattern)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to suggest tags", e))?;
+
+    let terms = sqlx::query_as::<_, TermSuggestion>(
+        "SELECT term AS value, question_count::int8 AS question_count FROM question_terms
+         WHERE term LIKE $1
+         ORDER BY question_count DESC, term
+         LIMIT $2"
+    )
+    .bind(&pattern)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to suggest terms", e))?;
+
+    Ok(Json(ApiResponse::success(SearchSuggestions { topics, tags, terms })))
+}
+
+// Helper functions
+/// Escape LIKE wildcards so user input only ever matches literally
+fn escape_like(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
@@ -0,0 +1,187 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Redirect},
+    Json
+};
+use chrono::Utc;
+use rand::Rng;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::encoding;
+use crate::handlers::catalog::public_url;
+use crate::models::{ShareLink, CreateShareLink, ShareLinkResponse, ShareTarget, QrQuery, ApiResponse, ErrorCode};
+
+/// Share codes avoid characters that are easily confused when read aloud or typed (0/O, 1/I)
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 6;
+
+// Share link handlers
+pub async fn create_share_link(
+    State(pool): State<PgPool>,
+    Json(payload): Json<CreateShareLink>,
+) -> Result<Json<ApiResponse<ShareLinkResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, "expires_at must be in the future".to_string())),
+        ));
+    }
+
+    // Fails with 404 when the target does not exist
+    let target_url = target_url(&pool, payload.target_type, payload.target_id).await?;
+
+    // Retry on the rare collision with an existing code
+    for _ in 0..5 {
+        let link = sqlx::query_as::<_, ShareLink>(
+            "INSERT INTO share_links (code, target_type, target_id, expires_at)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (code) DO NOTHING RETURNING *"
+        )
+        .bind(generate_code())
+        .bind(payload.target_type)
+        .bind(payload.target_id)
+        .bind(payload.expires_at)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| errors::internal("Failed to create share link", e))?;
+
+        if let Some(link) = link {
+            return Ok(Json(ApiResponse::success(ShareLinkResponse {
+                short_url: short_url(&link.code),
+                link,
+                target_url,
+            })));
+        }
+    }
+
+    Err((
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::error(ErrorCode::InternalError, "Failed to generate a unique share code".to_string())),
+    ))
+}
+
+pub async fn get_share_link(
+    State(pool): State<PgPool>,
+    Path(code): Path<String>,
+) -> Result<Json<ApiResponse<ShareLinkResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let link = get_link_by_code(&pool, &code).await?;
+    let target_url = target_url(&pool, link.target_type, link.target_id).await?;
+
+    Ok(Json(ApiResponse::success(ShareLinkResponse {
+        short_url: short_url(&link.code),
+        link,
+        target_url,
+    })))
+}
+
+/// `GET /s/{code}`: count the visit and redirect to the shared page
+pub async fn follow_share_link(
+    State(pool): State<PgPool>,
+    Path(code): Path<String>,
+) -> Result<Redirect, (StatusCode, Json<ApiResponse<()>>)> {
+    let link = get_link_by_code(&pool, &code).await?;
+
+    if link.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err((
+            StatusCode::GONE,
+            Json(ApiResponse::error(ErrorCode::ShareLinkExpired, "This share link has expired".to_string())),
+        ));
+    }
+
+    let target_url = target_url(&pool, link.target_type, link.target_id).await?;
+
+    sqlx::query(
+        "UPDATE share_links SET access_count = access_count + 1, last_accessed_at = NOW() WHERE id = $1"
+    )
+    .bind(link.id)
+    .execute(&pool)
+    .await
+    .map_err(|e| errors::internal("Failed to record share link access", e))?;
+
+    Ok(Redirect::to(&target_url))
+}
+
+/// QR code of the short URL, for projecting in a classroom
+pub async fn get_share_qr(
+    State(pool): State<PgPool>,
+    Path(code): Path<String>,
+    Query(query): Query<QrQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let link = get_link_by_code(&pool, &code).await?;
+
+    if link.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err((
+            StatusCode::GONE,
+            Json(ApiResponse::error(ErrorCode::ShareLinkExpired, "This share link has expired".to_string())),
+        ));
+    }
+
+    let scale = query.scale.unwrap_or(8).clamp(1, 32);
+    let png = encoding::to_qr_png(&short_url(&link.code), scale).map_err(|e| errors::internal("Failed to render QR code", e))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        png,
+    ))
+}
+
+// Helper functions
+pub async fn get_link_by_code(pool: &PgPool, code: &str) -> Result<ShareLink, (StatusCode, Json<ApiResponse<()>>)> {
+    // Codes are stored uppercase but accepted in any case, since people type them
+    let link = sqlx::query_as::<_, ShareLink>("SELECT * FROM share_links WHERE code = UPPER($1)")
+        .bind(code)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch share link", e))?;
+
+    link.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::ShareLinkNotFound, "Share link not found".to_string())),
+        )
+    })
+}
+
+pub fn short_url(code: &str) -> String {
+    public_url(&format!("/s/{}", code))
+}
+
+/// Public page of the shared question or topic
+async fn target_url(pool: &PgPool, target_type: ShareTarget, target_id: Uuid) -> Result<String, (StatusCode, Json<ApiResponse<()>>)> {
+    let path = match target_type {
+        ShareTarget::Topic => sqlx::query_scalar::<_, String>("SELECT '/topics/' || slug FROM topics WHERE id = $1"),
+        ShareTarget::Question => sqlx::query_scalar::<_, String>(
+            "SELECT '/topics/' || t.slug || '/questions/' || q.question_number
+             FROM questions q JOIN topics t ON q.topic_id = t.id
+             WHERE q.id = $1"
+        ),
+    }
+    .bind(target_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to resolve share link", e))?;
+
+    match path {
+        Some(path) => Ok(public_url(&path)),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(match target_type {
+                ShareTarget::Topic => ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string()),
+                ShareTarget::Question => ApiResponse::error(ErrorCode::QuestionNotFound, "Question not found".to_string()),
+            }),
+        )),
+    }
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LENGTH)
+        .map(|_| char::from(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())]))
+        .collect()
+}
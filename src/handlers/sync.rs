@@ -0,0 +1,369 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json
+};
+use chrono::{DateTime, Utc};
+use flate2::{write::GzEncoder, Compression};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction, types::Json as SqlxJson};
+use std::collections::HashMap;
+use std::io::Write;
+use uuid::Uuid;
+
+use crate::errors;
+use crate::models::{
+    Question, QuizSession, SessionAnswer, Topic,
+    Certification, BundleQuery, BundleQuestion, BundleTopic, OfflineBundle, CertificationBundle, BUNDLE_VERSION,
+    SyncResults, SyncResultsResponse, SyncSessionRecord, RejectedRecord, DeletedRecord,
+    DeletionsQuery, PaginatedResponse, PaginationMeta, PageQuery,
+    ApiResponse, ErrorCode,
+};
+use crate::handlers::quiz::{grade_answers, record_grades, score};
+
+// Offline bundle handlers
+pub async fn get_topic_bundle(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BundleQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let (mut transaction, generated_at) = begin_delta(&pool).await?;
+
+    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let Some(topic) = topic else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    };
+
+    let bundle = OfflineBundle {
+        version: BUNDLE_VERSION,
+        topic: bundle_topic(&mut transaction, topic, query.since).await?,
+        generated_at,
+        since: query.since,
+        deleted: bundle_deletions(&mut transaction, query.since).await?,
+    };
+
+    let filename = format!("{}-bundle.json.gz", bundle.topic.topic_slug);
+    gzip_attachment(&bundle, &filename)
+}
+
+/// Every topic linked to a certification in one bundle, e.g. to prepare for the whole exam offline
+pub async fn get_certification_bundle(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BundleQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let (mut transaction, generated_at) = begin_delta(&pool).await?;
+
+    let certification = sqlx::query_as::<_, Certification>("SELECT * FROM certifications WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch certification", e))?;
+
+    let Some(certification) = certification else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+        ));
+    };
+
+    let linked = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE certification_id = $1 ORDER BY name")
+        .bind(id)
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    let mut topics = Vec::with_capacity(linked.len());
+    for topic in linked {
+        topics.push(bundle_topic(&mut transaction, topic, query.since).await?);
+    }
+
+    let bundle = CertificationBundle {
+        version: BUNDLE_VERSION,
+        certification_id: certification.id,
+        certification_slug: certification.slug,
+        certification_name: certification.name,
+        generated_at,
+        since: query.since,
+        topics,
+        deleted: bundle_deletions(&mut transaction, query.since).await?,
+    };
+
+    let filename = format!("{}-bundle.json.gz", bundle.certification_slug);
+    gzip_attachment(&bundle, &filename)
+}
+
+// Result sync handlers
+pub async fn sync_results(
+    State(pool): State<PgPool>,
+    Json(payload): Json<SyncResults>,
+) -> Result<Json<ApiResponse<SyncResultsResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let mut response = SyncResultsResponse {
+        accepted: Vec::new(),
+        duplicates: Vec::new(),
+        rejected: Vec::new(),
+    };
+
+    for record in &payload.sessions {
+        match store_session_record(&pool, record).await {
+            Ok(true) => response.accepted.push(record.id),
+            Ok(false) => response.duplicates.push(record.id),
+            Err(reason) => response.rejected.push(RejectedRecord { id: record.id, reason }),
+        }
+    }
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+// Change feed handlers
+pub async fn get_deletions(
+    State(pool): State<PgPool>,
+    Query(query): Query<DeletionsQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<DeletedRecord>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, deletions) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM deleted_records
+             WHERE ($1::timestamptz IS NULL OR deleted_at > $1)
+               AND ($2::text IS NULL OR entity_type = $2)"
+        )
+        .bind(query.since)
+        .bind(&query.entity_type)
+        .fetch_one(&pool),
+        sqlx::query_as::<_, DeletedRecord>(
+            "SELECT * FROM deleted_records
+             WHERE ($1::timestamptz IS NULL OR deleted_at > $1)
+               AND ($2::text IS NULL OR entity_type = $2)
+             ORDER BY deleted_at, id
+             LIMIT $3 OFFSET $4"
+        )
+        .bind(query.since)
+        .bind(&query.entity_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&pool),
+    )
+    .map_err(|e| errors::internal("Failed to fetch deletions", e))?;
+
+    Ok(Json(ApiResponse::success(PaginatedResponse {
+        items: deletions,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    })))
+}
+
+// Helper functions
+/// Tombstones for one entity type, oldest first
+pub async fn get_deletions_since(
+    executor: impl PgExecutor<'_>,
+    entity_type: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<DeletedRecord>, (StatusCode, Json<ApiResponse<()>>)> {
+    sqlx::query_as::<_, DeletedRecord>(
+        "SELECT * FROM deleted_records WHERE entity_type = $1 AND deleted_at > $2 ORDER BY deleted_at"
+    )
+    .bind(entity_type)
+    .bind(since)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch deletions", e))
+}
+
+/// Start a read-only snapshot for a delta sync, with the database time to hand back as
+/// `synced_at`. Changed rows and tombstones read through it are consistent with that
+/// time, whatever the application clock says.
+pub async fn begin_delta(pool: &PgPool) -> Result<(Transaction<'static, Postgres>, DateTime<Utc>), (StatusCode, Json<ApiResponse<()>>)> {
+    let mut transaction = pool.begin().await.map_err(|e| errors::internal("Failed to start transaction", e))?;
+
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to start snapshot", e))?;
+
+    // NOW() is the transaction's start, taken before the snapshot's first read
+    let synced_at = sqlx::query_scalar("SELECT NOW()")
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to read database time", e))?;
+
+    Ok((transaction, synced_at))
+}
+
+/// Store one offline session with its answers. Returns `Ok(false)` when a session with
+/// the same client id was already synced, so retried uploads are harmless.
+async fn store_session_record(pool: &PgPool, record: &SyncSessionRecord) -> Result<bool, String> {
+    if record.answers.is_empty() {
+        return Err("Session has no answers".to_string());
+    }
+
+    // Last write wins when the same question was answered more than once
+    let mut latest: HashMap<Uuid, (&Vec<String>, _)> = HashMap::new();
+    for answer in &record.answers {
+        let entry = latest.entry(answer.question_id).or_insert((&answer.selected, answer.answered_at));
+        if answer.answered_at >= entry.1 {
+            *entry = (&answer.selected, answer.answered_at);
+        }
+    }
+
+    let mut ordered: Vec<_> = latest.into_iter().collect();
+    ordered.sort_by_key(|(_, (_, answered_at))| *answered_at);
+    let question_ids: Vec<Uuid> = ordered.iter().map(|(id, _)| *id).collect();
+
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions WHERE id = ANY($1) AND topic_id = $2"
+    )
+    .bind(&question_ids)
+    .bind(record.topic_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| errors::sanitize("Failed to fetch questions", e))?;
+
+    if questions.len() != question_ids.len() {
+        return Err("Session references questions that do not belong to the topic".to_string());
+    }
+
+    let mut transaction = pool.begin().await.map_err(|e| errors::sanitize("Failed to start transaction", e))?;
+
+    let session = sqlx::query_as::<_, QuizSession>(
+        "INSERT INTO quiz_sessions (id, topic_id, question_ids, started_at)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (id) DO NOTHING RETURNING *"
+    )
+    .bind(record.id)
+    .bind(record.topic_id)
+    .bind(SqlxJson(&question_ids))
+    .bind(record.started_at)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| errors::sanitize("Failed to store session", e))?;
+
+    let Some(session) = session else {
+        return Ok(false);
+    };
+
+    let mut answers = Vec::with_capacity(ordered.len());
+    for (question_id, (selected, answered_at)) in ordered {
+        let answer = sqlx::query_as::<_, SessionAnswer>(
+            "INSERT INTO quiz_session_answers (session_id, question_id, selected, saved_at)
+             VALUES ($1, $2, $3, $4) RETURNING *"
+        )
+        .bind(session.id)
+        .bind(question_id)
+        .bind(SqlxJson(selected))
+        .bind(answered_at)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(|e| errors::sanitize(&format!("Failed to store answer for question {}", question_id), e))?;
+
+        answers.push(answer);
+    }
+
+    if let Some(submitted_at) = record.submitted_at {
+        let grades = grade_answers(&session, &questions, &answers);
+
+        record_grades(&mut *transaction, session.id, &grades)
+            .await
+            .map_err(|e| errors::sanitize("Failed to record grades", e))?;
+
+        sqlx::query(
+            "UPDATE quiz_sessions SET status = 'submitted', score = $1, submitted_at = $2, updated_at = NOW() WHERE id = $3"
+        )
+        .bind(score(&grades))
+        .bind(submitted_at)
+        .bind(session.id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| errors::sanitize("Failed to score session", e))?;
+    }
+
+    transaction.commit().await.map_err(|e| errors::sanitize("Failed to commit session", e))?;
+
+    Ok(true)
+}
+
+fn compress_json<T: serde::Serialize>(value: &T) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, value)?;
+    encoder.flush()?;
+
+    Ok(encoder.finish()?)
+}
+
+/// A topic's questions for a bundle; with `since`, only questions changed after it, and
+/// the ids of questions that have left the topic since
+async fn bundle_topic(
+    transaction: &mut Transaction<'static, Postgres>,
+    topic: Topic,
+    since: Option<DateTime<Utc>>,
+) -> Result<BundleTopic, (StatusCode, Json<ApiResponse<()>>)> {
+    let questions = sqlx::query_as::<_, Question>(
+        "SELECT * FROM questions
+         WHERE topic_id = $1 AND ($2::timestamptz IS NULL OR updated_at > $2)
+         ORDER BY question_number"
+    )
+    .bind(topic.id)
+    .bind(since)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    let moved_out: Vec<Uuid> = match since {
+        Some(since) => sqlx::query_scalar(
+            "SELECT DISTINCT r.question_id
+             FROM question_revisions r
+             JOIN questions q ON q.id = r.question_id
+             WHERE 'topic_id' = ANY(r.changed_fields)
+               AND (r.previous->>'topic_id')::uuid = $1
+               AND r.created_at > $2
+               AND q.topic_id <> $1"
+        )
+        .bind(topic.id)
+        .bind(since)
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch moved questions", e))?,
+        None => Vec::new(),
+    };
+
+    Ok(BundleTopic {
+        topic_id: topic.id,
+        topic_slug: topic.slug,
+        topic_name: topic.name,
+        questions: questions.into_iter().map(BundleQuestion::from).collect(),
+        moved_out,
+    })
+}
+
+/// Question tombstones for a delta bundle. Tombstones do not record the topic, so a
+/// client drops the ids it holds and ignores the rest.
+async fn bundle_deletions(
+    transaction: &mut Transaction<'static, Postgres>,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<DeletedRecord>, (StatusCode, Json<ApiResponse<()>>)> {
+    match since {
+        Some(since) => get_deletions_since(&mut **transaction, "question", since).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+fn gzip_attachment<T: serde::Serialize>(bundle: &T, filename: &str) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    let compressed = compress_json(bundle).map_err(|e| errors::internal("Failed to build bundle", e))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        compressed,
+    )
+        .into_response())
+}
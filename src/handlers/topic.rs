@@ -3,28 +3,64 @@ use axum::{
     http::StatusCode, 
     Json
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use sqlx::{PgPool}; 
+use sqlx::{PgExecutor, PgPool}; 
+use std::collections::HashMap;
+use std::env;
 use uuid::Uuid;
 
 
-use crate::models::{generate_slug, ApiResponse, CreateTopic, Topic, UpdateTopic /* other specific items */}; 
+use crate::errors;
+use crate::models::{generate_slug, ApiResponse, ErrorCode, ApiWarning, CreateTopic, Topic, TopicWithCounts, TopicWithQuestions, TopicDependencies, DeleteTopicQuery, UpdateTopic, BulkCreateTopics, BulkCreateTopicsResponse, BulkTopicResult, BulkTopicStatus, DeltaResponse, ListResponse, PaginatedResponse, PaginationMeta, PageQuery, Question, QuestionResponse, RedactionPolicy, ViewQuery}; 
+use crate::handlers::sync::{begin_delta, get_deletions_since};
+use crate::handlers::freeze::ensure_not_frozen;
+use crate::catalog;
+
+/// Most topics accepted in one bulk request
+const MAX_BULK_TOPICS: usize = 100;
 
 // Topic handlers
+#[derive(Debug, Deserialize)]
+pub struct TopicQuery {
+    // Delta sync: return every topic changed after this time, unpaginated, plus deletion tombstones
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
 pub async fn get_topics(
     State(pool): State<PgPool>,
-) -> Result<Json<ApiResponse<Vec<Topic>>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let topics = sqlx::query_as::<_, Topic>("SELECT * FROM topics ORDER BY name")
-        .fetch_all(&pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to fetch topics: {}", e))),
-            )
-        })?;
-
-    Ok(Json(ApiResponse::success(topics)))
+    Query(query): Query<TopicQuery>,
+    Query(page_query): Query<PageQuery>,
+) -> Result<Json<ApiResponse<ListResponse<PaginatedResponse<TopicWithCounts>, TopicWithCounts>>>, (StatusCode, Json<ApiResponse<()>>)> {
+    // Delta syncs need every change, so they are not paged
+    if let Some(since) = query.updated_since {
+        let (mut transaction, synced_at) = begin_delta(&pool).await?;
+
+        let topics = fetch_topics(&mut *transaction, Some(since), None, 0)
+            .await
+            .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+        let delta = DeltaResponse {
+            items: topics,
+            deleted: get_deletions_since(&mut *transaction, "topic", since).await?,
+            synced_at,
+        };
+
+        return Ok(Json(ApiResponse::success(ListResponse::Delta(delta))));
+    }
+
+    let (page, limit, offset) = page_query.resolve();
+
+    let (total_count, topics) = tokio::try_join!(
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM topics").fetch_one(&pool),
+        fetch_topics(&pool, None, Some(limit), offset),
+    )
+    .map_err(|e| errors::internal("Failed to fetch topics", e))?;
+
+    Ok(Json(ApiResponse::success(ListResponse::Full(PaginatedResponse {
+        items: topics,
+        pagination: PaginationMeta::new(page, limit, total_count),
+    }))))
 }
 
 pub async fn get_topic(
@@ -35,79 +71,207 @@ pub async fn get_topic(
         .bind(id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to fetch topic: {}", e))),
-            )
-        })?;
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
 
     match topic {
         Some(topic) => Ok(Json(ApiResponse::success(topic))),
         None => Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Topic not found".to_string())),
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
         )),
     }
 }
 
+pub async fn get_topic_full(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(view): Query<ViewQuery>,
+) -> Result<Json<ApiResponse<TopicWithQuestions>>, (StatusCode, Json<ApiResponse<()>>)> {
+    // Both queries go out together instead of one round trip after the other
+    let (topic, questions) = tokio::join!(
+        sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&pool),
+        sqlx::query_as::<_, Question>(
+            "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number"
+        )
+        .bind(id)
+        .fetch_all(&pool),
+    );
+
+    let topic = topic.map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    let questions = questions.map_err(|e| errors::internal("Failed to fetch questions", e))?;
+
+    match topic {
+        Some(topic) => Ok(Json(ApiResponse::success(TopicWithQuestions {
+            topic,
+            questions: questions
+                .into_iter()
+                .map(|q| QuestionResponse::from(q).redact(&RedactionPolicy::for_audience(view.view)).render_math(view.math).render_code(view.code))
+                .collect(),
+        }))),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        )),
+    }
+}
+
+pub async fn get_topic_dependencies(
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TopicDependencies>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let dependencies = get_dependencies(&pool, id).await?;
+
+    Ok(Json(ApiResponse::success(dependencies)))
+}
+
 pub async fn delete_topic(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
+    Query(query): Query<DeleteTopicQuery>,
 ) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let require_confirmation = env::var("REQUIRE_DELETE_CONFIRMATION").is_ok_and(|v| v == "true");
+
+    match query.confirm {
+        Some(token) => {
+            let dependencies = get_dependencies(&pool, id).await?;
+            if token != dependencies.confirmation_token {
+                return Err((
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(ErrorCode::DependenciesChanged, 
+                        "Topic dependencies changed since the report was generated; fetch a new confirmation token".to_string(),
+                    )),
+                ));
+            }
+        }
+        None if require_confirmation => {
+            return Err((
+                StatusCode::PRECONDITION_REQUIRED,
+                Json(ApiResponse::error(ErrorCode::ConfirmationRequired, 
+                    "Deleting a topic requires ?confirm= with the token from GET /topics/{id}/dependencies".to_string(),
+                )),
+            ));
+        }
+        None => {}
+    }
+
+    // Deleting a topic deletes its questions, so a freeze blocks it too
+    ensure_not_frozen(&pool, &[id]).await?;
+
     let result = sqlx::query("DELETE FROM topics WHERE id = $1")
         .bind(id)
         .execute(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to delete topic: {}", e))),
-            )
-        })?;
+        .map_err(|e| errors::internal("Failed to delete topic", e))?;
 
     if result.rows_affected() == 0 {
         return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Topic not found".to_string())),
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
         ));
     }
 
+    catalog::invalidate();
+
     Ok(Json(ApiResponse::success(())))
 }
 
 pub async fn create_topic(
     State(pool): State<PgPool>,
-    Json(mut payload): Json<CreateTopic>,
+    Json(payload): Json<CreateTopic>,
 ) -> Result<Json<ApiResponse<Topic>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let slug_is_empty = match &payload.slug {
-        Some(s) => s.trim().is_empty(),
-        None => true,
-    };
-    if slug_is_empty {
-        payload.slug = Some(generate_slug(&payload.name));
-    }
+    let slug = resolve_slug(&payload);
 
-    if let Some(slug) = &mut payload.slug {
-        *slug = slug.trim().to_string();
+    let mut warnings = Vec::new();
+    if payload.slug.as_deref().is_none_or(|s| s.trim().is_empty()) {
+        warnings.push(slug_generated(&slug));
     }
 
     let topic = sqlx::query_as::<_, Topic>(
-        "INSERT INTO topics (name, slug, description) VALUES ($1, $2, $3) RETURNING *"
+        "INSERT INTO topics (name, slug, description, is_public, certification_id) VALUES ($1, $2, $3, $4, $5) RETURNING *"
     )
     .bind(payload.name)
-    .bind(payload.slug)
+    .bind(slug)
     .bind(payload.description)
+    .bind(payload.is_public)
+    .bind(payload.certification_id)
     .fetch_one(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to create topic: {}", e))),
-        )
+    .map_err(|e| match e.as_database_error() {
+        Some(db) if db.constraint() == Some("topics_name_key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateTopicName, "A topic with this name already exists".to_string())),
+        ),
+        Some(db) if db.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+        ),
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateSlug, "A topic with this slug already exists".to_string())),
+        ),
+        _ => errors::internal("Failed to create topic", e),
     })?;
 
-    Ok(Json(ApiResponse::success(topic)))
+    catalog::invalidate();
+
+    Ok(Json(ApiResponse::success(topic).with_warnings(warnings)))
+}
+
+/// Create many topics in one request. Each entry is created, reported as a duplicate
+/// of an existing slug, or rejected, independently of the others.
+pub async fn bulk_create_topics(
+    State(pool): State<PgPool>,
+    Json(payload): Json<BulkCreateTopics>,
+) -> Result<Json<ApiResponse<BulkCreateTopicsResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if payload.topics.is_empty() || payload.topics.len() > MAX_BULK_TOPICS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationFailed, format!("Send between 1 and {} topics", MAX_BULK_TOPICS))),
+        ));
+    }
+
+    // Slugs created or matched so far in this request
+    let mut seen: HashMap<String, Uuid> = HashMap::new();
+    let mut results = Vec::with_capacity(payload.topics.len());
+
+    for (index, topic) in payload.topics.into_iter().enumerate() {
+        let slug = resolve_slug(&topic);
+
+        let status = if topic.name.trim().is_empty() {
+            BulkTopicStatus::Rejected { error: "name must not be empty".to_string() }
+        } else if slug.is_empty() {
+            BulkTopicStatus::Rejected { error: format!("Could not generate a slug from '{}'", topic.name) }
+        } else if let Some(&existing_id) = seen.get(&slug.to_lowercase()) {
+            BulkTopicStatus::Duplicate { existing_id }
+        } else {
+            let status = insert_unless_exists(&pool, &topic, &slug).await?;
+            if let BulkTopicStatus::Created { topic: Topic { id, .. } } | BulkTopicStatus::Duplicate { existing_id: id } = &status {
+                seen.insert(slug.to_lowercase(), *id);
+            }
+            status
+        };
+
+        results.push(BulkTopicResult { index, slug, status });
+    }
+
+    let count = |f: fn(&BulkTopicStatus) -> bool| results.iter().filter(|r| f(&r.status)).count();
+    let created = count(|s| matches!(s, BulkTopicStatus::Created { .. }));
+    let duplicates = count(|s| matches!(s, BulkTopicStatus::Duplicate { .. }));
+    let rejected = count(|s| matches!(s, BulkTopicStatus::Rejected { .. }));
+
+    if created > 0 {
+        catalog::invalidate();
+    }
+
+    Ok(Json(ApiResponse::success(BulkCreateTopicsResponse {
+        created,
+        duplicates,
+        rejected,
+        results,
+    })))
 }
 
 pub async fn update_topic(
@@ -115,37 +279,57 @@ pub async fn update_topic(
     Path(id): Path<Uuid>,
     Json(mut payload): Json<UpdateTopic>,
 ) -> Result<Json<ApiResponse<Topic>>, (StatusCode, Json<ApiResponse<()>>)> {
-    if let (Some(name), Some(slug)) = (&payload.name, &payload.slug) {
-        if slug.trim().is_empty() {
-            payload.slug = Some(generate_slug(name));
-        }
+    let mut warnings = Vec::new();
+    if let (Some(name), Some(slug)) = (&payload.name, &payload.slug)
+        && slug.trim().is_empty()
+    {
+        let slug = generate_slug(name);
+        warnings.push(slug_generated(&slug));
+        payload.slug = Some(slug);
     }
 
     let topic = sqlx::query_as::<_, Topic>(
         "UPDATE topics SET 
             name = COALESCE($1, name), 
             slug = COALESCE($2, slug), 
-            description = COALESCE($3, description) 
-         WHERE id = $4 RETURNING *"
+            description = COALESCE($3, description),
+            is_public = COALESCE($4, is_public),
+            certification_id = CASE WHEN $5 THEN $6 ELSE certification_id END,
+            updated_at = NOW()
+         WHERE id = $7 RETURNING *"
     )
     .bind(payload.name)
     .bind(payload.slug)
     .bind(payload.description)
+    .bind(payload.is_public)
+    .bind(payload.certification_id.is_some())
+    .bind(payload.certification_id.flatten())
     .bind(id)
     .fetch_optional(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(format!("Failed to update topic: {}", e))),
-        )
+    .map_err(|e| match e.as_database_error() {
+        Some(db) if db.constraint() == Some("topics_name_key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateTopicName, "A topic with this name already exists".to_string())),
+        ),
+        Some(db) if db.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::CertificationNotFound, "Certification not found".to_string())),
+        ),
+        Some(db) if db.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(ErrorCode::DuplicateSlug, "A topic with this slug already exists".to_string())),
+        ),
+        _ => errors::internal("Failed to update topic", e),
     })?;
 
+    catalog::invalidate();
+
     match topic {
-        Some(topic) => Ok(Json(ApiResponse::success(topic))),
+        Some(topic) => Ok(Json(ApiResponse::success(topic).with_warnings(warnings))),
         None => Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Topic not found".to_string())),
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
         )),
     }
 }
@@ -154,45 +338,153 @@ pub async fn get_topic_by_slug(
     State(pool): State<PgPool>,
     Path(slug): Path<String>,
 ) -> Result<Json<ApiResponse<Topic>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE slug = $1")
+    let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE LOWER(slug) = LOWER($1)")
         .bind(slug)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Failed to fetch topic: {}", e))),
-            )
-        })?;
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
 
     match topic {
         Some(topic) => Ok(Json(ApiResponse::success(topic))),
         None => Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("Topic not found".to_string())),
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
         )),
     }
 }
 
 
 // Helper function
+/// Topics with question counts by name, optionally only those changed after `since`.
+/// A `limit` of `None` returns every row.
+async fn fetch_topics(
+    executor: impl PgExecutor<'_>,
+    since: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<TopicWithCounts>, sqlx::Error> {
+    sqlx::query_as::<_, TopicWithCounts>(
+        "SELECT t.*,
+            COUNT(q.id) AS question_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'easy') AS easy_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'medium') AS medium_count,
+            COUNT(q.id) FILTER (WHERE q.difficulty = 'hard') AS hard_count
+         FROM topics t
+         LEFT JOIN questions q ON q.topic_id = t.id
+         WHERE ($1::timestamptz IS NULL OR t.updated_at > $1)
+         GROUP BY t.id
+         ORDER BY t.name
+         LIMIT $2 OFFSET $3"
+    )
+    .bind(since)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(executor)
+    .await
+}
+
+/// Warning that a slug was generated because none was given
+pub fn slug_generated(slug: &str) -> ApiWarning {
+    ApiWarning::new("SLUG_GENERATED", "slug", format!("No slug given; generated '{}' from the name", slug))
+}
+
+/// The requested slug, or one generated from the name when none was given
+fn resolve_slug(topic: &CreateTopic) -> String {
+    match topic.slug.as_deref().map(str::trim) {
+        Some(slug) if !slug.is_empty() => slug.to_string(),
+        _ => generate_slug(&topic.name),
+    }
+}
+
+/// Insert the topic unless its slug is taken, ignoring case
+async fn insert_unless_exists(
+    pool: &PgPool,
+    topic: &CreateTopic,
+    slug: &str,
+) -> Result<BulkTopicStatus, (StatusCode, Json<ApiResponse<()>>)> {
+    let created = sqlx::query_as::<_, Topic>(
+        "INSERT INTO topics (name, slug, description, is_public, certification_id)
+         SELECT $1, $2, $3, $4, $5
+         WHERE NOT EXISTS (SELECT 1 FROM topics WHERE LOWER(slug) = LOWER($2))
+         ON CONFLICT (slug) DO NOTHING
+         RETURNING *"
+    )
+    .bind(topic.name.trim())
+    .bind(slug)
+    .bind(&topic.description)
+    .bind(topic.is_public)
+    .bind(topic.certification_id)
+    .fetch_optional(pool)
+    .await;
+
+    let created = match created {
+        Ok(created) => created,
+        Err(e) if e.as_database_error().is_some_and(|db| db.is_foreign_key_violation()) => {
+            return Ok(BulkTopicStatus::Rejected { error: "Certification not found".to_string() });
+        }
+        Err(e) => return Err(errors::internal("Failed to create topic", e)),
+    };
+
+    if let Some(topic) = created {
+        return Ok(BulkTopicStatus::Created { topic });
+    }
+
+    let existing_id: Uuid = sqlx::query_scalar("SELECT id FROM topics WHERE LOWER(slug) = LOWER($1)")
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| errors::internal("Failed to fetch topic", e))?;
+
+    Ok(BulkTopicStatus::Duplicate { existing_id })
+}
+
+/// Count everything a topic delete would cascade to
+async fn get_dependencies(pool: &PgPool, id: Uuid) -> Result<TopicDependencies, (StatusCode, Json<ApiResponse<()>>)> {
+    let dependencies = sqlx::query_as::<_, TopicDependencies>(
+        "SELECT
+            t.id AS topic_id,
+            t.updated_at AS topic_updated_at,
+            (SELECT COUNT(*) FROM questions WHERE topic_id = t.id) AS question_count,
+            (SELECT COUNT(*) FROM quiz_sessions WHERE topic_id = t.id) AS session_count,
+            (SELECT COUNT(*) FROM quiz_sessions WHERE topic_id = t.id AND status = 'submitted') AS submitted_session_count,
+            (SELECT COUNT(*) FROM quiz_session_answers a
+                JOIN quiz_sessions s ON a.session_id = s.id
+                WHERE s.topic_id = t.id) AS answer_count,
+            (SELECT COUNT(*) FROM regrade_jobs r
+                JOIN questions q ON r.question_id = q.id
+                WHERE q.topic_id = t.id) AS regrade_job_count,
+            (SELECT COUNT(*) FROM content_freezes WHERE topic_id = t.id) AS freeze_count
+         FROM topics t WHERE t.id = $1"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| errors::internal("Failed to fetch topic dependencies", e))?;
+
+    let Some(mut dependencies) = dependencies else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, "Topic not found".to_string())),
+        ));
+    };
+
+    dependencies.confirmation_token = dependencies.token();
+
+    Ok(dependencies)
+}
+
 pub async fn get_topic_id_by_slug(pool: &PgPool, slug: &str) -> Result<Uuid, (StatusCode, Json<ApiResponse<()>>)> {
-    let topic: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM topics WHERE slug = $1")
+    let topic: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM topics WHERE LOWER(slug) = LOWER($1)")
         .bind(slug)
         .fetch_optional(pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(format!("Database error: {}", e))),
-            )
-        })?;
+        .map_err(|e| errors::internal("Failed to look up topic", e))?;
 
     match topic {
         Some((id,)) => Ok(id),
         None => Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error(format!("Topic with slug '{}' not found", slug))),
+            Json(ApiResponse::error(ErrorCode::TopicNotFound, format!("Topic with slug '{}' not found", slug))),
         )),
     }
 }
\ No newline at end of file
@@ -0,0 +1,97 @@
+//! Outgoing HTTP for the background workers (content sync, grade passback, webhooks and
+//! diagram rendering). `https://` is verified against the Mozilla root certificates and
+//! every request has a deadline, so one unresponsive endpoint cannot stall a worker.
+
+use anyhow::{anyhow, bail};
+use axum::{body::Bytes, http::{Request, StatusCode, Uri}};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use std::env;
+use std::time::Duration;
+
+/// Largest response body read by default
+const MAX_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+    timeout: Duration,
+}
+
+/// A response read in full
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+impl HttpClient {
+    /// A client whose requests time out after `HTTP_TIMEOUT_SECS` (default 30), from
+    /// connecting to the last byte of the response
+    pub fn new() -> Self {
+        let timeout = env::var("HTTP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(30);
+
+        Self::with_timeout(Duration::from_secs(timeout))
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        let connector = HttpsConnectorBuilder::new()
+            .with_webpki_roots()
+            .https_or_http()
+            .enable_http1()
+            .build();
+
+        HttpClient {
+            client: Client::builder(TokioExecutor::new()).build(connector),
+            timeout,
+        }
+    }
+
+    pub async fn send(&self, request: Request<Full<Bytes>>) -> anyhow::Result<HttpResponse> {
+        self.send_limited(request, MAX_RESPONSE_BYTES).await
+    }
+
+    /// Send the request and read the response, failing on bodies over `max_body_bytes`
+    pub async fn send_limited(&self, request: Request<Full<Bytes>>, max_body_bytes: usize) -> anyhow::Result<HttpResponse> {
+        let uri = request.uri().clone();
+
+        let exchange = async {
+            let response = self.client.request(request).await?;
+            let status = response.status();
+            let body = Limited::new(response.into_body(), max_body_bytes)
+                .collect()
+                .await
+                .map_err(|e| anyhow!("Failed to read the response (at most {} bytes): {}", max_body_bytes, e))?
+                .to_bytes();
+
+            Ok(HttpResponse { status, body })
+        };
+
+        match tokio::time::timeout(self.timeout, exchange).await {
+            Ok(result) => result,
+            Err(_) => bail!("{} did not respond within {:?}", uri, self.timeout),
+        }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse an endpoint URL the client can reach: `http://` or `https://` with a host
+pub fn parse_endpoint(url: &str) -> anyhow::Result<Uri> {
+    let uri: Uri = url.parse()?;
+
+    match uri.scheme_str() {
+        Some("http" | "https") if uri.host().is_some() => Ok(uri),
+        _ => bail!("Only http:// and https:// URLs are supported, got {}", url),
+    }
+}
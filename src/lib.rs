@@ -0,0 +1,21 @@
+//! Quiz question bank API. The server binary in `main.rs` wires these modules into
+//! the router and background workers; benches and tests use them directly.
+
+pub mod analytics;
+pub mod catalog;
+pub mod database;
+pub mod diagram;
+pub mod encoding;
+pub mod errors;
+pub mod handlers;
+pub mod http_client;
+pub mod middleware;
+pub mod models;
+pub mod outbox;
+pub mod passback;
+pub mod rebuild;
+pub mod scraping;
+pub mod sync;
+pub mod telemetry;
+pub mod warmup;
+pub mod watermark;
@@ -1,25 +1,82 @@
 
-mod database;
-mod handlers;
-mod models;
-
 use axum::{
-    routing::{get, post, put, delete},
-    Router,
+    extract::Request,
+    routing::{get, options, post, put, delete},
+    Router, ServiceExt,
 };
+use tower::Layer;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::trace::TraceLayer;
+
+use beep_rust::{analytics, database, diagram, handlers, middleware, outbox, passback, sync, telemetry, warmup};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize tracing
-    tracing_subscriber::fmt::init();
+    telemetry::init();
 
     // Initialize database connection
     let pool = database::connect().await?;
 
+    // Check the schema and fill caches before taking traffic
+    warmup::run(&pool).await?;
+
+    // Start pulling content from an upstream instance, if configured
+    sync::spawn(pool.clone());
+
+    // Keep the analytics views fresh
+    analytics::spawn(pool.clone());
+
+    // Send submitted scores back to LMS endpoints
+    passback::spawn(pool.clone());
+
+    // Deliver content change events from the outbox
+    outbox::spawn(pool.clone());
+
+    // Render diagram code blocks to SVG, if a renderer is configured
+    diagram::spawn(pool.clone());
+
     // Define all app routes
     let api_routes = Router::new()
+        .route("/", options(handlers::meta::get_capabilities))
         .route("/health", get(health_check))
+        .route(
+            "/providers",
+            get(handlers::provider::get_providers).post(handlers::provider::create_provider),
+        )
+        .route(
+            "/providers/{id}",
+            get(handlers::provider::get_provider)
+                .put(handlers::provider::update_provider)
+                .delete(handlers::provider::delete_provider),
+        )
+        .route("/providers/{id}/certifications", get(handlers::provider::get_provider_certifications))
+        .route(
+            "/certifications",
+            get(handlers::certification::get_certifications).post(handlers::certification::create_certification),
+        )
+        .route(
+            "/certifications/{id}",
+            get(handlers::certification::get_certification)
+                .put(handlers::certification::update_certification)
+                .delete(handlers::certification::delete_certification),
+        )
+        .route("/certifications/{id}/topics", get(handlers::certification::get_certification_topics))
+        .route("/certifications/{id}/dependencies", get(handlers::certification::get_certification_dependencies))
+        .route("/certifications/{id}/questions", get(handlers::certification::get_certification_questions))
+        .route("/certifications/{id}/questions/order", put(handlers::certification::set_certification_order))
+        .route(
+            "/certifications/{id}/objectives",
+            get(handlers::objective::get_objectives).post(handlers::objective::create_objective),
+        )
+        .route("/certifications/{id}/objectives/coverage", get(handlers::objective::get_objective_coverage))
+        .route(
+            "/objectives/{id}",
+            get(handlers::objective::get_objective)
+                .put(handlers::objective::update_objective)
+                .delete(handlers::objective::delete_objective),
+        )
+        .route("/certifications/{id}/bundle", get(handlers::sync::get_certification_bundle))
         .route(
             "/topics",
             get(handlers::topic::get_topics).post(handlers::topic::create_topic),
@@ -30,18 +87,46 @@ async fn main() -> anyhow::Result<()> {
                 .put(handlers::topic::update_topic)
                 .delete(handlers::topic::delete_topic),
         )
+        .route("/topics/bulk", post(handlers::topic::bulk_create_topics))
         .route("/topics/slug/{slug}", get(handlers::topic::get_topic_by_slug))
+        .route("/topics/{id}/full", get(handlers::topic::get_topic_full))
+        .route("/topics/{id}/dependencies", get(handlers::topic::get_topic_dependencies))
+        .route("/topics/{id}/question-graph", get(handlers::prerequisite::get_question_graph))
+        // Keyed by slug; the segment shares the `{id}` name with the other topic routes
+        .route("/topics/{id}/feed.atom", get(handlers::catalog::get_topic_feed))
+        .route("/topics/{id}/bundle", get(handlers::sync::get_topic_bundle))
+        .route("/topics/{id}/questions/export", get(handlers::import_export::export_questions))
         .route(
             "/questions",
             get(handlers::question::get_questions).post(handlers::question::create_question),
         )
         .route("/questions/bulk", post(handlers::question::bulk_create_questions))
+        .route("/questions/import", post(handlers::import_export::import_questions))
         .route(
             "/questions/{id}",
             get(handlers::question::get_question)
                 .put(handlers::question::update_question)
                 .delete(handlers::question::delete_question),
         )
+        .route("/questions/{id}/variant", get(handlers::question::get_question_variant))
+        .route("/questions/{id}/timeline", get(handlers::question::get_question_timeline))
+        .route("/questions/{id}/prerequisites", put(handlers::prerequisite::set_prerequisites))
+        .route(
+            "/questions/{id}/objectives",
+            get(handlers::objective::get_question_objectives).put(handlers::objective::set_question_objectives),
+        )
+        .route("/diagrams/{hash}/image.svg", get(handlers::diagram::get_diagram_svg))
+        .route(
+            "/questions/{id}/explanation/vote",
+            post(handlers::explanation::vote_explanation)
+                .delete(handlers::explanation::retract_explanation_vote),
+        )
+        .route(
+            "/questions/{id}/explanations",
+            get(handlers::explanation::get_explanations).post(handlers::explanation::create_explanation),
+        )
+        .route("/explanations/{id}/vote", post(handlers::explanation::vote_explanation_entry))
+        .route("/custom-fields", get(handlers::custom_field::get_custom_fields))
         .route(
             "/questions/topic/{topic_id}",
             get(handlers::question::get_questions_by_topic),
@@ -51,23 +136,154 @@ async fn main() -> anyhow::Result<()> {
             get(handlers::question::get_questions_by_type),
         )
         .route("/questions/search/{query}", get(handlers::question::search_questions))
-        .with_state(pool);
+        .route(
+            "/import/templates",
+            get(handlers::import::get_templates).post(handlers::import::save_template),
+        )
+        .route(
+            "/import/templates/{name}",
+            get(handlers::import::get_template).delete(handlers::import::delete_template),
+        )
+        .route("/import/csv", post(handlers::import::import_csv))
+        .route("/import/yaml", post(handlers::import::import_yaml))
+        .route("/imports", get(handlers::import::get_imports))
+        .route("/imports/{id}/errors.csv", get(handlers::import::get_import_errors))
+        .route("/catalog", get(handlers::catalog::get_catalog))
+        .route("/share", post(handlers::share::create_share_link))
+        .route("/share/{code}", get(handlers::share::get_share_link))
+        .route("/share/{code}/qr.png", get(handlers::share::get_share_qr))
+        .route("/embed/{token}/config", get(handlers::embed::get_embed_config))
+        .route(
+            "/admin/embeds",
+            get(handlers::embed::get_embed_tokens).post(handlers::embed::create_embed_token),
+        )
+        .route("/admin/embeds/{id}", delete(handlers::embed::delete_embed_token))
+        .route("/feed.json", get(handlers::catalog::get_feed))
+        .route("/public/topics", get(handlers::public::get_public_topics))
+        .route("/public/topics/{slug}/questions", get(handlers::public::get_public_questions))
+        .route("/search", get(handlers::search::search))
+        .route("/search/suggest", get(handlers::search::suggest))
+        .route("/quizzes/generate", get(handlers::quiz::generate_quiz))
+        .route("/quiz/sessions", post(handlers::quiz::create_session))
+        .route("/quiz/sessions/{id}", get(handlers::quiz::get_session))
+        .route(
+            "/quiz/sessions/{id}/answers/{question_id}",
+            put(handlers::quiz::save_answer),
+        )
+        .route(
+            "/quiz/sessions/{id}/questions/{question_id}/mark",
+            post(handlers::quiz::mark_question).delete(handlers::quiz::unmark_question),
+        )
+        .route(
+            "/quiz/sessions/{id}/questions/{question_id}/hint",
+            post(handlers::quiz::reveal_hint),
+        )
+        .route("/quiz/sessions/{id}/answers/batch", post(handlers::quiz::save_answers_batch))
+        .route("/quiz/sessions/{id}/pause", post(handlers::quiz::pause_session))
+        .route("/quiz/sessions/{id}/resume", post(handlers::quiz::resume_session))
+        .route("/quiz/sessions/{id}/submit", post(handlers::quiz::submit_session))
+        .route(
+            "/admin/questions/{id}/regrade",
+            post(handlers::admin::regrade_question),
+        )
+        .route("/admin/regrade-jobs/{id}", get(handlers::admin::get_regrade_job))
+        .route("/admin/rebuild", post(handlers::admin::rebuild_derived))
+        .route("/admin/rebuild-jobs/{id}", get(handlers::admin::get_rebuild_job))
+        .route("/sync/results", post(handlers::sync::sync_results))
+        .route("/changes/deletions", get(handlers::sync::get_deletions))
+        .route("/analytics/topics", get(handlers::analytics::get_topic_accuracy))
+        .route("/analytics/questions", get(handlers::analytics::get_question_stats))
+        .route("/analytics/calibration", get(handlers::analytics::get_calibration))
+        .route("/analytics/cognitive-levels", get(handlers::analytics::get_cognitive_level_accuracy))
+        .route("/admin/analytics/refresh", post(handlers::analytics::refresh_analytics))
+        .route(
+            "/admin/freezes",
+            get(handlers::freeze::get_freezes).post(handlers::freeze::create_freeze),
+        )
+        .route("/admin/freezes/{id}", delete(handlers::freeze::delete_freeze))
+        .route(
+            "/admin/explanations/{id}",
+            put(handlers::explanation::review_explanation).delete(handlers::explanation::delete_explanation),
+        )
+        .route("/admin/reports/explanations", get(handlers::explanation::get_explanation_report))
+        .route(
+            "/admin/passback/targets",
+            get(handlers::passback::get_passback_targets).post(handlers::passback::create_passback_target),
+        )
+        .route("/admin/passback/targets/{id}", delete(handlers::passback::delete_passback_target))
+        .route("/admin/passback/deliveries", get(handlers::passback::get_passback_deliveries))
+        .route(
+            "/admin/api-keys",
+            get(handlers::public::get_api_keys).post(handlers::public::create_api_key),
+        )
+        .route(
+            "/admin/api-keys/{id}",
+            put(handlers::public::update_api_key).delete(handlers::public::delete_api_key),
+        )
+        .route("/admin/api-keys/{id}/block", delete(handlers::public::unblock_api_key))
+        .route("/admin/alerts", get(handlers::public::get_scraping_alerts))
+        .route("/admin/access-log", get(handlers::public::get_access_log))
+        .route("/admin/watermarks/trace", post(handlers::public::trace_watermark))
+        .route("/admin/custom-fields", post(handlers::custom_field::create_custom_field))
+        .route("/admin/custom-fields/{key}", delete(handlers::custom_field::delete_custom_field))
+        .route("/admin/events", get(handlers::outbox::get_events))
+        .route("/admin/events/stream", get(handlers::outbox::stream_events))
+        .route(
+            "/admin/webhooks",
+            get(handlers::outbox::get_webhooks).post(handlers::outbox::create_webhook),
+        )
+        .route("/admin/webhooks/{id}", delete(handlers::outbox::delete_webhook))
+        .route("/integrations/triggers/{event}", get(handlers::outbox::get_trigger_events))
+        .route("/admin/slow-queries", get(handlers::admin::get_slow_queries))
+        .route("/admin/consistency/timestamps", get(handlers::admin::check_timestamps))
+        .route("/admin/accessibility", get(handlers::admin::get_accessibility_report))
+        .route("/admin/manifest", get(handlers::admin::get_manifest))
+        .route("/admin/diff", post(handlers::admin::diff_manifest))
+        .route("/admin/apply", post(handlers::admin::apply_manifest))
+        .route("/admin/export/{slug}", get(handlers::admin::export_topic))
+        .with_state(pool.clone());
 
     // Wrap with /api prefix
     let app = Router::new()
+        .route("/sitemap.xml", get(handlers::catalog::get_sitemap))
+        .route("/embed/quiz/{token}", get(handlers::embed::get_embed_widget))
+        .route("/s/{code}", get(handlers::share::follow_share_link))
+        .with_state(pool)
         .nest("/api", api_routes)
+        .layer(axum::middleware::from_fn(middleware::wrap_plain_errors))
+        .layer(axum::middleware::from_fn(middleware::negotiate_content))
+        .layer(axum::middleware::from_fn(middleware::conditional_get))
+        .layer(TraceLayer::new_for_http().make_span_with(|req: &Request| {
+            let request_id = req
+                .extensions()
+                .get::<middleware::RequestId>()
+                .map(|id| id.0.as_str())
+                .unwrap_or_default();
+            tracing::info_span!(
+                "request",
+                method = %req.method(),
+                uri = %req.uri(),
+                version = ?req.version(),
+                request_id,
+            )
+        }))
+        .layer(axum::middleware::from_fn(middleware::assign_request_id))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
-                .allow_headers(Any),
+                .allow_headers(Any)
+                .expose_headers([middleware::X_REQUEST_ID.clone()]),
         );
 
+    // Normalize paths before they reach the router
+    let app = axum::middleware::map_request(middleware::normalize_path).layer(app);
+
     // Start server
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
     tracing::info!("Server listening on {}", listener.local_addr()?);
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await?;
 
     Ok(())
 }
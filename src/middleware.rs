@@ -0,0 +1,320 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::encoding;
+use crate::errors;
+use crate::handlers::meta::ROUTES;
+use crate::models::{ApiResponse, ErrorCode};
+
+pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request being handled, stored as a request extension for the trace span
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// The current request's ID, if called while handling one
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Give every request an ID: a caller-supplied `X-Request-Id` is kept when it is short
+/// and printable, otherwise a new UUID is used. The ID is echoed on the response and is
+/// available to handlers through `current_request_id`.
+pub async fn assign_request_id(mut req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty() && v.len() <= 128 && v.bytes().all(|b| b.is_ascii_graphic()))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(id.clone()));
+    let mut response = REQUEST_ID.scope(id.clone(), next.run(req)).await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(X_REQUEST_ID.clone(), value);
+    }
+
+    response
+}
+
+/// Normalize API paths before routing so `/api/Topics/` resolves like `/api/topics`:
+/// trailing slashes are trimmed and the fixed segments of the matching route are
+/// lowercased. Path parameters (slugs, codes, names) are left as the client sent them.
+/// Only paths under `/api` are touched; query strings are left as they are.
+pub async fn normalize_path(mut req: Request) -> Request {
+    let path = req.uri().path();
+    let Some(normalized) = normalize_api_path(path) else {
+        return req;
+    };
+
+    if normalized == path {
+        return req;
+    }
+
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{}?{}", normalized, query),
+        None => normalized,
+    };
+
+    let mut parts = req.uri().clone().into_parts();
+    if let Ok(path_and_query) = path_and_query.parse() {
+        parts.path_and_query = Some(path_and_query);
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+    }
+
+    req
+}
+
+/// The `/api` path with its route's fixed segments in their canonical case, or `None`
+/// outside `/api`. When several routes match ignoring case, the one with fixed segments
+/// earliest wins, as in the router. Paths matching no route keep their case.
+fn normalize_api_path(path: &str) -> Option<String> {
+    let trimmed = path.trim_end_matches('/');
+    let mut segments = trimmed.split('/').skip(1);
+
+    if !segments.next().is_some_and(|s| s.eq_ignore_ascii_case("api")) {
+        return None;
+    }
+    let segments: Vec<&str> = segments.collect();
+
+    let route = ROUTES
+        .iter()
+        .map(|(route, _)| route.split('/').filter(|s| !s.is_empty()).collect::<Vec<&str>>())
+        .filter(|route| {
+            route.len() == segments.len()
+                && route.iter().zip(&segments).all(|(r, s)| is_parameter(r) || r.eq_ignore_ascii_case(s))
+        })
+        .max_by_key(|route| route.iter().map(|r| !is_parameter(r)).collect::<Vec<bool>>());
+
+    let mut normalized = String::from("/api");
+    for (index, segment) in segments.iter().enumerate() {
+        normalized.push('/');
+        match route.as_ref().map(|route| route[index]) {
+            Some(fixed) if !is_parameter(fixed) => normalized.push_str(fixed),
+            _ => normalized.push_str(segment),
+        }
+    }
+
+    Some(normalized)
+}
+
+fn is_parameter(segment: &str) -> bool {
+    segment.starts_with('{')
+}
+
+/// Turn the plain-text client errors produced before a handler runs (unknown routes,
+/// wrong methods, extractor rejections such as malformed JSON) into the usual JSON
+/// envelope with an error code. Only API paths are touched.
+pub async fn wrap_plain_errors(req: Request, next: Next) -> Response {
+    let is_api = req.uri().path() == "/api" || req.uri().path().starts_with("/api/");
+    let response = next.run(req).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if !is_api || !response.status().is_client_error() || is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let text = match to_bytes(body, 64 * 1024).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).trim().to_string(),
+        Err(_) => String::new(),
+    };
+
+    let code = match parts.status {
+        StatusCode::NOT_FOUND => ErrorCode::RouteNotFound,
+        StatusCode::METHOD_NOT_ALLOWED => ErrorCode::MethodNotAllowed,
+        StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+        _ => ErrorCode::ValidationFailed,
+    };
+
+    let message = if text.is_empty() {
+        parts.status.canonical_reason().unwrap_or("Request failed").to_string()
+    } else {
+        text
+    };
+
+    let body = match serde_json::to_vec(&ApiResponse::error(code, message)) {
+        Ok(body) => body,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Give successful GET responses an ETag when the handler did not set one, answer a
+/// matching `If-None-Match` with `304`, and serve HEAD as GET without the body so its
+/// headers (ETag, Content-Length) match what GET would send.
+pub async fn conditional_get(mut req: Request, next: Next) -> Response {
+    let is_head = req.method() == Method::HEAD;
+    if !is_head && req.method() != Method::GET {
+        return next.run(req).await;
+    }
+
+    // The router would drop the body of a HEAD response before it could be hashed
+    if is_head {
+        *req.method_mut() = Method::GET;
+    }
+
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+    let response = next.run(req).await;
+    let (mut parts, body) = response.into_parts();
+
+    // Streams never end, so they cannot be hashed
+    let is_stream = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"text/event-stream"));
+
+    if parts.status != StatusCode::OK || is_stream {
+        return Response::from_parts(parts, if is_head { Body::empty() } else { body });
+    }
+
+    let body = match parts.headers.get(header::ETAG) {
+        Some(_) => body,
+        None => {
+            let bytes = match to_bytes(body, usize::MAX).await {
+                Ok(bytes) => bytes,
+                Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            };
+
+            let etag = format!("\"{}\"", &hex::encode(Sha256::digest(&bytes))[..32]);
+            if let Ok(value) = HeaderValue::from_str(&etag) {
+                parts.headers.insert(header::ETAG, value);
+            }
+            parts.headers.insert(header::CONTENT_LENGTH, HeaderValue::from(bytes.len()));
+
+            Body::from(bytes)
+        }
+    };
+
+    let not_modified = match (&if_none_match, parts.headers.get(header::ETAG)) {
+        (Some(if_none_match), Some(etag)) => etag_matches(if_none_match, etag.to_str().unwrap_or_default()),
+        _ => false,
+    };
+
+    if not_modified {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        parts.headers.remove(header::CONTENT_TYPE);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, if is_head { Body::empty() } else { body })
+}
+
+/// Weak comparison of an `If-None-Match` header against an ETag, as the header requires
+pub fn etag_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+
+    if_none_match.to_str().is_ok_and(|v| {
+        v.split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == "*" || tag == etag)
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum ResponseFormat {
+    Json,
+    Csv,
+    MessagePack,
+}
+
+/// Re-encode JSON responses according to the `Accept` header. `text/csv` flattens list
+/// payloads into rows (other payloads stay JSON) and `application/msgpack` encodes the
+/// whole envelope. JSON remains the default.
+pub async fn negotiate_content(req: Request, next: Next) -> Response {
+    let format = preferred_format(req.headers());
+    let mut response = next.run(req).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    response.headers_mut().append(header::VARY, HeaderValue::from_static("accept"));
+    if format == ResponseFormat::Json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let Ok(value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let (content_type, encoded) = match format {
+        ResponseFormat::Csv => match list_rows(&value) {
+            Some(rows) => ("text/csv; charset=utf-8", encoding::to_csv(rows).map(String::into_bytes).map_err(|e| e.to_string())),
+            None => return Response::from_parts(parts, Body::from(bytes)),
+        },
+        ResponseFormat::MessagePack => ("application/msgpack", encoding::to_msgpack(&value).map_err(|e| e.to_string())),
+        ResponseFormat::Json => unreachable!(),
+    };
+
+    let encoded = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => return errors::internal("Failed to encode response", e).into_response(),
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+
+    Response::from_parts(parts, Body::from(encoded))
+}
+
+/// First supported media type listed in `Accept`
+fn preferred_format(headers: &HeaderMap) -> ResponseFormat {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    for media_type in accept.split(',') {
+        let media_type = media_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match media_type.as_str() {
+            "text/csv" => return ResponseFormat::Csv,
+            "application/msgpack" | "application/x-msgpack" => return ResponseFormat::MessagePack,
+            "application/json" | "*/*" => return ResponseFormat::Json,
+            _ => {}
+        }
+    }
+
+    ResponseFormat::Json
+}
+
+/// Rows of a list response: `data` itself, or `data.items` for paginated responses
+fn list_rows(envelope: &Value) -> Option<&Vec<Value>> {
+    let data = envelope.get("data")?;
+    data.as_array().or_else(|| data.get("items")?.as_array())
+}
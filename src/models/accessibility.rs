@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use regex::Regex;
+use uuid::Uuid;
+
+use super::question::Question;
+
+/// Markdown image: `![alt](url)`
+static MARKDOWN_IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\(([^)\s]*)[^)]*\)").unwrap());
+
+static HTML_IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<img\b[^>]*>").unwrap());
+
+static HTML_ALT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?i)\balt\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+static HTML_SRC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?i)\bsrc\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+const COLORS: [&str; 16] = [
+    "red", "green", "blue", "yellow", "orange", "purple", "pink", "brown",
+    "black", "white", "gray", "grey", "violet", "cyan", "magenta", "teal",
+];
+
+// Words that may surround a color without adding meaning, as in "the red one"
+const FILLER: [&str; 8] = ["the", "a", "one", "option", "button", "box", "line", "icon"];
+
+// === Accessibility Models ===
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityIssueKind {
+    // An inline image without a text alternative
+    MissingAltText,
+    // Options that can only be told apart by color
+    ColorOnlyOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessibilityIssue {
+    pub kind: AccessibilityIssueKind,
+    // Question field the issue is in, e.g. `options` or `hints`
+    pub field: &'static str,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessibilityReport {
+    pub question_id: Uuid,
+    pub topic_id: Uuid,
+    pub question_number: i32,
+    pub issues: Vec<AccessibilityIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessibilityQuery {
+    pub topic_id: Option<Uuid>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Sources of the inline images (Markdown or HTML) in `text` that have no alt text
+pub fn images_without_alt(text: &str) -> Vec<String> {
+    let markdown = MARKDOWN_IMAGE
+        .captures_iter(text)
+        .filter(|c| c[1].trim().is_empty())
+        .map(|c| c[2].to_string());
+
+    let html = HTML_IMAGE
+        .find_iter(text)
+        .filter(|tag| {
+            HTML_ALT
+                .captures(tag.as_str())
+                .and_then(|c| c.get(1).or(c.get(2)))
+                .is_none_or(|alt| alt.as_str().trim().is_empty())
+        })
+        .map(|tag| {
+            HTML_SRC
+                .captures(tag.as_str())
+                .and_then(|c| c.get(1).or(c.get(2)))
+                .map_or_else(|| tag.as_str().to_string(), |src| src.as_str().to_string())
+        });
+
+    markdown.chain(html).collect()
+}
+
+/// Reject text with inline images that lack alt text
+pub fn validate_alt_text(text: &str) -> Result<(), String> {
+    match images_without_alt(text).first() {
+        Some(src) => Err(format!("Image '{}' needs alt text describing it", src)),
+        None => Ok(()),
+    }
+}
+
+/// Whether every option is a bare color, like "Red" or "the blue one", so that a
+/// learner who cannot tell the colors apart cannot tell the options apart either
+pub fn color_only_options(options: &[String]) -> bool {
+    options.len() >= 2
+        && options.iter().all(|option| {
+            let words: Vec<String> = option
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(str::to_lowercase)
+                .filter(|w| !FILLER.contains(&w.as_str()))
+                .collect();
+
+            words.len() == 1 && COLORS.contains(&words[0].as_str())
+        })
+}
+
+impl Question {
+    /// Accessibility problems in the question's content
+    pub fn accessibility_issues(&self) -> Vec<AccessibilityIssue> {
+        let mut texts: Vec<(&'static str, &str)> = vec![("question", &self.question), ("explanation", &self.explanation)];
+        texts.extend(self.options.0.iter().map(|o| ("options", o.as_str())));
+        texts.extend(self.hints.iter().flat_map(|h| &h.0).map(|h| ("hints", h.as_str())));
+        texts.extend(self.option_explanations.iter().flat_map(|e| e.0.values()).map(|e| ("option_explanations", e.as_str())));
+
+        let mut issues: Vec<AccessibilityIssue> = texts
+            .into_iter()
+            .flat_map(|(field, text)| {
+                images_without_alt(text).into_iter().map(move |src| AccessibilityIssue {
+                    kind: AccessibilityIssueKind::MissingAltText,
+                    field,
+                    detail: format!("Image '{}' has no alt text", src),
+                })
+            })
+            .collect();
+
+        if color_only_options(&self.options.0) {
+            issues.push(AccessibilityIssue {
+                kind: AccessibilityIssueKind::ColorOnlyOptions,
+                field: "options",
+                detail: "Options differ only by color; name what each color stands for".to_string(),
+            });
+        }
+
+        issues
+    }
+}
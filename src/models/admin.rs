@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[sqlx(type_name = "job_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+// === Regrade Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RegradeJob {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub status: JobStatus,
+    pub sessions_checked: i32,
+    pub sessions_adjusted: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ScoreAdjustment {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub session_id: Uuid,
+    pub question_id: Uuid,
+    pub previous_score: Option<i32>,
+    pub new_score: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegradeJobResponse {
+    pub job: RegradeJob,
+    pub adjustments: Vec<ScoreAdjustment>,
+}
+
+// === Rebuild Models ===
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[sqlx(type_name = "rebuild_target", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildTarget {
+    // Full-text and trigram indexes and the search suggestion terms
+    SearchIndex,
+    // Analytics views
+    Stats,
+    // Explanation vote tallies and question reading stats
+    Counts,
+}
+
+impl RebuildTarget {
+    pub const ALL: [RebuildTarget; 3] = [RebuildTarget::SearchIndex, RebuildTarget::Stats, RebuildTarget::Counts];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebuildTarget::SearchIndex => "search_index",
+            RebuildTarget::Stats => "stats",
+            RebuildTarget::Counts => "counts",
+        }
+    }
+}
+
+// Items are steps for search_index and stats, and questions for counts
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RebuildJob {
+    pub id: Uuid,
+    pub target: RebuildTarget,
+    pub status: JobStatus,
+    // Unknown until the job has started
+    pub items_total: Option<i32>,
+    pub items_done: i32,
+    // Stored values that were wrong and have been corrected
+    pub items_fixed: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RebuildQuery {
+    // Comma-separated, e.g. `search_index,stats`; defaults to every target
+    pub targets: Option<String>,
+}
+
+/// Targets that are accepted but have nothing to rebuild. `embeddings` is kept for clients
+/// written against the original spec: no question embeddings are stored, since search is
+/// PostgreSQL full-text and trigram matching.
+pub const NO_OP_REBUILD_TARGETS: [&str; 1] = ["embeddings"];
+
+/// Targets named in a comma-separated list, in the order given and each once, and the
+/// no-op targets named alongside them
+pub fn parse_rebuild_targets(targets: Option<&str>) -> Result<(Vec<RebuildTarget>, Vec<&'static str>), String> {
+    let Some(targets) = targets else {
+        return Ok((RebuildTarget::ALL.to_vec(), Vec::new()));
+    };
+
+    let mut parsed = Vec::new();
+    let mut skipped = Vec::new();
+    for name in targets.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if let Some(no_op) = NO_OP_REBUILD_TARGETS.into_iter().find(|&t| t == name) {
+            if !skipped.contains(&no_op) {
+                skipped.push(no_op);
+            }
+            continue;
+        }
+
+        let Some(target) = RebuildTarget::ALL.into_iter().find(|t| t.as_str() == name) else {
+            let known: Vec<&str> = RebuildTarget::ALL.iter().map(RebuildTarget::as_str).chain(NO_OP_REBUILD_TARGETS).collect();
+            return Err(format!("Unknown rebuild target '{}'; expected one of {}", name, known.join(", ")));
+        };
+        if !parsed.contains(&target) {
+            parsed.push(target);
+        }
+    }
+
+    if parsed.is_empty() && skipped.is_empty() {
+        return Err("targets must name at least one rebuild target".to_string());
+    }
+
+    Ok((parsed, skipped))
+}
+
+// === Consistency Check Models ===
+#[derive(Debug, Serialize)]
+pub struct TimestampCheck {
+    pub table: &'static str,
+    pub checked: i64,
+    pub stale: i64,
+    // Up to 20 offending row ids
+    pub sample_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimestampReport {
+    pub consistent: bool,
+    pub checks: Vec<TimestampCheck>,
+}
+
+// === Content Freeze Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ContentFreeze {
+    pub id: Uuid,
+    // Exactly one of topic_id and certification_id is set; a certification freeze
+    // covers every topic linked to it
+    pub topic_id: Option<Uuid>,
+    pub certification_id: Option<Uuid>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateContentFreeze {
+    // Give one of topic_id and certification_id
+    pub topic_id: Option<Uuid>,
+    pub certification_id: Option<Uuid>,
+    // Defaults to now
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
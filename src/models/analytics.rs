@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::question::CognitiveLevel;
+
+// === Analytics Models (read from materialized views) ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct QuestionStats {
+    pub question_id: Uuid,
+    pub topic_id: Uuid,
+    pub question_number: i32,
+    pub attempts: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct TopicAccuracy {
+    pub topic_id: Uuid,
+    pub topic_name: String,
+    pub sessions_submitted: i64,
+    pub answers: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+// Accuracy at one confidence level; well calibrated learners are right more often when surer
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CalibrationPoint {
+    pub confidence: i16,
+    pub answers: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+    // Accuracy a perfectly calibrated learner would have at this level
+    pub expected_accuracy: f64,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+// Accuracy on the questions of one cognitive level
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CognitiveLevelAccuracy {
+    pub cognitive_level: CognitiveLevel,
+    pub questions: i64,
+    pub attempts: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalibrationQuery {
+    pub topic_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuestionStatsQuery {
+    pub topic_id: Option<Uuid>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefreshResponse {
+    pub refreshed_at: DateTime<Utc>,
+}
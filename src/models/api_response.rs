@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 // === Response Types ===
 #[derive(Debug, Serialize)]
@@ -6,6 +6,107 @@ pub struct ApiResponse<T> {
     pub success: bool,
     pub data: T,
     pub message: Option<String>,
+    // Set on errors, for clients to branch on instead of the message text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
+    // Non-fatal issues with the request, left out when there are none
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiWarning>,
+}
+
+/// Stable, machine-readable reason for an error response. Codes are only ever added;
+/// a code keeps its meaning once released.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Request problems
+    ValidationFailed,
+    UnknownFields,
+    InvalidCsv,
+    CsvColumnsMissing,
+    ConfirmationRequired,
+    ApiKeyRequired,
+    InvalidApiKey,
+    QuotaExceeded,
+    ReadRateExceeded,
+    ApiKeyBlocked,
+    RouteNotFound,
+    MethodNotAllowed,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+
+    // Missing resources
+    TopicNotFound,
+    QuestionNotFound,
+    SessionNotFound,
+    QuestionNotInSession,
+    NoHints,
+    ExplanationNotFound,
+    VoteNotFound,
+    EmbedTokenNotFound,
+    ShareLinkNotFound,
+    ShareLinkExpired,
+    ApiKeyNotFound,
+    FreezeNotFound,
+    TemplateNotFound,
+    ImportNotFound,
+    RegradeJobNotFound,
+    RebuildJobNotFound,
+    PassbackTargetNotFound,
+    CustomFieldNotFound,
+    WebhookNotFound,
+    DiagramNotFound,
+    ProviderNotFound,
+    CertificationNotFound,
+    ObjectiveNotFound,
+
+    // State conflicts
+    DuplicateSlug,
+    DuplicateTopicName,
+    DuplicateProviderName,
+    DuplicateCertificationName,
+    DuplicateObjectiveCode,
+    DuplicateQuestionNumber,
+    DuplicateCustomField,
+    CustomFieldInUse,
+    ProviderHasCertifications,
+    ObjectiveHasChildren,
+    DependenciesChanged,
+    PrerequisiteCycle,
+    ContentFrozen,
+    TopicHasNoQuestions,
+    SessionSubmitted,
+    SessionPaused,
+    SessionNotPaused,
+    PauseBudgetExhausted,
+    HintsExhausted,
+    TimeLimitReached,
+
+    // Another instance could not be reached or answered with an error
+    UpstreamFailed,
+
+    InternalError,
+}
+
+/// Something the client may want to show or fix, on a request that still succeeded
+#[derive(Debug, Serialize, Clone)]
+pub struct ApiWarning {
+    // Stable identifier to branch on, e.g. SLUG_GENERATED
+    pub code: &'static str,
+    pub message: String,
+    // Request field the warning is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<&'static str>,
+}
+
+impl ApiWarning {
+    pub fn new(code: &'static str, field: &'static str, message: String) -> Self {
+        Self {
+            code,
+            message,
+            field: Some(field),
+        }
+    }
 }
 
 impl<T> ApiResponse<T> {
@@ -14,16 +115,73 @@ impl<T> ApiResponse<T> {
             success: true,
             data,
             message: None,
+            error_code: None,
+            warnings: Vec::new(),
         }
     }
+
+    pub fn with_warnings(mut self, warnings: Vec<ApiWarning>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
 }
 
 impl ApiResponse<()> {
-    pub fn error(message: String) -> Self {
+    pub fn error(code: ErrorCode, message: String) -> Self {
         Self {
             success: false,
             data: (),
             message: Some(message),
+            error_code: Some(code),
+            warnings: Vec::new(),
         }
     }
 }
+
+// One page of a list, with enough to render page controls
+#[derive(Debug, Serialize)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub pagination: PaginationMeta,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginationMeta {
+    pub current_page: i64,
+    pub per_page: i64,
+    pub total_items: i64,
+    pub total_pages: i64,
+    pub has_next: bool,
+    pub has_prev: bool,
+}
+
+impl PaginationMeta {
+    pub fn new(current_page: i64, per_page: i64, total_items: i64) -> Self {
+        let total_pages = (total_items as f64 / per_page as f64).ceil() as i64;
+        
+        Self {
+            current_page,
+            per_page,
+            total_items,
+            total_pages,
+            has_next: current_page < total_pages,
+            has_prev: current_page > 1,
+        }
+    }
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct PageQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl PageQuery {
+    /// The 1-based page, the page size (20 by default, at most 100) and the rows to skip
+    pub fn resolve(&self) -> (i64, i64, i64) {
+        let page = self.page.unwrap_or(1).max(1);
+        let limit = self.limit.unwrap_or(20).clamp(1, 100);
+        (page, limit, (page - 1) * limit)
+    }
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::topic::Topic;
+
+/// Most topics one manifest may declare
+const MAX_TOPICS: usize = 1000;
+
+// === Apply Models ===
+// The content an instance should have. Topics are matched to existing ones by slug,
+// ignoring case. Unknown sections are rejected rather than silently skipped.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContentManifest {
+    pub topics: Vec<TopicSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicSpec {
+    pub slug: String,
+    pub name: String,
+    // Leaving it out clears an existing description
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyQuery {
+    // Report what would change without changing it
+    #[serde(default)]
+    pub plan: bool,
+    // Delete topics the manifest does not declare, with their questions
+    #[serde(default)]
+    pub prune: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApplyAction {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopicChange {
+    pub slug: String,
+    pub action: ApplyAction,
+    // The existing topic; `None` for creations
+    pub topic_id: Option<Uuid>,
+    // Fields an update changes
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyResult {
+    // False for plans
+    pub applied: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+    // Topics the manifest does not declare, left alone because `prune` was not set
+    pub unmanaged: usize,
+    pub topics: Vec<TopicChange>,
+}
+
+impl ApplyResult {
+    pub fn new(applied: bool, topics: Vec<TopicChange>, unmanaged: usize) -> Self {
+        let count = |action| topics.iter().filter(|t| t.action == action).count();
+
+        ApplyResult {
+            applied,
+            created: count(ApplyAction::Create),
+            updated: count(ApplyAction::Update),
+            deleted: count(ApplyAction::Delete),
+            unchanged: count(ApplyAction::Unchanged),
+            unmanaged,
+            topics,
+        }
+    }
+}
+
+/// Check a manifest: non-empty slugs and names, each declared once
+pub fn validate_manifest(manifest: &ContentManifest) -> Result<(), String> {
+    if manifest.topics.len() > MAX_TOPICS {
+        return Err(format!("A manifest may declare at most {} topics", MAX_TOPICS));
+    }
+
+    let mut slugs = HashSet::new();
+    let mut names = HashSet::new();
+
+    for topic in &manifest.topics {
+        if topic.slug.trim().is_empty() {
+            return Err(format!("Topic '{}' needs a slug", topic.name));
+        }
+        if topic.name.trim().is_empty() {
+            return Err(format!("Topic '{}' needs a name", topic.slug));
+        }
+        if !slugs.insert(topic.slug.trim().to_lowercase()) {
+            return Err(format!("Slug '{}' is declared twice", topic.slug));
+        }
+        if !names.insert(topic.name.trim()) {
+            return Err(format!("Name '{}' is declared twice", topic.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Work out how to turn `current` into `desired`: declared topics in manifest order, then
+/// deletions when pruning. Also returns how many topics are left unmanaged.
+pub fn plan_topics(current: &[Topic], desired: &[TopicSpec], prune: bool) -> (Vec<TopicChange>, usize) {
+    let by_slug: HashMap<String, &Topic> = current.iter().map(|t| (t.slug.to_lowercase(), t)).collect();
+
+    let mut changes: Vec<TopicChange> = desired
+        .iter()
+        .map(|spec| {
+            let slug = spec.slug.trim();
+
+            let Some(topic) = by_slug.get(&slug.to_lowercase()) else {
+                return TopicChange { slug: slug.to_string(), action: ApplyAction::Create, topic_id: None, fields: Vec::new() };
+            };
+
+            let mut fields = Vec::new();
+            if topic.slug != slug {
+                fields.push("slug");
+            }
+            if topic.name != spec.name.trim() {
+                fields.push("name");
+            }
+            if topic.description != spec.description {
+                fields.push("description");
+            }
+            if topic.is_public != spec.is_public {
+                fields.push("is_public");
+            }
+
+            let action = if fields.is_empty() { ApplyAction::Unchanged } else { ApplyAction::Update };
+            TopicChange { slug: slug.to_string(), action, topic_id: Some(topic.id), fields }
+        })
+        .collect();
+
+    let declared: HashSet<String> = desired.iter().map(|s| s.slug.trim().to_lowercase()).collect();
+    let undeclared: Vec<&Topic> = current.iter().filter(|t| !declared.contains(&t.slug.to_lowercase())).collect();
+
+    if !prune {
+        return (changes, undeclared.len());
+    }
+
+    changes.extend(undeclared.into_iter().map(|topic| TopicChange {
+        slug: topic.slug.clone(),
+        action: ApplyAction::Delete,
+        topic_id: Some(topic.id),
+        fields: Vec::new(),
+    }));
+
+    (changes, 0)
+}
@@ -0,0 +1,43 @@
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+
+use super::topic::DifficultyCounts;
+
+// === Public Catalog Models (no ids or admin-only fields) ===
+#[derive(Debug, Serialize)]
+pub struct Catalog {
+    pub generated_at: DateTime<Utc>,
+    pub topic_count: usize,
+    pub question_count: i64,
+    pub topics: Vec<CatalogTopic>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct CatalogTopic {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub question_count: i64,
+    #[sqlx(flatten)]
+    pub difficulty_counts: DifficultyCounts,
+    // Latest change to the topic or any of its questions
+    pub last_modified: DateTime<Utc>,
+}
+
+// === Feed Models ===
+#[derive(Debug, Serialize)]
+pub struct Feed {
+    pub generated_at: DateTime<Utc>,
+    pub items: Vec<FeedItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedItem {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub name: String,
+    pub slug: String,
+    pub url: String,
+    pub last_modified: DateTime<Utc>,
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::question::{Question, QuestionResponse};
+
+// === Certification Models ===
+// An exam offered by a provider. Topics belong to at most one certification.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Certification {
+    pub id: Uuid,
+    pub provider_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    // The provider's exam code, e.g. SAA-C03
+    pub exam_code: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Certification listing entry
+#[derive(Debug, Serialize, FromRow)]
+pub struct CertificationWithCounts {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub certification: Certification,
+    pub topic_count: i64,
+}
+
+// A question of a certification's topics with its place in the official exam-guide order
+#[derive(Debug, FromRow)]
+pub struct OrderedQuestion {
+    #[sqlx(flatten)]
+    pub question: Question,
+    pub official_position: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertificationQuestion {
+    #[serde(flatten)]
+    pub question: QuestionResponse,
+    // 1-based; null for questions not placed in the official order
+    pub official_position: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertificationOrder {
+    pub certification_id: Uuid,
+    pub question_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCertification {
+    pub provider_id: Uuid,
+    pub name: String,
+    pub slug: Option<String>,
+    pub exam_code: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCertification {
+    // Moves the certification, with its topics, to another provider
+    pub provider_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub exam_code: Option<String>,
+    pub description: Option<String>,
+}
+
+// Everything a certification delete unlinks or removes
+#[derive(Debug, Serialize, FromRow)]
+pub struct CertificationDependencies {
+    pub certification_id: Uuid,
+    // Topics and their questions are kept and unlinked
+    pub topic_count: i64,
+    pub question_count: i64,
+    // Freezes of the whole certification are deleted with it
+    pub freeze_count: i64,
+    // Questions placed in the official order, which is deleted with it
+    pub ordered_question_count: i64,
+    // Exam-guide objectives, deleted with their question mappings
+    pub objective_count: i64,
+    #[serde(skip)]
+    pub certification_updated_at: DateTime<Utc>,
+    // Pass back as `?confirm=` when deleting; changes whenever any count or the certification changes
+    #[sqlx(skip)]
+    pub confirmation_token: String,
+}
+
+impl CertificationDependencies {
+    pub fn token(&self) -> String {
+        let report = format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.certification_id,
+            self.topic_count,
+            self.question_count,
+            self.freeze_count,
+            self.ordered_question_count,
+            self.objective_count,
+            self.certification_updated_at.timestamp_micros(),
+        );
+
+        hex::encode(Sha256::digest(report))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetCertificationOrder {
+    // Replaces the official order; `[]` clears it. Questions left out follow the listed ones.
+    pub question_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteCertificationQuery {
+    pub confirm: Option<String>,
+}
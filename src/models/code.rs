@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+use regex::Regex;
+
+use super::question::QuestionResponse;
+
+/// Most code blocks a question may carry
+const MAX_BLOCKS: usize = 5;
+
+/// Longest block content, in characters
+const MAX_CONTENT: usize = 20_000;
+
+static LANGUAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9][a-z0-9+#._-]{0,31}$").unwrap());
+
+// === Code Models ===
+// A code snippet shown with a question, kept apart from the prose
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CodeBlock {
+    // Lowercase language name, e.g. `python`, `sql`, `hcl`
+    pub language: String,
+    pub content: String,
+    // 1-based lines to draw attention to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlight_lines: Vec<u32>,
+    // Highlighted HTML, only in responses that ask for it
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    // Where the rendered SVG of a diagram block is served, in responses
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub diagram_url: Option<String>,
+}
+
+// How code blocks are returned
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeFormat {
+    // Content only, for clients that highlight it themselves
+    #[default]
+    Source,
+    // Content plus highlighted HTML in `html`
+    Html,
+}
+
+/// Check code blocks: a known shape of language name, non-empty content within the size
+/// limit, and highlighted lines that exist and are listed once
+pub fn validate_code_blocks(blocks: &[CodeBlock]) -> Result<(), String> {
+    if blocks.len() > MAX_BLOCKS {
+        return Err(format!("A question may have at most {} code blocks", MAX_BLOCKS));
+    }
+
+    for (index, block) in blocks.iter().enumerate() {
+        let number = index + 1;
+
+        if !LANGUAGE.is_match(&block.language) {
+            return Err(format!("Code block {} needs a lowercase language name such as 'python'", number));
+        }
+        if block.content.trim().is_empty() {
+            return Err(format!("Code block {} is empty", number));
+        }
+        if block.content.chars().count() > MAX_CONTENT {
+            return Err(format!("Code block {} is longer than {} characters", number, MAX_CONTENT));
+        }
+
+        let lines = block.content.lines().count() as u32;
+        let mut seen = HashSet::new();
+        for line in &block.highlight_lines {
+            if *line == 0 || *line > lines {
+                return Err(format!("Code block {} has {} lines, so line {} cannot be highlighted", number, lines, line));
+            }
+            if !seen.insert(line) {
+                return Err(format!("Code block {} highlights line {} twice", number, line));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The renderer that draws blocks of `language` as diagrams, if it is a diagram language
+pub fn diagram_renderer(language: &str) -> Option<&'static str> {
+    match language {
+        "mermaid" => Some("mermaid"),
+        "graphviz" | "dot" => Some("graphviz"),
+        _ => None,
+    }
+}
+
+/// Cache key of a diagram. The `diagrams` trigger computes the same hash in SQL.
+pub fn diagram_hash(renderer: &str, source: &str) -> String {
+    hex::encode(Sha256::digest(format!("{}\n{}", renderer, source).as_bytes()))
+}
+
+// Token rules for one family of languages
+struct Grammar {
+    keywords: &'static [&'static str],
+    line_comment: &'static [&'static str],
+    case_insensitive: bool,
+}
+
+const C_LIKE: &[&str] = &["//"];
+const HASH: &[&str] = &["#"];
+
+fn grammar(language: &str) -> Option<Grammar> {
+    let (keywords, line_comment): (&'static [&'static str], _) = match language {
+        "python" | "py" => (
+            &["and", "as", "class", "def", "elif", "else", "except", "False", "finally", "for", "from", "if", "import",
+              "in", "is", "lambda", "None", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield"],
+            HASH,
+        ),
+        "javascript" | "js" | "typescript" | "ts" => (
+            &["async", "await", "break", "case", "catch", "class", "const", "else", "export", "false", "for", "function",
+              "if", "import", "interface", "let", "new", "null", "return", "switch", "this", "throw", "true", "try",
+              "type", "undefined", "var", "while"],
+            C_LIKE,
+        ),
+        "java" | "csharp" | "c#" | "c" | "cpp" | "c++" => (
+            &["abstract", "bool", "boolean", "break", "case", "catch", "class", "const", "else", "false", "final", "for",
+              "if", "int", "interface", "new", "null", "private", "protected", "public", "return", "static", "string",
+              "switch", "this", "throw", "true", "try", "using", "void", "while"],
+            C_LIKE,
+        ),
+        "go" => (
+            &["break", "case", "chan", "const", "defer", "else", "false", "for", "func", "go", "if", "import",
+              "interface", "map", "nil", "package", "range", "return", "select", "struct", "switch", "true", "type", "var"],
+            C_LIKE,
+        ),
+        "rust" | "rs" => (
+            &["as", "async", "await", "else", "enum", "false", "fn", "for", "if", "impl", "let", "loop", "match", "mod",
+              "mut", "pub", "return", "self", "Self", "struct", "trait", "true", "use", "where", "while"],
+            C_LIKE,
+        ),
+        "sql" => (
+            &["AND", "AS", "BY", "CREATE", "DELETE", "FROM", "GROUP", "HAVING", "INSERT", "INTO", "JOIN", "LEFT", "LIMIT",
+              "NOT", "NULL", "ON", "OR", "ORDER", "SELECT", "SET", "TABLE", "UPDATE", "VALUES", "WHERE"],
+            &["--"],
+        ),
+        "bash" | "sh" | "shell" | "powershell" | "ps1" => (
+            &["case", "do", "done", "echo", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in",
+              "then", "while"],
+            HASH,
+        ),
+        "yaml" | "yml" | "hcl" | "terraform" | "dockerfile" => (&["false", "null", "true"], HASH),
+        "json" => (&["false", "null", "true"], &[]),
+        _ => return None,
+    };
+
+    Some(Grammar { keywords, line_comment, case_insensitive: language == "sql" })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn span(class: &str, text: &str) -> String {
+    format!("<span class=\"hl-{}\">{}</span>", class, escape(text))
+}
+
+/// Highlight one line: comments, strings, numbers and keywords. Tokens never span
+/// lines, so multi-line strings and block comments are shown as plain text.
+fn highlight_line(line: &str, grammar: &Grammar) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        if grammar.line_comment.iter().any(|marker| rest.starts_with(marker)) {
+            out.push_str(&span("comment", rest));
+            break;
+        }
+
+        let length = if c == '"' || c == '\'' || c == '`' {
+            // Up to the closing quote, skipping escaped ones; unclosed strings run to the end
+            let mut escaped = false;
+            let close = rest[1..].char_indices().find(|&(_, ch)| {
+                let closes = ch == c && !escaped;
+                escaped = ch == '\\' && !escaped;
+                closes
+            });
+            let length = close.map_or(rest.len(), |(at, _)| at + 2);
+            out.push_str(&span("string", &rest[..length]));
+            length
+        } else if c.is_ascii_digit() {
+            let length = rest.find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_')).unwrap_or(rest.len());
+            out.push_str(&span("number", &rest[..length]));
+            length
+        } else if c.is_alphabetic() || c == '_' {
+            let length = rest.find(|ch: char| !(ch.is_alphanumeric() || ch == '_')).unwrap_or(rest.len());
+            let word = &rest[..length];
+            let keyword = if grammar.case_insensitive {
+                grammar.keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+            } else {
+                grammar.keywords.contains(&word)
+            };
+            out.push_str(&if keyword { span("keyword", word) } else { escape(word) });
+            length
+        } else {
+            out.push_str(&escape(&rest[..c.len_utf8()]));
+            c.len_utf8()
+        };
+
+        rest = &rest[length..];
+    }
+
+    out
+}
+
+/// Render a block as HTML: one `line` span per line, `hl-line` on highlighted ones and
+/// `hl-*` token classes for languages the highlighter knows. Other languages are escaped only.
+pub fn render_code_html(block: &CodeBlock) -> String {
+    let grammar = grammar(&block.language);
+
+    let lines: Vec<String> = block
+        .content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let class = if block.highlight_lines.contains(&(index as u32 + 1)) { "line hl-line" } else { "line" };
+            let body = match &grammar {
+                Some(grammar) => highlight_line(line, grammar),
+                None => escape(line),
+            };
+            format!("<span class=\"{}\">{}</span>", class, body)
+        })
+        .collect();
+
+    format!(
+        "<pre class=\"code\"><code class=\"language-{}\">{}</code></pre>",
+        escape(&block.language),
+        lines.join("\n")
+    )
+}
+
+impl QuestionResponse {
+    /// Link diagram blocks to their rendered SVG, and add highlighted HTML to the
+    /// question's code blocks when asked for. Diagrams are shown as the image instead.
+    pub fn render_code(mut self, format: CodeFormat) -> Self {
+        let Some(blocks) = &mut self.code_blocks else {
+            return self;
+        };
+
+        for block in blocks {
+            let diagram = diagram_renderer(&block.language)
+                .map(|renderer| format!("/api/diagrams/{}/image.svg", diagram_hash(renderer, &block.content)));
+
+            if format == CodeFormat::Html {
+                block.html = Some(match &diagram {
+                    Some(url) => format!(
+                        "<figure class=\"diagram\"><img src=\"{}\" alt=\"{} diagram\"></figure>",
+                        url,
+                        escape(&block.language)
+                    ),
+                    None => render_code_html(block),
+                });
+            }
+            block.diagram_url = diagram;
+        }
+        self
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::prelude::FromRow;
+use sqlx::types::Json;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[sqlx(type_name = "custom_field_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Enum,
+}
+
+// === Custom Field Models ===
+#[derive(Debug, Serialize, FromRow)]
+pub struct CustomField {
+    pub key: String,
+    pub label: String,
+    pub field_type: CustomFieldType,
+    pub options: Option<Json<Vec<String>>>,
+    pub required: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Check question metadata against the field definitions: every key must be defined,
+/// values must match the field type, and required fields must be present
+pub fn validate_metadata(fields: &[CustomField], metadata: &HashMap<String, Value>) -> Result<(), String> {
+    for (key, value) in metadata {
+        let Some(field) = fields.iter().find(|f| &f.key == key) else {
+            return Err(format!("Unknown custom field '{}'", key));
+        };
+
+        let valid = match field.field_type {
+            CustomFieldType::Text => value.is_string(),
+            CustomFieldType::Number => value.is_number(),
+            CustomFieldType::Enum => value
+                .as_str()
+                .is_some_and(|v| field.options.as_ref().is_some_and(|o| o.0.iter().any(|option| option == v))),
+        };
+
+        if !valid {
+            let expected = match field.field_type {
+                CustomFieldType::Text => "a string".to_string(),
+                CustomFieldType::Number => "a number".to_string(),
+                CustomFieldType::Enum => format!(
+                    "one of {}",
+                    field.options.as_ref().map(|o| o.0.join(", ")).unwrap_or_default()
+                ),
+            };
+            return Err(format!("Custom field '{}' must be {}", key, expected));
+        }
+    }
+
+    if let Some(missing) = fields.iter().find(|f| f.required && !metadata.contains_key(&f.key)) {
+        return Err(format!("Custom field '{}' is required", missing.key));
+    }
+
+    Ok(())
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct CreateCustomField {
+    pub key: String,
+    pub label: String,
+    pub field_type: CustomFieldType,
+    // Required for enum fields
+    pub options: Option<Vec<String>>,
+    #[serde(default)]
+    pub required: bool,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// === Embed Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct EmbedToken {
+    pub id: Uuid,
+    pub token: String,
+    pub topic_id: Uuid,
+    // Host allowed to frame the widget, e.g. "blog.example.com"
+    pub domain: String,
+    pub question_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateEmbedToken {
+    pub topic_id: Uuid,
+    pub domain: String,
+    // Defaults to 5
+    pub question_count: Option<i32>,
+}
+
+// What the widget needs to start a quiz against the public API
+#[derive(Debug, Serialize)]
+pub struct EmbedConfig {
+    pub topic_id: Uuid,
+    pub topic_name: String,
+    pub question_count: i32,
+    pub api_base: String,
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[sqlx(type_name = "vote_direction", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum VoteDirection {
+    Up,
+    Down,
+}
+
+// === Explanation Vote Models ===
+#[derive(Debug, Serialize)]
+pub struct ExplanationVoteSummary {
+    pub question_id: Uuid,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    pub score: i32,
+    pub your_vote: Option<VoteDirection>,
+}
+
+// One row of the worst-rated explanations report
+#[derive(Debug, Serialize, FromRow)]
+pub struct ExplanationRating {
+    pub question_id: Uuid,
+    pub topic_id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+    pub explanation: String,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    // Share of upvotes, 0.0 to 1.0
+    pub approval: f64,
+}
+
+// === Community Explanation Models ===
+#[derive(Debug, Serialize, FromRow)]
+pub struct ExplanationEntry {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub author: String,
+    pub body: String,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    pub approved: bool,
+    pub is_primary: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct ExplanationVote {
+    pub vote: VoteDirection,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExplanationReportQuery {
+    // Explanations with fewer votes are left out, defaults to 3
+    pub min_votes: Option<i32>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateExplanationEntry {
+    pub author: String,
+    pub body: String,
+}
+
+// Editor review of a contributed explanation
+#[derive(Debug, Deserialize)]
+pub struct ReviewExplanationEntry {
+    pub approved: Option<bool>,
+    // Making an entry primary also approves it and copies its body to the question
+    pub is_primary: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExplanationEntryQuery {
+    // Editors can list entries still waiting for approval
+    pub include_pending: Option<bool>,
+}
@@ -0,0 +1,376 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::Json;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+
+use super::api_response::ErrorCode;
+use super::question::{BulkQuestionData, CognitiveLevel, Difficulty, QuestionType};
+
+// === Import Template Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ImportTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub mapping: Json<ImportMapping>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateImportTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub mapping: ImportMapping,
+}
+
+/// How the columns of a vendor spreadsheet map onto question fields
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ImportMapping {
+    // Field separator of the file, defaults to ','
+    pub delimiter: Option<char>,
+    pub question_number: ColumnMapping,
+    pub question: ColumnMapping,
+    pub options: ColumnMapping,
+    pub correct_answer: ColumnMapping,
+    pub explanation: ColumnMapping,
+    pub question_type: ColumnMapping,
+    pub difficulty: ColumnMapping,
+    pub cognitive_level: ColumnMapping,
+    pub tags: ColumnMapping,
+}
+
+/// Source of one question field. Cells from several columns are taken in order
+/// (e.g. "Option A".."Option D"), each optionally split, trimmed and then mapped.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ColumnMapping {
+    pub columns: Vec<String>,
+    // Split each cell on this separator, e.g. ";" for "A;C"
+    pub split: Option<String>,
+    // Replace whole values, e.g. {"E": "easy"}
+    pub map: HashMap<String, String>,
+    // Used when the cells are empty or no column is mapped
+    pub default: Option<String>,
+}
+
+impl ColumnMapping {
+    fn values(&self, row: &HashMap<&str, &str>) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .columns
+            .iter()
+            .flat_map(|column| {
+                let cell = row.get(column.as_str()).copied().unwrap_or_default();
+                match &self.split {
+                    Some(separator) => cell.split(separator.as_str()).collect::<Vec<_>>(),
+                    None => vec![cell],
+                }
+            })
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| self.map.get(value).cloned().unwrap_or_else(|| value.to_string()))
+            .collect();
+
+        if values.is_empty() {
+            values.extend(self.default.clone());
+        }
+
+        values
+    }
+
+    fn first(&self, row: &HashMap<&str, &str>) -> Option<String> {
+        self.values(row).into_iter().next()
+    }
+}
+
+impl ImportMapping {
+    fn fields(&self) -> [&ColumnMapping; 9] {
+        [
+            &self.question_number,
+            &self.question,
+            &self.options,
+            &self.correct_answer,
+            &self.explanation,
+            &self.question_type,
+            &self.difficulty,
+            &self.cognitive_level,
+            &self.tags,
+        ]
+    }
+
+    /// Mapped columns that the file's header row does not have
+    pub fn missing_columns(&self, headers: &[&str]) -> Vec<String> {
+        let mut missing: Vec<String> = self
+            .fields()
+            .iter()
+            .flat_map(|field| &field.columns)
+            .filter(|column| !headers.contains(&column.as_str()))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Header columns that no field of the template reads from
+    pub fn unmapped_columns(&self, headers: &[&str]) -> Vec<String> {
+        let mapped: HashSet<&str> = self
+            .fields()
+            .iter()
+            .flat_map(|field| &field.columns)
+            .map(String::as_str)
+            .collect();
+
+        headers
+            .iter()
+            .filter(|column| !column.is_empty() && !mapped.contains(*column))
+            .map(|column| column.to_string())
+            .collect()
+    }
+
+    /// Turn one spreadsheet row (header -> cell) into a question. Unless numbers are kept
+    /// as they are, an empty question_number becomes 0 so that it is assigned on insert.
+    pub fn apply(&self, row: &HashMap<&str, &str>, numbering: Numbering) -> Result<BulkQuestionData, String> {
+        let question_number = match self.question_number.first(row) {
+            Some(number) => number
+                .parse()
+                .map_err(|_| format!("question_number '{}' is not a number", number))?,
+            None if numbering == Numbering::Preserve => return Err("question_number is empty".to_string()),
+            None => 0,
+        };
+
+        let question = self.question.first(row).ok_or("question is empty")?;
+
+        let options = self.options.values(row);
+        if options.is_empty() {
+            return Err("options are empty".to_string());
+        }
+
+        let correct_answer = self.correct_answer.values(row);
+        if correct_answer.is_empty() {
+            return Err("correct_answer is empty".to_string());
+        }
+
+        // Without a mapped type, more than one correct answer means multiple choice
+        let question_type = match self.question_type.first(row) {
+            Some(value) => parse_enum::<QuestionType>("question_type", &value)?,
+            None if correct_answer.len() > 1 => QuestionType::Multiple,
+            None => QuestionType::Single,
+        };
+
+        let difficulty = self
+            .difficulty
+            .first(row)
+            .map(|value| parse_enum::<Difficulty>("difficulty", &value))
+            .transpose()?;
+
+        let cognitive_level = self
+            .cognitive_level
+            .first(row)
+            .map(|value| parse_enum::<CognitiveLevel>("cognitive_level", &value))
+            .transpose()?;
+
+        let tags = self.tags.values(row);
+
+        Ok(BulkQuestionData {
+            question_number,
+            question,
+            options,
+            correct_answer,
+            explanation: self.explanation.values(row).join("\n"),
+            question_type,
+            difficulty,
+            cognitive_level,
+            tags: (!tags.is_empty()).then_some(tags),
+            option_explanations: None,
+            hints: None,
+            metadata: None,
+            variables: None,
+            code_blocks: None,
+        })
+    }
+}
+
+fn parse_enum<T: serde::de::DeserializeOwned>(field: &str, value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("{} '{}' is not valid", field, value))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CsvImportQuery {
+    pub topic_slug: String,
+    // Name of a saved import template
+    pub template: String,
+    // Name of the uploaded file, recorded in the import history
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub on_error: OnError,
+    #[serde(default)]
+    pub numbering: Numbering,
+    // Reject files with columns the template does not map
+    #[serde(default)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YamlImportQuery {
+    pub topic_slug: String,
+    // Name of the uploaded file, recorded in the import history
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub on_error: OnError,
+    #[serde(default)]
+    pub numbering: Numbering,
+    // Fail questions whose front matter has keys the format does not know
+    #[serde(default)]
+    pub strict: bool,
+}
+
+// === Import History Models ===
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[sqlx(type_name = "import_source", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    Bulk,
+    Csv,
+    Yaml,
+    Json,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ImportRun {
+    pub id: Uuid,
+    pub topic_id: Option<Uuid>,
+    pub source: ImportSource,
+    pub source_filename: Option<String>,
+    pub template_name: Option<String>,
+    pub imported_by: Option<String>,
+    pub total_rows: i32,
+    pub created: i32,
+    pub failed: i32,
+    // Header order of the uploaded file, empty for JSON imports
+    #[serde(skip)]
+    pub columns: Json<Vec<String>>,
+    #[serde(skip)]
+    pub failed_rows: Json<Vec<FailedRow>>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct NewImportRun {
+    pub topic_id: Uuid,
+    pub source: ImportSource,
+    pub source_filename: Option<String>,
+    pub template_name: Option<String>,
+    pub imported_by: Option<String>,
+    pub total_rows: usize,
+    pub created: usize,
+    pub columns: Vec<String>,
+    pub failed_rows: Vec<FailedRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FailedRow {
+    pub row: usize,
+    pub error: String,
+    // The row as uploaded: header -> cell for CSV, the question object for JSON
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug)]
+pub struct RowError {
+    pub row: usize,
+    pub error: String,
+}
+
+/// What to do with the rest of an import when some rows fail
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    // Import nothing
+    #[default]
+    Abort,
+    // Import the valid rows and report the failed ones
+    Skip,
+}
+
+/// How imported question numbers are reconciled with the topic's existing questions
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Numbering {
+    // Keep the numbers from the file; collisions fail
+    #[default]
+    Preserve,
+    // Renumber every row after the topic's current highest number, in file order
+    Append,
+    // Keep numbers that are free, give missing and colliding ones the next free number
+    Auto,
+}
+
+impl Numbering {
+    /// The number to insert for each requested number. Numbers below 1 count as missing.
+    pub fn assign(self, existing: &[i32], requested: &[i32]) -> Vec<i32> {
+        let mut next = existing.iter().copied().max().unwrap_or(0) + 1;
+
+        match self {
+            Numbering::Preserve => requested.to_vec(),
+            Numbering::Append => (next..).take(requested.len()).collect(),
+            Numbering::Auto => {
+                let mut taken: HashSet<i32> = existing.iter().copied().collect();
+                let kept: Vec<Option<i32>> = requested
+                    .iter()
+                    .map(|&n| (n > 0 && taken.insert(n)).then_some(n))
+                    .collect();
+
+                next = next.max(taken.iter().copied().max().unwrap_or(0) + 1);
+                kept.into_iter()
+                    .map(|n| {
+                        n.unwrap_or_else(|| {
+                            next += 1;
+                            next - 1
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkImportQuery {
+    // Name of the uploaded file, recorded in the import history
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub on_error: OnError,
+    #[serde(default)]
+    pub numbering: Numbering,
+    // Reject payloads with fields the import does not know, e.g. `answer` for `correct_answer`
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Deserialize an import payload. Unknown fields are normally ignored; in strict mode
+/// they are all listed in the error, by path (e.g. `questions.3.answer`).
+pub fn parse_import<T: DeserializeOwned>(payload: serde_json::Value, strict: bool) -> Result<T, (ErrorCode, String)> {
+    let mut unknown = Vec::new();
+    let parsed: T = serde_ignored::deserialize(payload, |path| unknown.push(path.to_string()))
+        .map_err(|e| (ErrorCode::ValidationFailed, format!("Invalid import payload: {}", e)))?;
+
+    if strict && !unknown.is_empty() {
+        return Err((
+            ErrorCode::UnknownFields,
+            format!("Unknown fields in strict mode: {}", unknown.join(", ")),
+        ));
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportRunQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::import::{Numbering, OnError};
+use super::question::{BulkQuestionData, CognitiveLevel, Difficulty, QuestionType};
+use super::utils::{option_index, option_label};
+
+/// Separates the entries of a list cell, e.g. `Paris|London|Rome`. A literal `|` or `\`
+/// inside an entry is escaped with a backslash.
+const LIST_SEPARATOR: char = '|';
+
+/// Columns of a question file, in file order
+pub const QUESTION_ROW_COLUMNS: [&str; 10] = [
+    "question_number",
+    "question_type",
+    "difficulty",
+    "cognitive_level",
+    "question",
+    "options",
+    "correct_answer",
+    "explanation",
+    "tags",
+    "hints",
+];
+
+/// Columns every CSV question file must have
+pub const REQUIRED_QUESTION_ROW_COLUMNS: [&str; 3] = ["question", "options", "correct_answer"];
+
+// === Import/Export Models ===
+// One question as a flat row, the same for CSV and JSON files so that either can be
+// edited in a spreadsheet and uploaded again. Lists are cells joined with `|`.
+// Fields are in `QUESTION_ROW_COLUMNS` order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionRow {
+    // Missing or below 1 counts as missing, for `numbering=auto` or `append`
+    pub question_number: Option<i32>,
+    pub question_type: Option<QuestionType>,
+    pub difficulty: Option<Difficulty>,
+    pub cognitive_level: Option<CognitiveLevel>,
+    pub question: String,
+    pub options: String,
+    // Option labels, e.g. `B` or `A|C`
+    pub correct_answer: String,
+    pub explanation: Option<String>,
+    pub tags: Option<String>,
+    pub hints: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionFileFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl QuestionFileFormat {
+    /// The format a `Content-Type` names, ignoring parameters such as `charset`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        match media_type.to_ascii_lowercase().as_str() {
+            "text/csv" => Some(QuestionFileFormat::Csv),
+            "application/json" => Some(QuestionFileFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// The format a file name's extension names, e.g. `storage.csv`
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let (_, extension) = filename.rsplit_once('.')?;
+
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(QuestionFileFormat::Csv),
+            "json" => Some(QuestionFileFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            QuestionFileFormat::Csv => "text/csv; charset=utf-8",
+            QuestionFileFormat::Json => "application/json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            QuestionFileFormat::Csv => "csv",
+            QuestionFileFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuestionFileImportQuery {
+    pub topic_slug: String,
+    // Overrides the Content-Type and file name of the upload
+    pub format: Option<QuestionFileFormat>,
+    // Name of the uploaded file, recorded in the import history; defaults to the
+    // multipart file name
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub on_error: OnError,
+    #[serde(default)]
+    pub numbering: Numbering,
+    // Reject columns (or JSON keys) that are not part of the row schema
+    #[serde(default)]
+    pub strict: bool,
+}
+
+// A question file as uploaded, with what the upload says about it
+#[derive(Debug)]
+pub struct QuestionUpload {
+    pub body: String,
+    // From the Content-Type of the file (or of the request), else the file name
+    pub format: Option<QuestionFileFormat>,
+    // Only multipart uploads carry a file name
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuestionFileExportQuery {
+    #[serde(default)]
+    pub format: QuestionFileFormat,
+}
+
+impl QuestionRow {
+    /// Check the row and turn it into a question. Unless numbers are kept as they are,
+    /// a missing question_number becomes 0 so that it is assigned on insert.
+    pub fn into_question(self, numbering: Numbering) -> Result<BulkQuestionData, String> {
+        let question_number = match self.question_number {
+            Some(number) if number > 0 => number,
+            _ if numbering == Numbering::Preserve => return Err("question_number is missing".to_string()),
+            _ => 0,
+        };
+
+        let question = self.question.trim().to_string();
+        if question.is_empty() {
+            return Err("question is empty".to_string());
+        }
+
+        let options = split_list(&self.options);
+        if options.len() < 2 {
+            return Err("options need at least two entries".to_string());
+        }
+
+        let correct_answer = split_list(&self.correct_answer);
+        if correct_answer.is_empty() {
+            return Err("correct_answer is empty".to_string());
+        }
+
+        let mut labels = HashSet::new();
+        for label in &correct_answer {
+            let is_label = label.len() == 1 && option_index(label).is_some_and(|index| index < options.len());
+            if !is_label {
+                return Err(format!(
+                    "correct_answer '{}' is not an option label; use {} to {}",
+                    label,
+                    option_label(0),
+                    option_label(options.len() - 1)
+                ));
+            }
+            if !labels.insert(label.as_str()) {
+                return Err(format!("correct_answer '{}' is given twice", label));
+            }
+        }
+
+        // Without a type, more than one correct answer means multiple choice
+        let question_type = match self.question_type {
+            Some(QuestionType::Single) if correct_answer.len() > 1 => {
+                return Err("single choice questions have exactly one correct_answer".to_string());
+            }
+            Some(question_type) => question_type,
+            None if correct_answer.len() > 1 => QuestionType::Multiple,
+            None => QuestionType::Single,
+        };
+
+        let tags = self.tags.as_deref().map(split_list).filter(|tags| !tags.is_empty());
+        let hints = self.hints.as_deref().map(split_list).filter(|hints| !hints.is_empty());
+
+        Ok(BulkQuestionData {
+            question_number,
+            question,
+            options,
+            correct_answer,
+            explanation: self.explanation.unwrap_or_default().trim().to_string(),
+            question_type,
+            difficulty: self.difficulty,
+            cognitive_level: self.cognitive_level,
+            tags,
+            option_explanations: None,
+            hints,
+            metadata: None,
+            variables: None,
+            code_blocks: None,
+        })
+    }
+}
+
+/// Rows keep the fields of the row schema; option explanations, metadata, variables
+/// and code blocks are left out
+impl From<BulkQuestionData> for QuestionRow {
+    fn from(q: BulkQuestionData) -> Self {
+        QuestionRow {
+            question_number: Some(q.question_number),
+            question_type: Some(q.question_type),
+            difficulty: q.difficulty,
+            cognitive_level: q.cognitive_level,
+            question: q.question,
+            options: join_list(&q.options),
+            correct_answer: join_list(&q.correct_answer),
+            explanation: Some(q.explanation).filter(|e| !e.is_empty()),
+            tags: q.tags.filter(|t| !t.is_empty()).map(|t| join_list(&t)),
+            hints: q.hints.filter(|h| !h.is_empty()).map(|h| join_list(&h)),
+        }
+    }
+}
+
+/// Split a list cell on unescaped `|`, trimming entries and dropping empty ones
+pub fn split_list(cell: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+    let mut chars = cell.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => entry.push(chars.next().unwrap_or('\\')),
+            LIST_SEPARATOR => entries.push(std::mem::take(&mut entry)),
+            _ => entry.push(c),
+        }
+    }
+    entries.push(entry);
+
+    entries
+        .into_iter()
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+/// Join entries into a list cell that `split_list` reads back
+pub fn join_list(entries: &[String]) -> String {
+    entries
+        .iter()
+        .map(|entry| entry.replace('\\', "\\\\").replace(LIST_SEPARATOR, "\\|"))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Encode one part of an exported question file. `first` starts the file (the CSV
+/// header, the opening bracket of the JSON array) and `last` ends it, so a file can be
+/// written a batch of rows at a time.
+pub fn write_question_rows(format: QuestionFileFormat, rows: &[QuestionRow], first: bool, last: bool) -> Result<String, String> {
+    match format {
+        QuestionFileFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+            if first {
+                writer.write_record(QUESTION_ROW_COLUMNS).map_err(|e| e.to_string())?;
+            }
+            for row in rows {
+                writer.serialize(row).map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+        QuestionFileFormat::Json => {
+            let mut out = String::new();
+            if first {
+                out.push('[');
+            }
+            for (index, row) in rows.iter().enumerate() {
+                if !(first && index == 0) {
+                    out.push(',');
+                }
+                out.push('\n');
+                out.push_str(&serde_json::to_string(row).map_err(|e| e.to_string())?);
+            }
+            if last {
+                out.push_str("\n]\n");
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Keys of a JSON row that are not part of the row schema
+pub fn unknown_row_keys(row: &serde_json::Value) -> Vec<String> {
+    match row {
+        serde_json::Value::Object(map) => map
+            .keys()
+            .filter(|key| !QUESTION_ROW_COLUMNS.contains(&key.as_str()))
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,123 @@
+use serde::Deserialize;
+use latex2mathml::{latex_to_mathml, DisplayStyle};
+
+use super::question::QuestionResponse;
+
+/// Math delimiters, as MathJax uses them by default. Single `$` is left alone so prices
+/// in question text are not mistaken for math.
+const DELIMITERS: [(&str, &str, DisplayStyle); 3] = [
+    ("$$", "$$", DisplayStyle::Block),
+    ("\\[", "\\]", DisplayStyle::Block),
+    ("\\(", "\\)", DisplayStyle::Inline),
+];
+
+/// Marker the renderer leaves in its output for commands it does not know
+const PARSE_ERROR: &str = "[PARSE ERROR";
+
+// === Math Models ===
+// How LaTeX segments in question text are returned
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MathFormat {
+    // As written, for clients that typeset it themselves (e.g. with MathJax)
+    #[default]
+    Source,
+    // Each segment replaced by MathML
+    Mathml,
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Math { source: &'a str, delimited: &'a str, display: DisplayStyle },
+}
+
+/// Split text into plain text and math segments, checking that delimiters pair up
+fn segments(text: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut out = Vec::new();
+    let mut rest = text;
+
+    loop {
+        let next = DELIMITERS
+            .iter()
+            .filter_map(|delimiter| rest.find(delimiter.0).map(|at| (at, delimiter)))
+            .min_by_key(|(at, _)| *at);
+
+        let plain = next.map_or(rest, |(at, _)| &rest[..at]);
+        if let Some(closer) = ["\\)", "\\]"].iter().find(|closer| plain.contains(**closer)) {
+            return Err(format!("Math delimiter '{}' closes a segment that was never opened", closer));
+        }
+        out.push(Segment::Text(plain));
+
+        let Some((at, (open, close, display))) = next else {
+            return Ok(out);
+        };
+
+        let body = &rest[at + open.len()..];
+        let Some(length) = body.find(close) else {
+            return Err(format!("Math delimiter '{}' is never closed with '{}'", open, close));
+        };
+
+        let source = &body[..length];
+        if source.trim().is_empty() {
+            return Err(format!("Math segment '{}{}' is empty", open, close));
+        }
+        if DELIMITERS.iter().any(|d| source.contains(d.0)) {
+            return Err(format!("Math segment '{}{}{}' contains another math delimiter", open, source, close));
+        }
+
+        let end = at + open.len() + length + close.len();
+        out.push(Segment::Math { source, delimited: &rest[at..end], display: *display });
+        rest = &rest[end..];
+    }
+}
+
+/// Check the LaTeX segments of a text: delimiters pair up and each segment renders
+pub fn validate_math(text: &str) -> Result<(), String> {
+    for segment in segments(text)? {
+        if let Segment::Math { source, display, .. } = segment {
+            let mathml = latex_to_mathml(source, display).map_err(|e| format!("Invalid LaTeX in '{}': {}", source, e))?;
+            if mathml.contains(PARSE_ERROR) {
+                return Err(format!("Unsupported LaTeX command in '{}'", source));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace each LaTeX segment with MathML. Segments the renderer does not understand,
+/// and text whose delimiters do not pair up, are returned as written.
+pub fn render_mathml(text: &str) -> String {
+    let Ok(segments) = segments(text) else {
+        return text.to_string();
+    };
+
+    segments
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_string(),
+            Segment::Math { source, delimited, display } => match latex_to_mathml(source, display) {
+                Ok(mathml) if !mathml.contains(PARSE_ERROR) => mathml,
+                _ => delimited.to_string(),
+            },
+        })
+        .collect()
+}
+
+impl QuestionResponse {
+    /// Return the question's math in the requested format
+    pub fn render_math(mut self, format: MathFormat) -> Self {
+        if format == MathFormat::Source {
+            return self;
+        }
+
+        self.question = render_mathml(&self.question);
+        self.options = self.options.iter().map(|o| render_mathml(o)).collect();
+        self.explanation = self.explanation.map(|e| render_mathml(&e));
+        self.hints = self.hints.map(|hints| hints.iter().map(|h| render_mathml(h)).collect());
+        self.option_explanations = self.option_explanations.map(|explanations| {
+            explanations.into_iter().map(|(label, e)| (label, render_mathml(&e))).collect()
+        });
+        self
+    }
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// === Capability Models ===
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub base_path: &'static str,
+    // Media types accepted in the Accept header
+    pub response_formats: Vec<&'static str>,
+    pub features: BTreeMap<&'static str, bool>,
+    pub routes: Vec<RouteInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteInfo {
+    pub path: &'static str,
+    pub methods: Vec<&'static str>,
+}
@@ -2,21 +2,67 @@ mod enums;
 mod api_response;
 mod provider;
 mod certification;
+mod objective;
 mod topic;
 mod question;
 mod quiz;
 mod filters;
+mod admin;
+mod sync;
+mod analytics;
+mod search;
+mod import;
+mod import_export;
+mod catalog;
+mod embed;
+mod share;
+mod explanation;
+mod passback;
+mod public;
+mod redaction;
+mod custom_field;
+mod meta;
+mod outbox;
+mod variant;
+mod math;
+mod code;
+mod prerequisite;
+mod accessibility;
+mod apply;
+mod question_file;
 
 // Re-export everything
-pub use enums::*;
 pub use api_response::*;
 pub use provider::*;
 pub use certification::*;
+pub use objective::*;
 pub use topic::*;
 pub use question::*;
 pub use quiz::*;
-pub use filters::*;
+pub use admin::*;
+pub use sync::*;
+pub use analytics::*;
+pub use search::*;
+pub use import::*;
+pub use import_export::*;
+pub use catalog::*;
+pub use embed::*;
+pub use share::*;
+pub use explanation::*;
+pub use passback::*;
+pub use public::*;
+pub use redaction::*;
+pub use custom_field::*;
+pub use meta::*;
+pub use outbox::*;
+pub use variant::*;
+pub use math::*;
+pub use code::*;
+pub use prerequisite::*;
+pub use accessibility::*;
+pub use apply::*;
+pub use question_file::*;
 
 // Utility functions that don't belong to specific models
 mod utils;
-pub use utils::*;
\ No newline at end of file
+pub use utils::*;
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+// === Objective Models ===
+// An objective of a certification's official exam guide. Codes are dotted, and an
+// objective sits under the one whose code is its own minus the last part.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Objective {
+    pub id: Uuid,
+    pub certification_id: Uuid,
+    // e.g. "2.1"
+    pub code: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Questions mapped to an objective or any objective under it, with their answers
+#[derive(Debug, Serialize, FromRow)]
+pub struct ObjectiveCoverage {
+    pub objective_id: Uuid,
+    pub code: String,
+    pub title: String,
+    pub question_count: i64,
+    pub attempts: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertificationCoverage {
+    pub certification_id: Uuid,
+    // Questions in the certification's topics, and how many of them test an objective
+    pub question_count: i64,
+    pub mapped_question_count: i64,
+    pub objectives: Vec<ObjectiveCoverage>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct CreateObjective {
+    pub code: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+// The code is fixed, since the objectives under it are found by it
+#[derive(Debug, Deserialize)]
+pub struct UpdateObjective {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetQuestionObjectives {
+    // Replaces the question's objectives; `[]` clears them
+    pub objective_ids: Vec<Uuid>,
+}
+
+/// Whether `code` is a dotted objective code such as `2` or `2.1.a`: letters and digits,
+/// at most 50 characters, with no empty parts
+pub fn is_valid_objective_code(code: &str) -> bool {
+    code.len() <= 50 && code.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// The code of the objective `code` sits under, or `None` at the top level
+pub fn parent_objective_code(code: &str) -> Option<&str> {
+    code.rsplit_once('.').map(|(parent, _)| parent)
+}
+
+/// Exam-guide order of objective codes: part by part, numbers by value, so `2.10` comes
+/// after `2.9` and every objective directly after the ones it sits under
+pub fn compare_objective_codes(a: &str, b: &str) -> Ordering {
+    let key = |code: &str| -> Vec<(Option<u64>, String)> {
+        code.split('.').map(|part| (part.parse().ok(), part.to_ascii_lowercase())).collect()
+    };
+
+    key(a).cmp(&key(b))
+}
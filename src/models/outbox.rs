@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{prelude::FromRow, Type};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Event types written by the outbox triggers
+pub const EVENT_TYPES: [&str; 6] = [
+    "topic.created",
+    "topic.updated",
+    "topic.deleted",
+    "question.created",
+    "question.updated",
+    "question.deleted",
+];
+
+// === Outbox Models ===
+#[derive(Debug, Serialize, FromRow)]
+pub struct OutboxEvent {
+    pub seq: i64,
+    pub event_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    // The row after the change; only the id for deletions
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+// Body of a webhook delivery
+#[derive(Debug, Serialize)]
+pub struct WebhookBatch<'a> {
+    pub subscription_id: Uuid,
+    pub events: &'a [OutboxEvent],
+}
+
+// One event as automation platforms expect it: a flat object whose `id` is unique per
+// event, so a platform that deduplicates on `id` still sees every update of an entity
+#[derive(Debug, Serialize)]
+pub struct TriggerItem<'a> {
+    pub id: i64,
+    pub event_type: &'a str,
+    pub entity_type: &'a str,
+    pub entity_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub data: &'a serde_json::Value,
+}
+
+impl<'a> From<&'a OutboxEvent> for TriggerItem<'a> {
+    fn from(event: &'a OutboxEvent) -> Self {
+        TriggerItem {
+            id: event.seq,
+            event_type: &event.event_type,
+            entity_type: &event.entity_type,
+            entity_id: event.entity_id,
+            occurred_at: event.created_at,
+            data: &event.payload,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq, Default)]
+#[sqlx(type_name = "webhook_delivery", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDelivery {
+    // Up to 100 events per request as a `WebhookBatch`
+    #[default]
+    Batch,
+    // One request per event, as a `TriggerItem`
+    Event,
+}
+
+// The signing secret is write-only and never loaded here
+#[derive(Debug, Serialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Option<Vec<String>>,
+    pub delivery: WebhookDelivery,
+    pub active: bool,
+    pub last_seq: i64,
+    pub failures: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookSubscription {
+    pub url: String,
+    pub secret: String,
+    // Defaults to every event type
+    pub event_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub delivery: WebhookDelivery,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutboxEventQuery {
+    // Return events after this seq; defaults to the start of the retained log
+    pub after: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriggerQuery {
+    // Only events with a greater `id`; without it the newest events are returned
+    pub since: Option<i64>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[sqlx(type_name = "delivery_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+// === Grade Passback Models ===
+// The signing secret is write-only and never loaded here
+#[derive(Debug, Serialize, FromRow)]
+pub struct PassbackTarget {
+    pub id: Uuid,
+    pub topic_id: Uuid,
+    pub endpoint_url: String,
+    pub assignment_ref: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct PassbackDelivery {
+    pub id: Uuid,
+    pub target_id: Uuid,
+    pub session_id: Uuid,
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Score report sent to the LMS, named after LTI Assignment and Grade Services scores
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PassbackScore {
+    pub user_id: String,
+    pub assignment_id: String,
+    pub session_id: Uuid,
+    pub score_given: f64,
+    pub score_maximum: f64,
+    pub activity_progress: &'static str,
+    pub grading_progress: &'static str,
+    pub timestamp: DateTime<Utc>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct CreatePassbackTarget {
+    pub topic_id: Uuid,
+    pub endpoint_url: String,
+    pub secret: String,
+    pub assignment_ref: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PassbackDeliveryQuery {
+    pub status: Option<DeliveryStatus>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+// === Prerequisite Models ===
+// A question in a topic's prerequisite graph
+#[derive(Debug, Serialize, FromRow)]
+pub struct QuestionNode {
+    pub id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+}
+
+// `question_id` assumes knowledge tested by `prerequisite_id`
+#[derive(Debug, Serialize, FromRow, Clone, Copy)]
+pub struct PrerequisiteEdge {
+    pub prerequisite_id: Uuid,
+    pub question_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionGraph {
+    pub topic_id: Uuid,
+    pub nodes: Vec<QuestionNode>,
+    pub edges: Vec<PrerequisiteEdge>,
+    // Question ids with every prerequisite before the questions that need it
+    pub order: Vec<Uuid>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct SetPrerequisites {
+    // Replaces the question's prerequisites; `[]` clears them
+    pub prerequisite_ids: Vec<Uuid>,
+}
+
+/// Order `nodes` so every prerequisite comes before the questions that need it, otherwise
+/// keeping their given order as far as the edges allow. Edges to ids outside `nodes` are
+/// ignored. Returns `None` when the edges form a cycle.
+pub fn prerequisite_order(nodes: &[Uuid], edges: &[PrerequisiteEdge]) -> Option<Vec<Uuid>> {
+    let position: HashMap<Uuid, usize> = nodes.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut waiting_on = vec![0usize; nodes.len()];
+    let mut unlocks: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+
+    for edge in edges {
+        if let (Some(&before), Some(&after)) = (position.get(&edge.prerequisite_id), position.get(&edge.question_id)) {
+            waiting_on[after] += 1;
+            unlocks[before].push(after);
+        }
+    }
+
+    // Always take the earliest ready node, so unrelated questions keep their order
+    let mut ready: BTreeSet<usize> = (0..nodes.len()).filter(|&i| waiting_on[i] == 0).collect();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while let Some(next) = ready.pop_first() {
+        order.push(nodes[next]);
+        for &after in &unlocks[next] {
+            waiting_on[after] -= 1;
+            if waiting_on[after] == 0 {
+                ready.insert(after);
+            }
+        }
+    }
+
+    (order.len() == nodes.len()).then_some(order)
+}
+
+/// Reorder dealt questions so prerequisites come first, also through questions of the
+/// topic that were not dealt. The deal order is kept wherever the graph allows.
+pub fn order_dealt(dealt: Vec<Uuid>, topic_questions: &[Uuid], edges: &[PrerequisiteEdge]) -> Vec<Uuid> {
+    if edges.is_empty() {
+        return dealt;
+    }
+
+    // Questions left out of the deal go first: they only pass ordering on, and placing
+    // them early lets each dealt question surface as soon as its own prerequisites have
+    let in_deal: HashSet<Uuid> = dealt.iter().copied().collect();
+    let mut nodes: Vec<Uuid> = topic_questions.iter().filter(|id| !in_deal.contains(id)).copied().collect();
+    nodes.extend(&dealt);
+
+    match prerequisite_order(&nodes, edges) {
+        Some(order) => order.into_iter().filter(|id| in_deal.contains(id)).collect(),
+        // Cycles are rejected on write; fall back to the deal order if one slipped in
+        None => dealt,
+    }
+}
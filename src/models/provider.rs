@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// === Provider Models ===
+// An organisation that issues certifications, e.g. AWS
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Provider {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub website_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Provider listing entry
+#[derive(Debug, Serialize, FromRow)]
+pub struct ProviderWithCounts {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub provider: Provider,
+    pub certification_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProvider {
+    pub name: String,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub website_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProvider {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub website_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteProviderQuery {
+    // Delete the provider's certifications along with it; their topics are kept, unlinked
+    #[serde(default)]
+    pub cascade: bool,
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+use std::collections::HashMap;
+
+use super::question::{Question, QuestionType, Difficulty, serialize_options_as_map};
+use super::redaction::{Audience, RedactionPolicy};
+
+// === API Key Models ===
+// The key hash is never loaded here
+#[derive(Debug, Serialize, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub daily_quota: i32,
+    pub include_answers: bool,
+    pub include_explanations: bool,
+    // Question text served to the key carries an invisible per-key mark
+    pub watermark: bool,
+    pub active: bool,
+    // Set while the key is blocked for unusual read activity
+    pub blocked_until: Option<DateTime<Utc>>,
+    // Only filled when listing keys
+    #[sqlx(default)]
+    pub requests_today: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// What the key may see: the quiz view plus whatever its toggles unlock
+    pub fn policy(&self) -> RedactionPolicy {
+        RedactionPolicy {
+            correct_answer: self.include_answers,
+            explanation: self.include_explanations,
+            option_explanations: self.include_explanations,
+            ..RedactionPolicy::for_audience(Audience::Quiz)
+        }
+    }
+}
+
+// Returned once on creation; the key cannot be retrieved again
+#[derive(Debug, Serialize)]
+pub struct ApiKeyCreated {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+// === Public API Models ===
+// Question as served to API key holders; answers and explanations depend on the key
+#[derive(Debug, Serialize)]
+pub struct PublicQuestion {
+    pub id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+    #[serde(serialize_with = "serialize_options_as_map")]
+    pub options: Vec<String>,
+    pub question_type: QuestionType,
+    pub difficulty: Difficulty,
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct_answer: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_explanations: Option<HashMap<String, String>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PublicQuestion {
+    /// Options stay in stored order so answer labels line up
+    pub fn new(q: Question, policy: &RedactionPolicy) -> Self {
+        Self {
+            id: q.id,
+            question_number: q.question_number,
+            question: q.question,
+            options: q.options.0,
+            question_type: q.question_type,
+            difficulty: q.difficulty,
+            tags: q.tags.map(|t| t.0),
+            correct_answer: policy.correct_answer.then_some(q.correct_answer.0),
+            explanation: policy.explanation.then_some(q.explanation),
+            option_explanations: q.option_explanations.filter(|_| policy.option_explanations).map(|e| e.0),
+            updated_at: q.updated_at,
+        }
+    }
+}
+
+// === Watermark Models ===
+// A key whose mark was found in a pasted dump
+#[derive(Debug, Serialize, FromRow)]
+pub struct WatermarkCandidate {
+    pub api_key_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub active: bool,
+    #[sqlx(skip)]
+    pub marks: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WatermarkReport {
+    pub marks_found: usize,
+    // Marks that match no existing key (e.g. the key was deleted)
+    pub unmatched: usize,
+    // Most marks first
+    pub candidates: Vec<WatermarkCandidate>,
+}
+
+// === Scraping Alert Models ===
+#[derive(Debug, Serialize, FromRow)]
+pub struct ScrapingAlert {
+    pub id: Uuid,
+    pub key_id: Uuid,
+    pub key_name: String,
+    // "bank_scrape" or "answer_harvest"
+    pub kind: String,
+    pub detail: String,
+    pub blocked_until: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+// === Access Log Models ===
+// Views of one question by one key over the requested days
+#[derive(Debug, Serialize, FromRow)]
+pub struct QuestionAccess {
+    pub question_id: Uuid,
+    pub question_number: i32,
+    pub topic_slug: String,
+    pub key_id: Uuid,
+    pub key_name: String,
+    pub views: i64,
+    pub days: i64,
+    pub with_answers: bool,
+    pub first_viewed_at: DateTime<Utc>,
+    pub last_viewed_at: DateTime<Utc>,
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKey {
+    pub name: String,
+    // Defaults to 1000
+    pub daily_quota: Option<i32>,
+    #[serde(default)]
+    pub include_answers: bool,
+    #[serde(default)]
+    pub include_explanations: bool,
+    #[serde(default)]
+    pub watermark: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateApiKey {
+    pub daily_quota: Option<i32>,
+    pub include_answers: Option<bool>,
+    pub include_explanations: Option<bool>,
+    pub watermark: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TraceWatermark {
+    // Leaked content as found, e.g. copied from a brain-dump site
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScrapingAlertQuery {
+    pub key_id: Option<Uuid>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessLogQuery {
+    pub question_id: Option<Uuid>,
+    pub key_id: Option<Uuid>,
+    pub topic: Option<String>,
+    // UTC days, inclusive
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicQuestionQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
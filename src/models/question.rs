@@ -4,10 +4,13 @@ use sqlx::types::Json;
 use sqlx::Type;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use regex::Regex;
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
-use std::collections::hash_map::DefaultHasher;
+use sha2::{Digest, Sha256};
+
+use super::code::CodeBlock;
+use super::explanation::ExplanationEntry;
+use super::variant::{variant_renderer, VariableSpec};
+use super::utils::{option_index, option_label};
 
 
 // === Enums with proper serde attributes ===
@@ -19,7 +22,7 @@ pub enum QuestionType {
     Multiple,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq, Eq, Hash)]
 #[sqlx(type_name = "difficulty_level", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum Difficulty {
@@ -27,6 +30,53 @@ pub enum Difficulty {
     Medium,
     Hard,
 }
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+// Bloom's taxonomy level a question tests, lowest first
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq, Eq, Hash)]
+#[sqlx(type_name = "cognitive_level", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CognitiveLevel {
+    Remember,
+    Understand,
+    Apply,
+    Analyze,
+    Evaluate,
+    Create,
+}
+
+impl CognitiveLevel {
+    pub const ALL: [CognitiveLevel; 6] = [
+        CognitiveLevel::Remember,
+        CognitiveLevel::Understand,
+        CognitiveLevel::Apply,
+        CognitiveLevel::Analyze,
+        CognitiveLevel::Evaluate,
+        CognitiveLevel::Create,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CognitiveLevel::Remember => "remember",
+            CognitiveLevel::Understand => "understand",
+            CognitiveLevel::Apply => "apply",
+            CognitiveLevel::Analyze => "analyze",
+            CognitiveLevel::Evaluate => "evaluate",
+            CognitiveLevel::Create => "create",
+        }
+    }
+}
 // === Question Models ===
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Question {
@@ -39,7 +89,23 @@ pub struct Question {
     pub explanation: String,
     pub question_type: QuestionType,
     pub difficulty: Difficulty,
+    pub cognitive_level: Option<CognitiveLevel>,
     pub tags: Option<Json<Vec<String>>>, 
+    // Rationale per option label, shown after answering
+    pub option_explanations: Option<Json<HashMap<String, String>>>,
+    // Revealed one at a time during a quiz session
+    pub hints: Option<Json<Vec<String>>>,
+    // Values of the instance's custom fields, validated on write
+    pub metadata: Json<HashMap<String, serde_json::Value>>,
+    // Set on template questions: how each `{{placeholder}}` is generated
+    pub variables: Option<Json<HashMap<String, VariableSpec>>>,
+    // Code snippets shown with the question, outside the prose
+    pub code_blocks: Option<Json<Vec<CodeBlock>>>,
+    // Words in the question, options and explanation, and the time to read them
+    pub word_count: i32,
+    pub estimated_secs: i32,
+    pub explanation_upvotes: i32,
+    pub explanation_downvotes: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -47,24 +113,143 @@ pub struct Question {
 
 impl Question {
     /// Get option text by letter label
+    #[allow(dead_code)]
     pub fn get_option_by_label(&self, label: &str) -> Option<&String> {
-        let index = (label.chars().next()? as usize)
-            .checked_sub('A' as usize)?;
-        self.options.0.get(index)
+        self.options.0.get(option_index(label)?)
     }
     
     /// Validate if user's answer is correct
     pub fn is_correct_answer(&self, user_answers: &[String]) -> bool {
-        let correct = &self.correct_answer.0;
-        
-        // Same length
-        if user_answers.len() != correct.len() {
-            return false;
+        // Same labels in any order; a repeated label can't stand in for a missing one
+        let mut given: Vec<&String> = user_answers.iter().collect();
+        let mut correct: Vec<&String> = self.correct_answer.0.iter().collect();
+        given.sort();
+        correct.sort();
+
+        given == correct
+    }
+
+    /// Texts that may hold placeholders
+    pub fn template_texts(&self) -> Vec<&str> {
+        template_texts(
+            &self.question,
+            &self.explanation,
+            &self.options.0,
+            self.hints.as_ref().map(|h| &h.0),
+            self.option_explanations.as_ref().map(|e| &e.0),
+        )
+    }
+
+    /// The variant of a template question for `seed`, with every placeholder filled.
+    /// Other questions are returned as they are.
+    pub fn instantiate(mut self, seed: u64) -> Self {
+        let Some(variables) = self.variables.take() else {
+            return self;
+        };
+
+        {
+            let render = variant_renderer(&variables.0, seed);
+            self.question = render(&self.question);
+            self.explanation = render(&self.explanation);
+            self.options.0 = self.options.0.iter().map(|o| render(o)).collect();
+            if let Some(hints) = &mut self.hints {
+                hints.0 = hints.0.iter().map(|h| render(h)).collect();
+            }
+            if let Some(explanations) = &mut self.option_explanations {
+                explanations.0 = explanations.0.iter().map(|(label, e)| (label.clone(), render(e))).collect();
+            }
+        }
+
+        self.variables = Some(variables);
+        self
+    }
+
+    /// SHA-256 over the question's content fields, stable across environments
+    /// (ids and timestamps are left out so copies of the same question match)
+    pub fn checksum(&self) -> String {
+        let mut content = serde_json::json!([
+            self.question,
+            self.options.0,
+            self.correct_answer.0,
+            self.explanation,
+            self.question_type,
+            self.difficulty,
+            self.tags.as_ref().map(|t| &t.0),
+        ]);
+
+        // Only templates hash their variables, and only questions with code their code
+        // blocks, so other checksums stay as they were
+        if let (Some(variables), Some(content)) = (&self.variables, content.as_array_mut()) {
+            content.push(serde_json::json!(variables.0));
+        }
+        if let (Some(code_blocks), Some(content)) = (&self.code_blocks, content.as_array_mut()) {
+            content.push(serde_json::json!({ "code_blocks": code_blocks.0 }));
+        }
+        if let (Some(level), Some(content)) = (&self.cognitive_level, content.as_array_mut()) {
+            content.push(serde_json::json!({ "cognitive_level": level }));
+        }
+
+        hex::encode(Sha256::digest(content.to_string()))
+    }
+}
+
+/// Texts of a question that may hold template placeholders
+pub fn template_texts<'a>(
+    question: &'a str,
+    explanation: &'a str,
+    options: &'a [String],
+    hints: Option<&'a Vec<String>>,
+    option_explanations: Option<&'a HashMap<String, String>>,
+) -> Vec<&'a str> {
+    let mut texts = vec![question, explanation];
+    texts.extend(options.iter().map(String::as_str));
+    texts.extend(hints.into_iter().flatten().map(String::as_str));
+    texts.extend(option_explanations.into_iter().flat_map(|e| e.values()).map(String::as_str));
+    texts
+}
+
+/// Reading speed behind `estimated_secs`
+const WORDS_PER_MINUTE: usize = 200;
+
+// Length of a question's text, options and explanation, stored on write
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadingStats {
+    pub word_count: i32,
+    pub estimated_secs: i32,
+}
+
+impl ReadingStats {
+    pub fn of(question: &str, options: &[String], explanation: &str) -> Self {
+        let words = std::iter::once(question)
+            .chain(options.iter().map(String::as_str))
+            .chain(std::iter::once(explanation))
+            .map(|text| text.split_whitespace().count())
+            .sum::<usize>();
+
+        Self {
+            word_count: words.min(i32::MAX as usize) as i32,
+            estimated_secs: (words * 60).div_ceil(WORDS_PER_MINUTE).min(i32::MAX as usize) as i32,
+        }
+    }
+}
+
+/// Check that per-option explanations only refer to labels of existing options
+pub fn validate_option_explanations(options: &[String], explanations: &HashMap<String, String>) -> Result<(), String> {
+    let mut labels: Vec<&String> = explanations.keys().collect();
+    labels.sort();
+
+    for label in labels {
+        let valid = label.len() == 1 && option_index(label).is_some_and(|i| i < options.len());
+        if !valid {
+            return Err(format!(
+                "Option explanation '{}' does not match any of the {} options",
+                label,
+                options.len()
+            ));
         }
-        
-        // All correct answers present
-        user_answers.iter().all(|ans| correct.contains(ans))
     }
+
+    Ok(())
 }
 
 // For API responses - clean types without Json wrapper
@@ -76,19 +261,43 @@ pub struct QuestionResponse {
     pub question: String,
     #[serde(serialize_with = "serialize_options_as_map")]
     pub options: Vec<String>,
-    pub correct_answer: Vec<String>,
-    pub explanation: String,
+    // Optional fields below are left out when the audience's redaction policy hides them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct_answer: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
     pub question_type: QuestionType,
     pub difficulty: Difficulty,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cognitive_level: Option<CognitiveLevel>,
     pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_explanations: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hints: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, VariableSpec>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_blocks: Option<Vec<CodeBlock>>,
+    pub word_count: i32,
+    pub estimated_secs: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation_upvotes: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation_downvotes: Option<i32>,
+    // Approved community explanations, only loaded for a single question
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<ExplanationEntry>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 
 // Custom serializer to convert Vec<String> to {"A": "...", "B": "..."}
-fn serialize_options_as_map<S>(
-    options: &Vec<String>,
+pub(super) fn serialize_options_as_map<S>(
+    options: &[String],
     serializer: S,
 ) -> Result<S::Ok, S::Error>
 where
@@ -97,12 +306,7 @@ where
     let map: HashMap<String, String> = options
         .iter()
         .enumerate()
-        .map(|(i, text)| {
-            let label = std::char::from_u32(65 + i as u32)
-                .unwrap()
-                .to_string();
-            (label, text.clone())
-        })
+        .map(|(i, text)| (option_label(i), text.clone()))
         .collect();
     
     map.serialize(serializer)
@@ -121,17 +325,57 @@ impl From<Question> for QuestionResponse {
                 opt.sort();
                 opt
             },      
-            correct_answer: q.correct_answer.0, 
-            explanation: q.explanation,     
+            correct_answer: Some(q.correct_answer.0),
+            explanation: Some(q.explanation),
             question_type: q.question_type,
             difficulty: q.difficulty,
+            cognitive_level: q.cognitive_level,
             tags: q.tags.map(|t| t.0),    
+            option_explanations: q.option_explanations.map(|e| e.0),
+            hints: q.hints.map(|h| h.0),
+            metadata: q.metadata.0,
+            variables: q.variables.map(|v| v.0),
+            code_blocks: q.code_blocks.map(|c| c.0),
+            word_count: q.word_count,
+            estimated_secs: q.estimated_secs,
+            explanation_upvotes: Some(q.explanation_upvotes),
+            explanation_downvotes: Some(q.explanation_downvotes),
+            explanations: Vec::new(),
             created_at: q.created_at,
             updated_at: q.updated_at,
         }
     }
 }
 
+// === History Models ===
+#[derive(Debug, Serialize, FromRow)]
+pub struct QuestionRevision {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub changed_fields: Vec<String>,
+    // The full question row as it was before the change
+    pub previous: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Created,
+    Revised,
+    RegradeRequested,
+    RegradeCompleted,
+    RegradeFailed,
+}
+
+// One entry of a question's timeline; `details` depends on the kind
+#[derive(Debug, Serialize)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub kind: TimelineEventKind,
+    pub details: serde_json::Value,
+}
+
 // === Input Models - Vec<String> for easy JSON deserialization ===
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateQuestion {
@@ -143,7 +387,13 @@ pub struct CreateQuestion {
     pub explanation: String,
     pub question_type: QuestionType,
     pub difficulty: Option<Difficulty>,
+    pub cognitive_level: Option<CognitiveLevel>,
     pub tags: Option<Vec<String>>,
+    pub option_explanations: Option<HashMap<String, String>>,
+    pub hints: Option<Vec<String>>,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub variables: Option<HashMap<String, VariableSpec>>,
+    pub code_blocks: Option<Vec<CodeBlock>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -156,7 +406,22 @@ pub struct UpdateQuestion {
     pub explanation: Option<String>,
     pub question_type: Option<QuestionType>,
     pub difficulty: Option<Difficulty>,
+    pub cognitive_level: Option<CognitiveLevel>,
     pub tags: Option<Vec<String>>,
+    pub option_explanations: Option<HashMap<String, String>>,
+    pub hints: Option<Vec<String>>,
+    // Replaces all custom field values when given
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    // Replaces the template variables when given; `{}` turns a template back into a plain question
+    pub variables: Option<HashMap<String, VariableSpec>>,
+    // Replaces the code blocks when given; `[]` removes them
+    pub code_blocks: Option<Vec<CodeBlock>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VariantQuery {
+    // Session seed to preview; random when left out
+    pub seed: Option<i64>,
 }
 
 // === Bulk Operations ===
@@ -167,7 +432,7 @@ pub struct BulkCreateQuestions {
     pub questions: Vec<BulkQuestionData>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BulkQuestionData {
     pub question_number: i32,
     pub question: String,
@@ -176,7 +441,19 @@ pub struct BulkQuestionData {
     pub explanation: String,           
     pub question_type: QuestionType,
     pub difficulty: Option<Difficulty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cognitive_level: Option<CognitiveLevel>,
     pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option_explanations: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hints: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, VariableSpec>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_blocks: Option<Vec<CodeBlock>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -184,41 +461,15 @@ pub struct BulkCreateResponse {
     pub created: usize,
     pub failed: usize,
     pub errors: Vec<String>,
-}
-
-
-// === Utility Functions ===
-pub fn generate_slug(name: &str) -> String {
-    // Convert to lowercase
-    let slug = name.to_lowercase();
-    
-    // Replace spaces and special characters with hyphens
-    let slug = slug.replace(" ", "-");
-    
-    // Remove any remaining special characters except hyphens and alphanumeric
-    let re = Regex::new(r"[^a-z0-9-]").unwrap();
-    let slug = re.replace_all(&slug, "").to_string();
-    
-    // Remove consecutive hyphens
-    let re = Regex::new(r"-+").unwrap();
-    let slug = re.replace_all(&slug, "-").to_string();
-    
-    // Trim hyphens from start and end
-    let slug = slug.trim_matches('-').to_string();
-    
-    // If slug is empty, generate a hash-based one
-    if slug.is_empty() {
-        let mut hasher = DefaultHasher::new();
-        name.hash(&mut hasher);
-        format!("topic-{}", hasher.finish())
-    } else {
-        slug
-    }
+    // Import history entry, for fetching the failed rows as CSV
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_id: Option<Uuid>,
 }
 
 
 impl BulkQuestionData {
     /// Convert to CreateQuestion for reusing existing handler logic
+    #[allow(dead_code)]
     pub fn to_create_question(&self, topic_id: Uuid) -> CreateQuestion {
         CreateQuestion {
             topic_id,
@@ -229,37 +480,35 @@ impl BulkQuestionData {
             explanation: self.explanation.clone(),
             question_type: self.question_type.clone(),
             difficulty: self.difficulty.clone(),
+            cognitive_level: self.cognitive_level,
             tags: self.tags.clone(),
+            option_explanations: self.option_explanations.clone(),
+            hints: self.hints.clone(),
+            metadata: self.metadata.clone(),
+            variables: self.variables.clone(),
+            code_blocks: self.code_blocks.clone(),
         }
     }
 }
-#[derive(Debug, Serialize)]
-pub struct PaginatedResponse<T> {
-    pub items: Vec<T>,
-    pub pagination: PaginationMeta,
-}
 
-#[derive(Debug, Serialize)]
-pub struct PaginationMeta {
-    pub current_page: i64,
-    pub per_page: i64,
-    pub total_items: i64,
-    pub total_pages: i64,
-    pub has_next: bool,
-    pub has_prev: bool,
-}
-
-impl PaginationMeta {
-    pub fn new(current_page: i64, per_page: i64, total_items: i64) -> Self {
-        let total_pages = (total_items as f64 / per_page as f64).ceil() as i64;
-        
+impl From<Question> for BulkQuestionData {
+    fn from(q: Question) -> Self {
         Self {
-            current_page,
-            per_page,
-            total_items,
-            total_pages,
-            has_next: current_page < total_pages,
-            has_prev: current_page > 1,
+            question_number: q.question_number,
+            question: q.question,
+            options: q.options.0,
+            correct_answer: q.correct_answer.0,
+            explanation: q.explanation,
+            question_type: q.question_type,
+            difficulty: Some(q.difficulty),
+            cognitive_level: q.cognitive_level,
+            tags: q.tags.map(|t| t.0),
+            option_explanations: q.option_explanations.map(|e| e.0),
+            hints: q.hints.map(|h| h.0),
+            metadata: (!q.metadata.0.is_empty()).then_some(q.metadata.0),
+            variables: q.variables.map(|v| v.0),
+            code_blocks: q.code_blocks.map(|c| c.0),
         }
     }
-}
\ No newline at end of file
+}
+
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::question::{BulkQuestionData, CognitiveLevel, Difficulty, QuestionType};
+use super::variant::VariableSpec;
+use super::code::CodeBlock;
+
+/// Line that opens and closes each question's front matter
+const FENCE: &str = "---";
+
+/// Heading that ends the question text in a body and starts the explanation
+const EXPLANATION_HEADING: &str = "## Explanation";
+
+// === Question File Models ===
+// A question's YAML front matter. Maps are ordered so exports diff cleanly.
+#[derive(Debug, Serialize, Deserialize)]
+struct FrontMatter {
+    // Below 1 counts as missing, for `numbering=auto` or `append`
+    #[serde(default)]
+    question_number: i32,
+    question_type: QuestionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    difficulty: Option<Difficulty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cognitive_level: Option<CognitiveLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    options: Vec<String>,
+    correct_answer: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    option_explanations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hints: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variables: Option<BTreeMap<String, VariableSpec>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_blocks: Option<Vec<CodeBlock>>,
+    // Texts the Markdown body cannot hold as written, e.g. ones containing a `---` line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    question: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    explanation: Option<String>,
+}
+
+// One question read from a question file
+#[derive(Debug)]
+pub struct ParsedQuestion {
+    // 1-based line of the question's opening `---`
+    pub line: usize,
+    pub result: Result<BulkQuestionData, String>,
+}
+
+/// Whether the body can carry `text` as written: trimmed, and without lines the parser
+/// would take for a fence or the explanation heading
+fn fits_body(text: &str) -> bool {
+    text == text.trim()
+        && !text.contains('\r')
+        && text
+            .lines()
+            .all(|line| line.trim_end() != FENCE && !line.trim().eq_ignore_ascii_case(EXPLANATION_HEADING))
+}
+
+/// Write questions in the question file format: per question, YAML front matter between
+/// `---` lines, then the question text in Markdown and its explanation under `## Explanation`
+pub fn write_question_file(questions: &[BulkQuestionData]) -> Result<String, String> {
+    let mut out = String::new();
+
+    for q in questions {
+        let question_in_body = !q.question.is_empty() && fits_body(&q.question);
+        let explanation_in_body = fits_body(&q.explanation);
+
+        let front = FrontMatter {
+            question_number: q.question_number,
+            question_type: q.question_type.clone(),
+            difficulty: q.difficulty.clone(),
+            cognitive_level: q.cognitive_level,
+            tags: q.tags.clone(),
+            options: q.options.clone(),
+            correct_answer: q.correct_answer.clone(),
+            option_explanations: q.option_explanations.clone().map(|m| m.into_iter().collect()),
+            hints: q.hints.clone(),
+            metadata: q.metadata.clone().map(|m| m.into_iter().collect()),
+            variables: q.variables.clone().map(|m| m.into_iter().collect()),
+            code_blocks: q.code_blocks.clone(),
+            question: (!question_in_body).then(|| q.question.clone()),
+            explanation: (!explanation_in_body).then(|| q.explanation.clone()),
+        };
+
+        let yaml = serde_yaml::to_string(&front).map_err(|e| format!("Question {}: {}", q.question_number, e))?;
+
+        out.push_str(FENCE);
+        out.push('\n');
+        out.push_str(&yaml);
+        out.push_str(FENCE);
+        out.push('\n');
+        if question_in_body {
+            out.push_str(&q.question);
+            out.push('\n');
+        }
+        if explanation_in_body && !q.explanation.is_empty() {
+            out.push('\n');
+            out.push_str(EXPLANATION_HEADING);
+            out.push_str("\n\n");
+            out.push_str(&q.explanation);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Read a question file. The file's layout must hold together; each question then parses,
+/// or fails, on its own. With `strict`, front matter keys the format does not know fail
+/// their question instead of being ignored.
+pub fn parse_question_file(text: &str, strict: bool) -> Result<Vec<ParsedQuestion>, String> {
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line)).peekable();
+    let mut questions = Vec::new();
+
+    while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+
+    while let Some((line, fence)) = lines.next() {
+        if fence.trim_end() != FENCE {
+            return Err(format!("Line {}: expected '{}' to start a question", line, FENCE));
+        }
+
+        // Every line keeps its newline: block scalars may end in blank lines
+        let mut front = String::new();
+        loop {
+            match lines.next() {
+                Some((_, l)) if l.trim_end() == FENCE => break,
+                Some((_, l)) => {
+                    front.push_str(l);
+                    front.push('\n');
+                }
+                None => return Err(format!("Line {}: front matter is never closed with '{}'", line, FENCE)),
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some((_, l)) = lines.next_if(|(_, l)| l.trim_end() != FENCE) {
+            body.push(l);
+        }
+
+        questions.push(ParsedQuestion { line, result: parse_question(&front, &body, strict) });
+    }
+
+    Ok(questions)
+}
+
+fn parse_question(front: &str, body: &[&str], strict: bool) -> Result<BulkQuestionData, String> {
+    let mut unknown = Vec::new();
+    let front: FrontMatter = serde_ignored::deserialize(serde_yaml::Deserializer::from_str(front), |path| {
+        unknown.push(path.to_string())
+    })
+    .map_err(|e| format!("Invalid front matter: {}", e))?;
+
+    if strict && !unknown.is_empty() {
+        return Err(format!("Unknown front matter keys in strict mode: {}", unknown.join(", ")));
+    }
+
+    let heading = body.iter().position(|l| l.trim().eq_ignore_ascii_case(EXPLANATION_HEADING));
+    let (question_lines, explanation_lines) = match heading {
+        Some(at) => (&body[..at], &body[at + 1..]),
+        None => (body, &[][..]),
+    };
+    let body_question = question_lines.join("\n").trim().to_string();
+    let body_explanation = explanation_lines.join("\n").trim().to_string();
+
+    let question = match (front.question, body_question.is_empty()) {
+        (Some(_), false) => return Err("Question text is given both in the front matter and the body".to_string()),
+        (Some(question), true) => question,
+        (None, false) => body_question,
+        (None, true) => return Err("Question text is missing".to_string()),
+    };
+    let explanation = match (front.explanation, body_explanation.is_empty()) {
+        (Some(_), false) => return Err("Explanation is given both in the front matter and the body".to_string()),
+        (Some(explanation), true) => explanation,
+        (None, _) => body_explanation,
+    };
+
+    Ok(BulkQuestionData {
+        question_number: front.question_number,
+        question,
+        options: front.options,
+        correct_answer: front.correct_answer,
+        explanation,
+        question_type: front.question_type,
+        difficulty: front.difficulty,
+        cognitive_level: front.cognitive_level,
+        tags: front.tags,
+        option_explanations: front.option_explanations.map(|m| m.into_iter().collect()),
+        hints: front.hints,
+        metadata: front.metadata.map(|m| m.into_iter().collect()),
+        variables: front.variables.map(|m| m.into_iter().collect()),
+        code_blocks: front.code_blocks,
+    })
+}
@@ -0,0 +1,588 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::types::Json;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::api_response::ApiWarning;
+use super::code::CodeBlock;
+use super::question::{serialize_options_as_map, CognitiveLevel, Difficulty, Question, QuestionType};
+use super::utils::{option_index, option_label};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[sqlx(type_name = "session_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    InProgress,
+    Submitted,
+}
+
+// === Quiz Session Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct QuizSession {
+    pub id: Uuid,
+    pub topic_id: Uuid,
+    pub question_ids: Json<Vec<Uuid>>,
+    pub status: SessionStatus,
+    pub score: Option<i32>,
+    pub seed: Option<i64>,
+    pub hint_penalty: f64,
+    pub hints_used: i32,
+    pub time_limit_secs: Option<i32>,
+    pub pause_budget_secs: Option<i32>,
+    pub paused_at: Option<DateTime<Utc>>,
+    // Pause time credited so far, not counting a pause in progress
+    pub paused_secs: i32,
+    // Learner id in the launching LMS, used for grade passback
+    pub learner_ref: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl QuizSession {
+    /// Seconds of the current pause that stop the clock. Timed sessions only get credit
+    /// up to their remaining pause budget; any excess counts as active time.
+    pub fn current_pause_credit(&self, now: DateTime<Utc>) -> i32 {
+        let Some(paused_at) = self.paused_at else {
+            return 0;
+        };
+
+        let elapsed = (now - paused_at).num_seconds().max(0) as i32;
+        match self.pause_budget_secs {
+            Some(budget) => elapsed.min((budget - self.paused_secs).max(0)),
+            None => elapsed,
+        }
+    }
+
+    /// Seconds the session has been running, excluding credited pauses
+    pub fn active_secs(&self, now: DateTime<Utc>) -> i32 {
+        let end = self.submitted_at.unwrap_or(now);
+        let elapsed = (end - self.started_at).num_seconds().max(0) as i32;
+
+        (elapsed - self.paused_secs - self.current_pause_credit(now)).max(0)
+    }
+
+    /// Score minus the hint penalty, never below zero
+    pub fn adjusted_score(&self) -> Option<f64> {
+        self.score.map(|score| (score as f64 - self.hints_used as f64 * self.hint_penalty).max(0.0))
+    }
+
+    /// Whether a timed session has used up its time
+    pub fn is_out_of_time(&self, now: DateTime<Utc>) -> bool {
+        self.time_limit_secs.is_some_and(|limit| self.active_secs(now) >= limit)
+    }
+
+    /// Display order of a question's options: entry `i` is the original index shown at position `i`.
+    /// Derived from the session seed and question id, so it is identical on every request.
+    pub fn option_order(&self, question_id: Uuid, option_count: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..option_count).collect();
+        if let Some(seed) = self.seed {
+            let (hi, lo) = question_id.as_u64_pair();
+            let mut rng = ChaCha8Rng::seed_from_u64(seed as u64 ^ hi ^ lo);
+            order.shuffle(&mut rng);
+        }
+        order
+    }
+
+    /// Seed of the variant a template question takes in sessions with `seed`
+    pub fn variant_seed(seed: i64, question_id: Uuid) -> u64 {
+        let (hi, lo) = question_id.as_u64_pair();
+        (seed as u64 ^ hi ^ lo).rotate_left(32)
+    }
+
+    /// This session's variant of a template question; other questions are unchanged
+    pub fn instantiate(&self, question: Question) -> Question {
+        let seed = Self::variant_seed(self.seed.unwrap_or_default(), question.id);
+        question.instantiate(seed)
+    }
+
+    /// Present a question with its options in this session's shuffled order. Once the
+    /// session is submitted the answer key and explanations are included for review.
+    pub fn present(&self, question: Question) -> SessionQuestion {
+        let order = self.option_order(question.id, question.options.0.len());
+        let options = order
+            .iter()
+            .filter_map(|&i| question.options.0.get(i).cloned())
+            .collect();
+
+        let review = (self.status == SessionStatus::Submitted).then(|| {
+            let to_session_label = |label: &String| {
+                match option_index(label).and_then(|i| order.iter().position(|&o| o == i)) {
+                    Some(position) => option_label(position),
+                    None => label.clone(),
+                }
+            };
+
+            QuestionReview {
+                correct_answer: question.correct_answer.0.iter().map(to_session_label).collect(),
+                explanation: question.explanation.clone(),
+                option_explanations: question.option_explanations.as_ref().map(|explanations| {
+                    explanations.0.iter().map(|(label, text)| (to_session_label(label), text.clone())).collect()
+                }),
+            }
+        });
+
+        SessionQuestion {
+            id: question.id,
+            question_number: question.question_number,
+            question: question.question,
+            options,
+            question_type: question.question_type,
+            code_blocks: question.code_blocks.map(|c| c.0).unwrap_or_default(),
+            hints_available: question.hints.map_or(0, |h| h.0.len()),
+            hints_revealed: Vec::new(),
+            review,
+        }
+    }
+
+    /// Map labels picked from the shuffled options back to the stored option labels
+    pub fn to_canonical_labels(&self, question: &Question, selected: &[String]) -> Vec<String> {
+        let order = self.option_order(question.id, question.options.0.len());
+        selected
+            .iter()
+            .map(|label| match option_index(label).and_then(|i| order.get(i)) {
+                Some(&original) => option_label(original),
+                None => label.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Deal `count` questions from a pool in an order fixed by `seed`
+pub fn deal_questions(mut pool: Vec<Uuid>, seed: i64, count: usize) -> Vec<Uuid> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed as u64);
+    pool.shuffle(&mut rng);
+    pool.truncate(count);
+    pool
+}
+
+/// Deal `count` questions split across difficulties by the relative weights in `mix`.
+/// When a difficulty runs short the gap is filled from the other difficulties and a
+/// `MIX_SHORTFALL` warning describing the shortfall is returned alongside the dealt ids.
+pub fn deal_questions_with_mix(
+    pool: Vec<(Uuid, Difficulty)>,
+    mix: &HashMap<Difficulty, u32>,
+    seed: i64,
+    count: usize,
+) -> (Vec<Uuid>, Vec<ApiWarning>) {
+    let pool = pool.into_iter().map(|(id, d)| (id, Some(d))).collect();
+    let weights = Difficulty::ALL.map(|d| (mix.get(&d).copied().unwrap_or(0), d));
+    deal_by_weight(pool, &weights, Difficulty::as_str, ("difficulty_mix", "difficulties"), seed, count)
+}
+
+/// Deal `count` questions split across cognitive levels by the relative weights in `mix`,
+/// like `deal_questions_with_mix`. Questions without a level only fill shortfalls.
+pub fn deal_questions_with_level_mix(
+    pool: Vec<(Uuid, Option<CognitiveLevel>)>,
+    mix: &HashMap<CognitiveLevel, u32>,
+    seed: i64,
+    count: usize,
+) -> (Vec<Uuid>, Vec<ApiWarning>) {
+    let weights = CognitiveLevel::ALL.map(|l| (mix.get(&l).copied().unwrap_or(0), l));
+    deal_by_weight(pool, &weights, CognitiveLevel::as_str, ("cognitive_level_mix", "cognitive levels"), seed, count)
+}
+
+fn deal_by_weight<K: PartialEq + Clone>(
+    pool: Vec<(Uuid, Option<K>)>,
+    weights: &[(u32, K)],
+    name: fn(&K) -> &'static str,
+    (field, plural): (&'static str, &str),
+    seed: i64,
+    count: usize,
+) -> (Vec<Uuid>, Vec<ApiWarning>) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed as u64);
+    let count = count.min(pool.len());
+    let total: u64 = weights.iter().map(|&(w, _)| w as u64).sum();
+
+    // Largest remainder rounding so the targets always add up to `count`
+    let mut targets: Vec<(K, usize, u64)> = weights
+        .iter()
+        .map(|(weight, key)| {
+            let share = count as u64 * *weight as u64;
+            (key.clone(), (share / total.max(1)) as usize, share % total.max(1))
+        })
+        .collect();
+    let mut missing = count - targets.iter().map(|(_, n, _)| n).sum::<usize>();
+    let mut by_remainder: Vec<usize> = (0..targets.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(targets[i].2));
+    for i in by_remainder {
+        if missing == 0 {
+            break;
+        }
+        targets[i].1 += 1;
+        missing -= 1;
+    }
+
+    let mut dealt = Vec::with_capacity(count);
+    let mut leftovers = Vec::new();
+    let mut warnings = Vec::new();
+    for (key, target, _) in targets {
+        let mut bucket: Vec<Uuid> = pool.iter().filter(|(_, k)| k.as_ref() == Some(&key)).map(|(id, _)| *id).collect();
+        bucket.shuffle(&mut rng);
+
+        if bucket.len() < target {
+            warnings.push(ApiWarning::new("MIX_SHORTFALL", field, format!(
+                "Only {} {} questions available, {} requested; filled from other {}",
+                bucket.len(),
+                name(&key),
+                target,
+                plural
+            )));
+        }
+
+        let rest = bucket.split_off(target.min(bucket.len()));
+        dealt.extend(bucket);
+        leftovers.extend(rest);
+    }
+
+    leftovers.extend(pool.iter().filter(|(_, k)| k.is_none()).map(|(id, _)| *id));
+    leftovers.shuffle(&mut rng);
+    let shortfall = count - dealt.len();
+    dealt.extend(leftovers.into_iter().take(shortfall));
+    dealt.shuffle(&mut rng);
+
+    (dealt, warnings)
+}
+
+/// Swap dealt questions for shorter ones until their `estimated_secs` fit in `limit_secs`.
+/// `candidates` are the topic's questions as (id, estimated_secs, group); a question is only
+/// swapped for an undealt one of the same group, so a difficulty or level mix is kept. The
+/// longest question with a shorter replacement goes first and keeps its position. Returns a
+/// `TIME_LIMIT_TOO_SHORT` warning when even the shortest questions do not fit.
+pub fn fit_to_time_limit(mut dealt: Vec<Uuid>, candidates: &[(Uuid, i32, u8)], limit_secs: i64) -> (Vec<Uuid>, Option<ApiWarning>) {
+    let info: HashMap<Uuid, (i32, u8)> = candidates.iter().map(|&(id, secs, group)| (id, (secs, group))).collect();
+    let mut total: i64 = dealt.iter().filter_map(|id| info.get(id)).map(|&(secs, _)| secs as i64).sum();
+
+    let mut unused: Vec<(Uuid, i32, u8)> = candidates.iter().filter(|(id, _, _)| !dealt.contains(id)).copied().collect();
+    unused.sort_by_key(|&(_, secs, _)| secs);
+
+    while total > limit_secs {
+        let mut best: Option<(usize, usize, i32)> = None;
+        for (position, id) in dealt.iter().enumerate() {
+            let Some(&(secs, group)) = info.get(id) else {
+                continue;
+            };
+            if let Some(index) = unused.iter().position(|&(_, _, g)| g == group)
+                && unused[index].1 < secs
+                && best.is_none_or(|(_, _, saving)| secs - unused[index].1 > saving)
+            {
+                best = Some((position, index, secs - unused[index].1));
+            }
+        }
+
+        let Some((position, index, saving)) = best else {
+            break;
+        };
+
+        let (replacement, _, _) = unused.remove(index);
+        let replaced = std::mem::replace(&mut dealt[position], replacement);
+        let (secs, group) = info[&replaced];
+        let at = unused.partition_point(|&(_, s, _)| s <= secs);
+        unused.insert(at, (replaced, secs, group));
+        total -= saving as i64;
+    }
+
+    let warning = (total > limit_secs).then(|| ApiWarning::new("TIME_LIMIT_TOO_SHORT", "time_limit_secs", format!(
+        "Expected reading time of {}s exceeds the time limit of {}s even with the shortest questions",
+        total, limit_secs
+    )));
+
+    (dealt, warning)
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct SessionAnswer {
+    pub session_id: Uuid,
+    pub question_id: Uuid,
+    pub selected: Json<Vec<String>>,
+    pub confidence: Option<i16>,
+    pub saved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuizSessionResponse {
+    pub id: Uuid,
+    pub topic_id: Uuid,
+    pub question_ids: Vec<Uuid>,
+    pub status: SessionStatus,
+    pub score: Option<i32>,
+    pub seed: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learner_ref: Option<String>,
+    pub hints_used: i32,
+    pub hint_penalty: f64,
+    // Score minus the hint penalty, never below zero
+    pub adjusted_score: Option<f64>,
+    pub time_limit_secs: Option<i32>,
+    pub pause_budget_secs: Option<i32>,
+    pub paused: bool,
+    pub paused_secs: i32,
+    pub active_secs: i32,
+    pub time_remaining_secs: Option<i32>,
+    pub total_questions: usize,
+    pub started_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<QuizSession> for QuizSessionResponse {
+    fn from(s: QuizSession) -> Self {
+        let now = Utc::now();
+        let active_secs = s.active_secs(now);
+        let paused_secs = s.paused_secs + s.current_pause_credit(now);
+        let adjusted_score = s.adjusted_score();
+
+        Self {
+            id: s.id,
+            topic_id: s.topic_id,
+            total_questions: s.question_ids.0.len(),
+            question_ids: s.question_ids.0,
+            status: s.status,
+            score: s.score,
+            seed: s.seed,
+            learner_ref: s.learner_ref,
+            hints_used: s.hints_used,
+            hint_penalty: s.hint_penalty,
+            adjusted_score,
+            time_limit_secs: s.time_limit_secs,
+            pause_budget_secs: s.pause_budget_secs,
+            paused: s.paused_at.is_some(),
+            paused_secs,
+            active_secs,
+            time_remaining_secs: s.time_limit_secs.map(|limit| (limit - active_secs).max(0)),
+            started_at: s.started_at,
+            submitted_at: s.submitted_at,
+            updated_at: s.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SavedAnswerResponse {
+    pub question_id: Uuid,
+    pub selected: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<i16>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl From<SessionAnswer> for SavedAnswerResponse {
+    fn from(a: SessionAnswer) -> Self {
+        Self {
+            question_id: a.question_id,
+            selected: a.selected.0,
+            confidence: a.confidence,
+            saved_at: a.saved_at,
+        }
+    }
+}
+
+// Question as shown while a session is running - no answers or explanations
+#[derive(Debug, Serialize)]
+pub struct SessionQuestion {
+    pub id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+    #[serde(serialize_with = "serialize_options_as_map")]
+    pub options: Vec<String>,
+    pub question_type: QuestionType,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub code_blocks: Vec<CodeBlock>,
+    #[serde(skip_serializing_if = "is_zero")]
+    pub hints_available: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hints_revealed: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review: Option<QuestionReview>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+// Answer key for a submitted session, using the session's shuffled option labels
+#[derive(Debug, Serialize)]
+pub struct QuestionReview {
+    pub correct_answer: Vec<String>,
+    pub explanation: String,
+    // Why each option is right or wrong
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_explanations: Option<HashMap<String, String>>,
+}
+
+// Full saved state, enough for a client to resume after a disconnect
+#[derive(Debug, Serialize)]
+pub struct QuizSessionState {
+    pub session: QuizSessionResponse,
+    pub questions: Vec<SessionQuestion>,
+    pub answers: Vec<SavedAnswerResponse>,
+    pub navigation: SessionNavigation,
+}
+
+// Exam-style navigator: what has been answered or marked, in dealt order
+#[derive(Debug, Serialize)]
+pub struct SessionNavigation {
+    pub answered: usize,
+    pub marked: usize,
+    // Neither answered nor marked yet
+    pub unseen: usize,
+    pub questions: Vec<QuestionNavigation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionNavigation {
+    pub question_id: Uuid,
+    pub answered: bool,
+    pub marked: bool,
+}
+
+impl SessionNavigation {
+    pub fn new(question_ids: &[Uuid], answered: &[Uuid], marked: &[Uuid]) -> Self {
+        let questions: Vec<QuestionNavigation> = question_ids
+            .iter()
+            .map(|id| QuestionNavigation {
+                question_id: *id,
+                answered: answered.contains(id),
+                marked: marked.contains(id),
+            })
+            .collect();
+
+        Self {
+            answered: questions.iter().filter(|q| q.answered).count(),
+            marked: questions.iter().filter(|q| q.marked).count(),
+            unseen: questions.iter().filter(|q| !q.answered && !q.marked).count(),
+            questions,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionMark {
+    pub question_id: Uuid,
+    pub marked: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchAnswerResult {
+    pub question_id: Uuid,
+    #[serde(flatten)]
+    pub status: BatchAnswerStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchAnswerStatus {
+    Accepted,
+    // A newer draft was already stored
+    Stale,
+    Rejected { error: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct HintResponse {
+    pub question_id: Uuid,
+    pub hint: String,
+    // 1-based position of this hint
+    pub hint_number: i32,
+    pub hints_remaining: i32,
+    // Hints revealed across the whole session
+    pub session_hints_used: i32,
+}
+
+// === Generated Quiz Models ===
+// A one-off random set of questions, outside any session and never graded
+#[derive(Debug, Serialize)]
+pub struct GeneratedQuiz {
+    pub topic_id: Uuid,
+    pub questions: Vec<GeneratedQuestion>,
+}
+
+// Question with its options shuffled and no answers or explanations
+#[derive(Debug, Serialize)]
+pub struct GeneratedQuestion {
+    pub id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+    #[serde(serialize_with = "serialize_options_as_map")]
+    pub options: Vec<String>,
+    pub question_type: QuestionType,
+    pub difficulty: Difficulty,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub code_blocks: Vec<CodeBlock>,
+}
+
+impl GeneratedQuestion {
+    /// A random variant of `question` with its options in a random order
+    pub fn generate<R: rand::Rng>(question: Question, rng: &mut R) -> Self {
+        let mut question = question.instantiate(rng.r#gen());
+        question.options.0.shuffle(rng);
+
+        GeneratedQuestion {
+            id: question.id,
+            question_number: question.question_number,
+            question: question.question,
+            options: question.options.0,
+            question_type: question.question_type,
+            difficulty: question.difficulty,
+            code_blocks: question.code_blocks.map(|c| c.0).unwrap_or_default(),
+        }
+    }
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct GenerateQuizQuery {
+    pub topic_id: Uuid,
+    pub count: Option<i64>,
+    pub difficulty: Option<Difficulty>,
+    pub question_type: Option<QuestionType>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateQuizSession {
+    pub topic_id: Uuid,
+    pub question_count: Option<i64>,
+    // Reuse a previous session's seed to reproduce its question order and option shuffle
+    pub seed: Option<i64>,
+    // Relative weight per difficulty, e.g. {"easy": 30, "medium": 50, "hard": 20}
+    pub difficulty_mix: Option<HashMap<Difficulty, u32>>,
+    // Relative weight per cognitive level, e.g. {"remember": 20, "apply": 50, "analyze": 30};
+    // cannot be combined with difficulty_mix
+    pub cognitive_level_mix: Option<HashMap<CognitiveLevel, u32>>,
+    // Points deducted per hint revealed, 0 to 1; defaults to no penalty
+    pub hint_penalty: Option<f64>,
+    // Makes this a timed mock; untimed sessions can pause without limit
+    pub time_limit_secs: Option<i32>,
+    // Total pause time allowed in a timed mock, defaults to none
+    pub pause_budget_secs: Option<i32>,
+    // Learner id in the launching LMS; submitted scores are passed back to it
+    pub learner_ref: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveAnswer {
+    pub selected: Vec<String>,
+    // How sure the learner is, 1 (guessing) to 5 (certain)
+    pub confidence: Option<i16>,
+    // Client-side timestamp of the edit; the newest write wins
+    pub saved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchSaveAnswers {
+    pub answers: Vec<BatchAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchAnswer {
+    pub question_id: Uuid,
+    #[serde(flatten)]
+    pub answer: SaveAnswer,
+}
@@ -0,0 +1,109 @@
+use serde::Deserialize;
+
+use super::code::CodeFormat;
+use super::math::MathFormat;
+use super::question::QuestionResponse;
+
+// === Redaction Models ===
+// Who a question is being shown to
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Audience {
+    // A learner answering it: no answers, explanations or hints up front
+    Quiz,
+    // A learner reviewing it after answering
+    Review,
+    // Content authors and admins see everything
+    #[default]
+    Admin,
+}
+
+// Which optional question fields a response may include
+#[derive(Debug, Clone, Copy)]
+pub struct RedactionPolicy {
+    pub correct_answer: bool,
+    pub explanation: bool,
+    pub option_explanations: bool,
+    pub hints: bool,
+    pub explanation_votes: bool,
+    pub community_explanations: bool,
+    // How a template question's placeholders are generated
+    pub variables: bool,
+}
+
+impl RedactionPolicy {
+    pub fn for_audience(audience: Audience) -> Self {
+        match audience {
+            Audience::Quiz => Self {
+                correct_answer: false,
+                explanation: false,
+                option_explanations: false,
+                hints: false,
+                explanation_votes: false,
+                community_explanations: false,
+                variables: false,
+            },
+            Audience::Review => Self {
+                correct_answer: true,
+                explanation: true,
+                option_explanations: true,
+                hints: true,
+                explanation_votes: true,
+                community_explanations: true,
+                variables: false,
+            },
+            Audience::Admin => Self {
+                correct_answer: true,
+                explanation: true,
+                option_explanations: true,
+                hints: true,
+                explanation_votes: true,
+                community_explanations: true,
+                variables: true,
+            },
+        }
+    }
+}
+
+impl QuestionResponse {
+    /// Drop every field the policy does not allow
+    pub fn redact(mut self, policy: &RedactionPolicy) -> Self {
+        if !policy.correct_answer {
+            self.correct_answer = None;
+        }
+        if !policy.explanation {
+            self.explanation = None;
+        }
+        if !policy.option_explanations {
+            self.option_explanations = None;
+        }
+        if !policy.hints {
+            self.hints = None;
+        }
+        if !policy.explanation_votes {
+            self.explanation_upvotes = None;
+            self.explanation_downvotes = None;
+        }
+        if !policy.community_explanations {
+            self.explanations.clear();
+        }
+        if !policy.variables {
+            self.variables = None;
+        }
+        self
+    }
+}
+
+// === Input Models ===
+#[derive(Debug, Deserialize)]
+pub struct ViewQuery {
+    // Defaults to admin, the full question
+    #[serde(default)]
+    pub view: Audience,
+    // Defaults to source, the LaTeX as written
+    #[serde(default)]
+    pub math: MathFormat,
+    // Defaults to source, code blocks without highlighted HTML
+    #[serde(default)]
+    pub code: CodeFormat,
+}
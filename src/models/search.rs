@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use super::question::{Question, QuestionResponse};
+use super::topic::Topic;
+use super::code::CodeFormat;
+use super::math::MathFormat;
+use super::redaction::Audience;
+
+// === Search Suggestion Models ===
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    pub q: String,
+    // Suggestions per group, defaults to 5
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopicSuggestion {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TermSuggestion {
+    pub value: String,
+    pub question_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchSuggestions {
+    pub topics: Vec<TopicSuggestion>,
+    pub tags: Vec<TermSuggestion>,
+    pub terms: Vec<TermSuggestion>,
+}
+
+// === Unified Search Models ===
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    // Results per group, defaults to 10
+    pub limit: Option<i64>,
+    // Include matched fragments of question hits
+    #[serde(default)]
+    pub highlight: bool,
+    #[serde(default)]
+    pub view: Audience,
+    #[serde(default)]
+    pub math: MathFormat,
+    #[serde(default)]
+    pub code: CodeFormat,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopicSearchHit {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub topic: Topic,
+    pub score: f64,
+}
+
+#[derive(Debug, FromRow)]
+pub struct ScoredQuestion {
+    #[sqlx(flatten)]
+    pub question: Question,
+    pub score: f64,
+    // ts_headline excerpts, only selected when highlights were requested
+    pub question_headline: Option<String>,
+    pub explanation_headline: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionSearchHit {
+    #[serde(flatten)]
+    pub question: QuestionResponse,
+    pub score: f64,
+    // HTML-escaped fragments with matches wrapped in <mark>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Vec<String>>,
+}
+
+impl From<ScoredQuestion> for QuestionSearchHit {
+    fn from(scored: ScoredQuestion) -> Self {
+        QuestionSearchHit {
+            question: QuestionResponse::from(scored.question),
+            score: scored.score,
+            highlights: highlights(scored.question_headline, scored.explanation_headline),
+        }
+    }
+}
+
+// === Substring Search Models ===
+#[derive(Debug, Deserialize)]
+pub struct HighlightQuery {
+    // Include matched fragments of each question
+    #[serde(default)]
+    pub highlight: bool,
+}
+
+#[derive(Debug, FromRow)]
+pub struct HighlightedQuestion {
+    #[sqlx(flatten)]
+    pub question: Question,
+    // ts_headline excerpts, only selected when highlights were requested
+    pub question_headline: Option<String>,
+    pub explanation_headline: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionMatch {
+    #[serde(flatten)]
+    pub question: QuestionResponse,
+    // HTML-escaped fragments with matches wrapped in <mark>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Vec<String>>,
+}
+
+impl From<HighlightedQuestion> for QuestionMatch {
+    fn from(highlighted: HighlightedQuestion) -> Self {
+        QuestionMatch {
+            question: QuestionResponse::from(highlighted.question),
+            highlights: highlights(highlighted.question_headline, highlighted.explanation_headline),
+        }
+    }
+}
+
+/// The escaped headline fragments that contain a match, or `None` when none were selected
+fn highlights(question_headline: Option<String>, explanation_headline: Option<String>) -> Option<Vec<String>> {
+    let headlines = [question_headline, explanation_headline];
+
+    headlines.iter().any(Option::is_some).then(|| {
+        headlines
+            .iter()
+            .flatten()
+            .filter(|fragment| fragment.contains(MARK_START))
+            .map(|fragment| escape_fragment(fragment))
+            .collect()
+    })
+}
+
+const MARK_START: &str = "<mark>";
+const MARK_END: &str = "</mark>";
+
+/// Escape the question text in a headline while keeping the <mark> tags ts_headline added
+fn escape_fragment(fragment: &str) -> String {
+    fragment
+        .split(MARK_START)
+        .map(|part| {
+            part.split(MARK_END)
+                .map(escape_html)
+                .collect::<Vec<_>>()
+                .join(MARK_END)
+        })
+        .collect::<Vec<_>>()
+        .join(MARK_START)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Result groups, each sorted by descending score
+#[derive(Debug, Serialize)]
+pub struct SearchResults {
+    pub query: String,
+    pub topics: Vec<TopicSearchHit>,
+    pub questions: Vec<QuestionSearchHit>,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use sqlx::Type;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[sqlx(type_name = "share_target", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ShareTarget {
+    Question,
+    Topic,
+}
+
+// === Share Link Models ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub code: String,
+    pub target_type: ShareTarget,
+    pub target_id: Uuid,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub access_count: i32,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShareLink {
+    pub target_type: ShareTarget,
+    pub target_id: Uuid,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShareLinkResponse {
+    #[serde(flatten)]
+    pub link: ShareLink,
+    // Short URL to hand out, e.g. http://localhost:3000/s/AB3X9Q
+    pub short_url: String,
+    // Page on the public site the short URL redirects to
+    pub target_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QrQuery {
+    // Pixels per QR module, defaults to 8
+    pub scale: Option<usize>,
+}
@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::question::{BulkQuestionData, Difficulty, Question, QuestionType};
+
+// === Export Manifest ===
+// Questions are identified by topic slug and question number, since ids differ between instances
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub generated_at: DateTime<Utc>,
+    pub topics: Vec<TopicManifest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TopicManifest {
+    pub slug: String,
+    pub name: String,
+    pub questions: Vec<QuestionChecksum>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionChecksum {
+    pub question_number: i32,
+    pub checksum: String,
+}
+
+// Full content of one topic, with options in stored order so it can be re-imported as-is
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TopicExport {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub questions: Vec<BulkQuestionData>,
+}
+
+// How a topic export is returned
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    // The question file format accepted by `POST /import/yaml`
+    Yaml,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+// === Diff Models ===
+// What `POST /admin/diff` compares against: a manifest, or the instance to fetch it from
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DiffSource {
+    // Base URL of the other instance, e.g. https://staging.example.com; the key is sent as X-Api-Key
+    Remote { url: String, api_key: Option<String> },
+    Manifest(ExportManifest),
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestDiff {
+    pub topics: Vec<TopicDiff>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopicDiff {
+    pub slug: String,
+    pub added: Vec<i32>,
+    pub removed: Vec<i32>,
+    pub changed: Vec<i32>,
+}
+
+impl ExportManifest {
+    /// Compare this manifest against `other`: `added` questions exist only here,
+    /// `removed` only in `other`, `changed` in both with different content.
+    /// Topics without differences are left out.
+    pub fn diff(&self, other: &ExportManifest) -> ManifestDiff {
+        let ours = self.checksums_by_topic();
+        let theirs = other.checksums_by_topic();
+        let empty = BTreeMap::new();
+
+        let slugs: BTreeSet<&str> = ours.keys().chain(theirs.keys()).copied().collect();
+
+        let topics: Vec<TopicDiff> = slugs
+            .into_iter()
+            .map(|slug| {
+                let ours = ours.get(slug).unwrap_or(&empty);
+                let theirs = theirs.get(slug).unwrap_or(&empty);
+
+                TopicDiff {
+                    slug: slug.to_string(),
+                    added: ours.keys().filter(|n| !theirs.contains_key(n)).copied().collect(),
+                    removed: theirs.keys().filter(|n| !ours.contains_key(n)).copied().collect(),
+                    changed: ours
+                        .iter()
+                        .filter(|(n, sum)| theirs.get(n).is_some_and(|other| other != *sum))
+                        .map(|(n, _)| *n)
+                        .collect(),
+                }
+            })
+            .filter(|t| !(t.added.is_empty() && t.removed.is_empty() && t.changed.is_empty()))
+            .collect();
+
+        ManifestDiff {
+            added: topics.iter().map(|t| t.added.len()).sum(),
+            removed: topics.iter().map(|t| t.removed.len()).sum(),
+            changed: topics.iter().map(|t| t.changed.len()).sum(),
+            topics,
+        }
+    }
+
+    fn checksums_by_topic(&self) -> BTreeMap<&str, BTreeMap<i32, &str>> {
+        self.topics
+            .iter()
+            .map(|t| {
+                let questions = t
+                    .questions
+                    .iter()
+                    .map(|q| (q.question_number, q.checksum.as_str()))
+                    .collect();
+                (t.slug.as_str(), questions)
+            })
+            .collect()
+    }
+}
+
+// === Offline Bundles ===
+pub const BUNDLE_VERSION: u32 = 2;
+
+#[derive(Debug, Serialize)]
+pub struct OfflineBundle {
+    pub version: u32,
+    #[serde(flatten)]
+    pub topic: BundleTopic,
+    // Database time the bundle was read at; pass it back as `since` for the next delta
+    pub generated_at: DateTime<Utc>,
+    // Set for delta bundles: only questions changed after this time are included
+    pub since: Option<DateTime<Utc>>,
+    // Delta bundles only: questions deleted since `since`, in any topic
+    pub deleted: Vec<DeletedRecord>,
+}
+
+// Every topic linked to a certification, read from one snapshot. Topics no longer
+// listed have been unlinked since the last bundle.
+#[derive(Debug, Serialize)]
+pub struct CertificationBundle {
+    pub version: u32,
+    pub certification_id: Uuid,
+    pub certification_slug: String,
+    pub certification_name: String,
+    pub generated_at: DateTime<Utc>,
+    pub since: Option<DateTime<Utc>>,
+    pub topics: Vec<BundleTopic>,
+    pub deleted: Vec<DeletedRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleTopic {
+    pub topic_id: Uuid,
+    pub topic_slug: String,
+    pub topic_name: String,
+    pub questions: Vec<BundleQuestion>,
+    // Delta bundles only: questions moved to another topic since `since`
+    pub moved_out: Vec<Uuid>,
+}
+
+// Everything needed to practice offline, options in stored order
+#[derive(Debug, Serialize)]
+pub struct BundleQuestion {
+    pub id: Uuid,
+    pub question_number: i32,
+    pub question: String,
+    pub options: Vec<String>,
+    pub correct_answer: Vec<String>,
+    pub explanation: String,
+    pub question_type: QuestionType,
+    pub difficulty: Difficulty,
+    pub tags: Option<Vec<String>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Question> for BundleQuestion {
+    fn from(q: Question) -> Self {
+        Self {
+            id: q.id,
+            question_number: q.question_number,
+            question: q.question,
+            options: q.options.0,
+            correct_answer: q.correct_answer.0,
+            explanation: q.explanation,
+            question_type: q.question_type,
+            difficulty: q.difficulty,
+            tags: q.tags.map(|t| t.0),
+            updated_at: q.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BundleQuery {
+    pub since: Option<DateTime<Utc>>,
+}
+
+// === Offline Result Sync ===
+#[derive(Debug, Deserialize)]
+pub struct SyncResults {
+    pub sessions: Vec<SyncSessionRecord>,
+}
+
+// A practice session recorded offline; `id` is generated by the client so retries are idempotent
+#[derive(Debug, Deserialize)]
+pub struct SyncSessionRecord {
+    pub id: Uuid,
+    pub topic_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    // Unfinished sessions are stored in progress and can be resumed online
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub answers: Vec<SyncAnswerRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncAnswerRecord {
+    pub question_id: Uuid,
+    pub selected: Vec<String>,
+    pub answered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResultsResponse {
+    pub accepted: Vec<Uuid>,
+    pub duplicates: Vec<Uuid>,
+    pub rejected: Vec<RejectedRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RejectedRecord {
+    pub id: Uuid,
+    pub reason: String,
+}
+
+// === Delta Sync ===
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct DeletedRecord {
+    pub id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeletionsQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub entity_type: Option<String>,
+}
+
+// Rows changed since the client's last sync plus tombstones for rows deleted since then.
+// Clients pass `synced_at` back as `updated_since` on the next call.
+#[derive(Debug, Serialize)]
+pub struct DeltaResponse<T> {
+    pub items: Vec<T>,
+    pub deleted: Vec<DeletedRecord>,
+    pub synced_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ListResponse<F, T> {
+    Full(F),
+    Delta(DeltaResponse<T>),
+}
@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::question::QuestionResponse;
+use super::utils::deserialize_present;
+
 
 
 
@@ -12,6 +16,9 @@ pub struct Topic {
     pub name: String,
     pub slug: String,
     pub description: Option<String>,
+    // Approved for the public read-only API
+    pub is_public: bool,
+    pub certification_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -21,6 +28,9 @@ pub struct CreateTopic {
     pub name: String,
     pub slug: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+    pub certification_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,4 +38,108 @@ pub struct UpdateTopic {
     pub name: Option<String>,
     pub description: Option<String>,
     pub slug: Option<String>,
+    pub is_public: Option<bool>,
+    // `null` unlinks the topic from its certification; leaving it out keeps the link
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub certification_id: Option<Option<Uuid>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkCreateTopics {
+    pub topics: Vec<CreateTopic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkCreateTopicsResponse {
+    pub created: usize,
+    pub duplicates: usize,
+    pub rejected: usize,
+    pub results: Vec<BulkTopicResult>,
+}
+
+// Outcome of one entry, in request order
+#[derive(Debug, Serialize)]
+pub struct BulkTopicResult {
+    pub index: usize,
+    pub slug: String,
+    #[serde(flatten)]
+    pub status: BulkTopicStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BulkTopicStatus {
+    Created { topic: Topic },
+    // The slug already exists, or appeared earlier in the same request
+    Duplicate { existing_id: Uuid },
+    Rejected { error: String },
+}
+
+// Topic listing entry with aggregated question counts
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopicWithCounts {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub topic: Topic,
+    pub question_count: i64,
+    #[sqlx(flatten)]
+    pub difficulty_counts: DifficultyCounts,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DifficultyCounts {
+    #[sqlx(rename = "easy_count")]
+    pub easy: i64,
+    #[sqlx(rename = "medium_count")]
+    pub medium: i64,
+    #[sqlx(rename = "hard_count")]
+    pub hard: i64,
+}
+
+// Topic page payload: the topic and all of its questions in one response
+#[derive(Debug, Serialize)]
+pub struct TopicWithQuestions {
+    #[serde(flatten)]
+    pub topic: Topic,
+    pub questions: Vec<QuestionResponse>,
+}
+
+// Everything that is deleted along with a topic
+#[derive(Debug, Serialize, FromRow)]
+pub struct TopicDependencies {
+    pub topic_id: Uuid,
+    pub question_count: i64,
+    pub session_count: i64,
+    pub submitted_session_count: i64,
+    pub answer_count: i64,
+    pub regrade_job_count: i64,
+    pub freeze_count: i64,
+    #[serde(skip)]
+    pub topic_updated_at: DateTime<Utc>,
+    // Pass back as `?confirm=` when deleting; changes whenever any count or the topic changes
+    #[sqlx(skip)]
+    pub confirmation_token: String,
+}
+
+impl TopicDependencies {
+    pub fn token(&self) -> String {
+        let report = format!(
+            "{}:{}:{}:{}:{}:{}:{}:{}",
+            self.topic_id,
+            self.question_count,
+            self.session_count,
+            self.submitted_session_count,
+            self.answer_count,
+            self.regrade_job_count,
+            self.freeze_count,
+            self.topic_updated_at.timestamp_micros(),
+        );
+
+        hex::encode(Sha256::digest(report))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteTopicQuery {
+    pub confirm: Option<String>,
 }
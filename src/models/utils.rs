@@ -1,20 +1,54 @@
 use regex::Regex;
+use serde::{Deserialize, Deserializer};
 use std::hash::{Hash, Hasher};
 use std::collections::hash_map::DefaultHasher;
 
-pub fn generateSlug(name: &str) -> String {
-    let slug = name.to_lowercase().replace(" ", "-");
+pub fn generate_slug(name: &str) -> String {
+    // Convert to lowercase
+    let slug = name.to_lowercase();
+    
+    // Replace spaces and special characters with hyphens
+    let slug = slug.replace(" ", "-");
+    
+    // Remove any remaining special characters except hyphens and alphanumeric
     let re = Regex::new(r"[^a-z0-9-]").unwrap();
     let slug = re.replace_all(&slug, "").to_string();
+    
+    // Remove consecutive hyphens
     let re = Regex::new(r"-+").unwrap();
     let slug = re.replace_all(&slug, "-").to_string();
+    
+    // Trim hyphens from start and end
     let slug = slug.trim_matches('-').to_string();
     
+    // If slug is empty, generate a hash-based one
     if slug.is_empty() {
         let mut hasher = DefaultHasher::new();
         name.hash(&mut hasher);
-        format!("slug-{}", hasher.finish())
+        format!("topic-{}", hasher.finish())
     } else {
         slug
     }
-}
\ No newline at end of file
+}
+
+/// Letter label for an option index: 0 -> "A", 1 -> "B", ...
+pub fn option_label(index: usize) -> String {
+    std::char::from_u32(65 + index as u32)
+        .unwrap()
+        .to_string()
+}
+
+/// Option index for a letter label: "A" -> 0, "B" -> 1, ...
+pub fn option_index(label: &str) -> Option<usize> {
+    (label.chars().next()? as usize).checked_sub('A' as usize)
+}
+
+/// For `Option<Option<T>>` fields with `#[serde(default)]`: a present `null` becomes
+/// `Some(None)`, so "clear this" can be told apart from a field left out (`None`)
+pub fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
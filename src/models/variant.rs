@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
+use std::sync::LazyLock;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use regex::Regex;
+
+/// Most variables a template question may declare
+const MAX_VARIABLES: usize = 20;
+
+/// Fields a `cidr` variable exposes as `{{name.field}}`; `{{name}}` is the block itself
+const CIDR_FIELDS: [&str; 8] = ["network", "prefix", "netmask", "broadcast", "addresses", "hosts", "first_host", "last_host"];
+
+static NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z_][a-z0-9_]*$").unwrap());
+
+/// `{{name}}` or `{{name.field}}`
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{([a-z_][a-z0-9_]*)(?:\.([a-z_][a-z0-9_]*))?\}\}").unwrap());
+
+// === Variant Models ===
+// How one placeholder variable of a template question is generated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VariableSpec {
+    // A whole number from min to max (inclusive) in steps of `step`
+    Int {
+        min: i64,
+        max: i64,
+        #[serde(default = "default_step")]
+        step: i64,
+    },
+    // One of the values. Object values bind several fields at once, so related facts
+    // (an instance size and its vCPU count) stay consistent: `{{size.vcpu}}`
+    Choice { values: Vec<Value> },
+    // An IPv4 block inside 10.0.0.0/8 with a prefix length in the range
+    Cidr { min_prefix: u8, max_prefix: u8 },
+}
+
+fn default_step() -> i64 {
+    1
+}
+
+// A generated value: plain, or a set of fields with an optional value of its own
+enum Resolved {
+    Value(String),
+    Fields {
+        value: Option<String>,
+        fields: HashMap<String, String>,
+    },
+}
+
+impl Resolved {
+    fn get(&self, field: Option<&str>) -> Option<&String> {
+        match (self, field) {
+            (Resolved::Value(value), None) => Some(value),
+            (Resolved::Fields { value, .. }, None) => value.as_ref(),
+            (Resolved::Fields { fields, .. }, Some(field)) => fields.get(field),
+            (Resolved::Value(_), Some(_)) => None,
+        }
+    }
+}
+
+impl VariableSpec {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        match self {
+            VariableSpec::Int { min, max, step } => {
+                if min > max || *step < 1 || max.checked_sub(*min).is_none() {
+                    return Err(format!("Variable '{}' needs min <= max and a step of at least 1", name));
+                }
+            }
+            VariableSpec::Choice { values } => {
+                let Some(first) = values.first() else {
+                    return Err(format!("Variable '{}' needs at least one value", name));
+                };
+
+                let keys = field_names(first);
+                for value in values {
+                    let valid = match value {
+                        Value::Object(fields) => {
+                            field_names(value) == keys
+                                && fields.keys().all(|k| NAME.is_match(k))
+                                && fields.values().all(is_scalar)
+                        }
+                        _ => keys.is_none() && is_scalar(value),
+                    };
+
+                    if !valid {
+                        return Err(format!(
+                            "Variable '{}' values must all be scalars, or objects of scalars with the same field names",
+                            name
+                        ));
+                    }
+                }
+            }
+            VariableSpec::Cidr { min_prefix, max_prefix } => {
+                if !(8 <= *min_prefix && min_prefix <= max_prefix && *max_prefix <= 30) {
+                    return Err(format!("Variable '{}' needs 8 <= min_prefix <= max_prefix <= 30", name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `{{name}}` (no field) or `{{name.field}}` can be filled
+    fn supports(&self, field: Option<&str>) -> bool {
+        match (self, field) {
+            (VariableSpec::Int { .. }, None) => true,
+            (VariableSpec::Int { .. }, Some(_)) => false,
+            (VariableSpec::Choice { values }, field) => match (values.first().and_then(field_names), field) {
+                (None, None) => true,
+                (Some(keys), Some(field)) => keys.iter().any(|k| *k == field),
+                _ => false,
+            },
+            (VariableSpec::Cidr { .. }, None) => true,
+            (VariableSpec::Cidr { .. }, Some(field)) => CIDR_FIELDS.contains(&field),
+        }
+    }
+
+    fn generate(&self, rng: &mut ChaCha8Rng) -> Resolved {
+        match self {
+            VariableSpec::Int { min, max, step } => {
+                let steps = (max - min) / step;
+                Resolved::Value((min + rng.gen_range(0..=steps) * step).to_string())
+            }
+            VariableSpec::Choice { values } if values.is_empty() => Resolved::Value(String::new()),
+            VariableSpec::Choice { values } => match &values[rng.gen_range(0..values.len())] {
+                Value::Object(fields) => Resolved::Fields {
+                    value: None,
+                    fields: fields.iter().map(|(k, v)| (k.clone(), scalar_text(v))).collect(),
+                },
+                value => Resolved::Value(scalar_text(value)),
+            },
+            VariableSpec::Cidr { min_prefix, max_prefix } => {
+                let prefix = rng.gen_range(*min_prefix..=*max_prefix) as u32;
+                let host_bits = 32 - prefix;
+                let network = ((10u32 << 24) | rng.gen_range(0..1u32 << 24)) & (u32::MAX << host_bits);
+                let addresses = 1u64 << host_bits;
+                let broadcast = network | ((1u32 << host_bits) - 1);
+
+                let fields = HashMap::from([
+                    ("network".to_string(), Ipv4Addr::from(network).to_string()),
+                    ("prefix".to_string(), prefix.to_string()),
+                    ("netmask".to_string(), Ipv4Addr::from(u32::MAX << host_bits).to_string()),
+                    ("broadcast".to_string(), Ipv4Addr::from(broadcast).to_string()),
+                    ("addresses".to_string(), addresses.to_string()),
+                    ("hosts".to_string(), (addresses - 2).to_string()),
+                    ("first_host".to_string(), Ipv4Addr::from(network + 1).to_string()),
+                    ("last_host".to_string(), Ipv4Addr::from(broadcast - 1).to_string()),
+                ]);
+
+                Resolved::Fields {
+                    value: Some(format!("{}/{}", Ipv4Addr::from(network), prefix)),
+                    fields,
+                }
+            }
+        }
+    }
+}
+
+fn field_names(value: &Value) -> Option<Vec<&String>> {
+    let Value::Object(fields) = value else {
+        return None;
+    };
+
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    Some(keys)
+}
+
+fn is_scalar(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_))
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Check a template question: every variable spec is well formed and every placeholder
+/// in `texts` refers to a declared variable (and one of its fields, if given)
+pub fn validate_variables(variables: &HashMap<String, VariableSpec>, texts: &[&str]) -> Result<(), String> {
+    if variables.len() > MAX_VARIABLES {
+        return Err(format!("A question may declare at most {} variables", MAX_VARIABLES));
+    }
+
+    let mut names: Vec<&String> = variables.keys().collect();
+    names.sort();
+
+    for name in names {
+        if !NAME.is_match(name) {
+            return Err(format!("Variable name '{}' must be lowercase letters, digits and underscores", name));
+        }
+        variables[name].validate(name)?;
+    }
+
+    for text in texts {
+        for captures in PLACEHOLDER.captures_iter(text) {
+            let name = &captures[1];
+            let field = captures.get(2).map(|f| f.as_str());
+
+            match variables.get(name) {
+                Some(spec) if spec.supports(field) => {}
+                Some(_) => return Err(format!("Placeholder '{}' does not match how variable '{}' is defined", &captures[0], name)),
+                None => return Err(format!("Placeholder '{}' refers to an undeclared variable", &captures[0])),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate a value for every variable. The same seed always gives the same values.
+fn resolve(variables: &HashMap<String, VariableSpec>, seed: u64) -> HashMap<&str, Resolved> {
+    // Draw in name order so the values do not depend on map iteration order
+    let ordered: BTreeMap<&String, &VariableSpec> = variables.iter().collect();
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    ordered
+        .into_iter()
+        .map(|(name, spec)| (name.as_str(), spec.generate(&mut rng)))
+        .collect()
+}
+
+/// A renderer filling the placeholders of one variant; unknown placeholders are kept
+pub fn variant_renderer(variables: &HashMap<String, VariableSpec>, seed: u64) -> impl Fn(&str) -> String {
+    let resolved = resolve(variables, seed);
+
+    move |text: &str| {
+        PLACEHOLDER
+            .replace_all(text, |captures: &regex::Captures| {
+                resolved
+                    .get(&captures[1])
+                    .and_then(|r| r.get(captures.get(2).map(|f| f.as_str())))
+                    .cloned()
+                    .unwrap_or_else(|| captures[0].to_string())
+            })
+            .into_owned()
+    }
+}
@@ -0,0 +1,245 @@
+use anyhow::bail;
+use axum::{body::Bytes, http::{header, Request}};
+use chrono::{Duration as ChronoDuration, Utc};
+use http_body_util::Full;
+use serde::Serialize;
+use sqlx::{prelude::FromRow, PgPool};
+use std::env;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::encoding;
+use crate::http_client::{self, HttpClient};
+use crate::models::{OutboxEvent, TriggerItem, WebhookBatch, WebhookDelivery};
+
+/// Events sent per webhook request
+const BATCH_SIZE: i64 = 100;
+
+/// Subscriptions served per tick
+const SUBSCRIPTIONS_PER_TICK: i64 = 20;
+
+#[derive(FromRow)]
+struct DueSubscription {
+    id: Uuid,
+    url: String,
+    secret: String,
+    event_types: Option<Vec<String>>,
+    delivery: WebhookDelivery,
+    last_seq: i64,
+    failures: i32,
+}
+
+/// Sequence committed outbox events and deliver them to webhook subscribers every
+/// `OUTBOX_INTERVAL_SECS` (default 2). Events are kept for `OUTBOX_RETENTION_DAYS` (default 7).
+pub fn spawn(pool: PgPool) {
+    let interval = env::var("OUTBOX_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(2);
+
+    let retention_days: i32 = env::var("OUTBOX_RETENTION_DAYS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(7);
+
+    tokio::spawn(async move {
+        let client = HttpClient::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = sequence(&pool).await {
+                error!("Outbox sequencing failed: {}", e);
+                continue;
+            }
+
+            match dispatch(&pool, &client).await {
+                Ok(0) => {}
+                Ok(sent) => info!("Delivered {} outbox events to webhooks", sent),
+                Err(e) => error!("Outbox dispatch failed: {}", e),
+            }
+
+            if let Err(e) = prune(&pool, retention_days).await {
+                error!("Outbox pruning failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Give every committed, unsequenced event the next seq, in insertion order. Events of
+/// transactions still open are invisible here and get a later seq once they commit.
+async fn sequence(pool: &PgPool) -> anyhow::Result<()> {
+    let mut transaction = pool.begin().await?;
+
+    // Serializes sequencing across instances; the numbering below then sees their results
+    let last_seq: i64 = sqlx::query_scalar("SELECT last_seq FROM outbox_sequence FOR UPDATE")
+        .fetch_one(&mut *transaction)
+        .await?;
+
+    let numbered: Option<i64> = sqlx::query_scalar(
+        "WITH pending AS (
+            SELECT id, row_number() OVER (ORDER BY id) AS n FROM outbox_events WHERE seq IS NULL
+         ),
+         numbered AS (
+            UPDATE outbox_events e SET seq = $1 + pending.n
+            FROM pending WHERE e.id = pending.id
+            RETURNING e.seq
+         )
+         SELECT MAX(seq) FROM numbered"
+    )
+    .bind(last_seq)
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    if let Some(last_seq) = numbered {
+        sqlx::query("UPDATE outbox_sequence SET last_seq = $1")
+            .bind(last_seq)
+            .execute(&mut *transaction)
+            .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+/// Send each subscriber the events after its cursor. Subscriptions are claimed with a
+/// lease, so an instance that dies mid-send lets another retry.
+async fn dispatch(pool: &PgPool, client: &HttpClient) -> anyhow::Result<usize> {
+    let head: i64 = sqlx::query_scalar("SELECT last_seq FROM outbox_sequence")
+        .fetch_one(pool)
+        .await?;
+
+    let due = sqlx::query_as::<_, DueSubscription>(
+        "UPDATE webhook_subscriptions SET next_attempt_at = NOW() + INTERVAL '5 minutes'
+         WHERE id IN (
+            SELECT id FROM webhook_subscriptions
+            WHERE active AND next_attempt_at <= NOW() AND last_seq < $1
+            ORDER BY next_attempt_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, url, secret, event_types, delivery, last_seq, failures"
+    )
+    .bind(head)
+    .bind(SUBSCRIPTIONS_PER_TICK)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0;
+
+    for subscription in &due {
+        let events = sqlx::query_as::<_, OutboxEvent>(
+            "SELECT * FROM outbox_events
+             WHERE seq > $1 AND seq <= $2 AND ($3::text[] IS NULL OR event_type = ANY($3))
+             ORDER BY seq
+             LIMIT $4"
+        )
+        .bind(subscription.last_seq)
+        .bind(head)
+        .bind(&subscription.event_types)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+
+        // A short batch means everything up to the head has been seen
+        let cursor = match events.last() {
+            Some(last) if events.len() as i64 == BATCH_SIZE => last.seq,
+            _ => head,
+        };
+
+        // Highest seq the receiver has acknowledged, so per-event delivery resumes after it
+        let mut delivered = subscription.last_seq;
+
+        let result = match subscription.delivery {
+            _ if events.is_empty() => Ok(()),
+            WebhookDelivery::Batch => {
+                let batch = WebhookBatch { subscription_id: subscription.id, events: &events };
+                send(client, subscription, &batch).await
+            }
+            WebhookDelivery::Event => {
+                let mut result = Ok(());
+                for event in &events {
+                    if let Err(e) = send(client, subscription, &TriggerItem::from(event)).await {
+                        result = Err(e);
+                        break;
+                    }
+                    delivered = event.seq;
+                    sent += 1;
+                }
+                result
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                if subscription.delivery == WebhookDelivery::Batch {
+                    sent += events.len();
+                }
+
+                sqlx::query(
+                    "UPDATE webhook_subscriptions SET
+                        last_seq = $1, failures = 0, next_attempt_at = NOW(), last_error = NULL
+                     WHERE id = $2"
+                )
+                .bind(cursor)
+                .bind(subscription.id)
+                .execute(pool)
+                .await?;
+            }
+            Err(e) => {
+                let failures = subscription.failures + 1;
+                warn!("Webhook {} delivery failed ({} in a row): {}", subscription.id, failures, e);
+
+                // Exponential backoff from 30 seconds, capped at 6 hours
+                let backoff = ChronoDuration::seconds((30i64 << (failures - 1).min(10)).min(6 * 3600));
+
+                sqlx::query(
+                    "UPDATE webhook_subscriptions SET failures = $1, last_error = $2, next_attempt_at = $3, last_seq = $4
+                     WHERE id = $5"
+                )
+                .bind(failures)
+                .bind(e.to_string())
+                .bind(Utc::now() + backoff)
+                .bind(delivered)
+                .bind(subscription.id)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn send(client: &HttpClient, subscription: &DueSubscription, body: &impl Serialize) -> anyhow::Result<()> {
+    let uri = http_client::parse_endpoint(&subscription.url)?;
+
+    let body = serde_json::to_vec(body)?;
+
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-signature", format!("sha256={}", encoding::hmac_sha256_hex(&subscription.secret, &body)))
+        .body(Full::new(Bytes::from(body)))?;
+
+    let response = client.send(request).await?;
+    if !response.status.is_success() {
+        bail!("{} returned {}", subscription.url, response.status);
+    }
+
+    Ok(())
+}
+
+/// Drop sequenced events older than the retention period; subscribers further behind miss them
+async fn prune(pool: &PgPool, retention_days: i32) -> anyhow::Result<()> {
+    sqlx::query(
+        "DELETE FROM outbox_events WHERE seq IS NOT NULL AND created_at < NOW() - make_interval(days => $1)"
+    )
+    .bind(retention_days)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
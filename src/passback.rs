@@ -0,0 +1,189 @@
+use anyhow::bail;
+use axum::{body::Bytes, http::{header, Request}};
+use chrono::{Duration as ChronoDuration, Utc};
+use http_body_util::Full;
+use sqlx::{prelude::FromRow, PgPool, types::Json as SqlxJson};
+use std::env;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::encoding;
+use crate::http_client::{self, HttpClient};
+use crate::models::{PassbackScore, PassbackTarget, QuizSession};
+
+/// Deliveries give up after this many failed attempts
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Deliveries sent per tick
+const BATCH_SIZE: i64 = 20;
+
+#[derive(FromRow)]
+struct DueDelivery {
+    id: Uuid,
+    payload: serde_json::Value,
+    attempts: i32,
+    endpoint_url: String,
+    secret: String,
+}
+
+/// Send pending grade passbacks every `PASSBACK_INTERVAL_SECS` (default 30)
+pub fn spawn(pool: PgPool) {
+    let interval = env::var("PASSBACK_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(30);
+
+    tokio::spawn(async move {
+        let client = HttpClient::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+
+        loop {
+            ticker.tick().await;
+
+            match deliver_due(&pool, &client).await {
+                Ok(0) => {}
+                Ok(sent) => info!("Processed {} grade passback deliveries", sent),
+                Err(e) => error!("Grade passback failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Queue the session's score for every active target of its topic. Sessions without a
+/// learner reference are not reported. Failures are logged rather than failing the
+/// submission, which has already been committed.
+pub async fn enqueue(pool: &PgPool, session: &QuizSession) {
+    let Some(learner_ref) = &session.learner_ref else {
+        return;
+    };
+
+    let targets = sqlx::query_as::<_, PassbackTarget>(
+        "SELECT * FROM grade_passback_targets WHERE topic_id = $1 AND active"
+    )
+    .bind(session.topic_id)
+    .fetch_all(pool)
+    .await;
+
+    let targets = match targets {
+        Ok(targets) => targets,
+        Err(e) => {
+            error!("Failed to fetch passback targets for session {}: {}", session.id, e);
+            return;
+        }
+    };
+
+    for target in targets {
+        let score = PassbackScore {
+            user_id: learner_ref.clone(),
+            assignment_id: target.assignment_ref.clone(),
+            session_id: session.id,
+            score_given: session.adjusted_score().unwrap_or(0.0),
+            score_maximum: session.question_ids.0.len() as f64,
+            activity_progress: "Completed",
+            grading_progress: "FullyGraded",
+            timestamp: session.submitted_at.unwrap_or_else(Utc::now),
+        };
+
+        // A resubmitted score replaces the queued one and starts its retries over
+        let result = sqlx::query(
+            "INSERT INTO grade_passback_deliveries (target_id, session_id, payload) VALUES ($1, $2, $3)
+             ON CONFLICT (target_id, session_id) DO UPDATE SET
+                payload = EXCLUDED.payload,
+                status = 'pending',
+                attempts = 0,
+                next_attempt_at = NOW(),
+                last_error = NULL"
+        )
+        .bind(target.id)
+        .bind(session.id)
+        .bind(SqlxJson(&score))
+        .execute(pool)
+        .await;
+
+        if let Err(e) = result {
+            error!("Failed to queue grade passback for session {}: {}", session.id, e);
+        }
+    }
+}
+
+/// Claim due deliveries with a lease, so an instance that dies mid-send lets another retry
+async fn deliver_due(pool: &PgPool, client: &HttpClient) -> anyhow::Result<usize> {
+    let due = sqlx::query_as::<_, DueDelivery>(
+        "WITH due AS (
+            UPDATE grade_passback_deliveries SET next_attempt_at = NOW() + INTERVAL '5 minutes'
+            WHERE id IN (
+                SELECT id FROM grade_passback_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, target_id, payload, attempts
+         )
+         SELECT due.id, due.payload, due.attempts, t.endpoint_url, t.secret
+         FROM due JOIN grade_passback_targets t ON t.id = due.target_id"
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    for delivery in &due {
+        let attempts = delivery.attempts + 1;
+
+        match send(client, delivery).await {
+            Ok(()) => {
+                sqlx::query(
+                    "UPDATE grade_passback_deliveries SET
+                        status = 'delivered', attempts = $1, delivered_at = NOW(), last_error = NULL
+                     WHERE id = $2"
+                )
+                .bind(attempts)
+                .bind(delivery.id)
+                .execute(pool)
+                .await?;
+            }
+            Err(e) => {
+                warn!("Grade passback {} attempt {} failed: {}", delivery.id, attempts, e);
+
+                // Exponential backoff from 30 seconds, capped at 6 hours
+                let backoff = ChronoDuration::seconds((30i64 << (attempts - 1).min(10)).min(6 * 3600));
+                let status = if attempts >= MAX_ATTEMPTS { "failed" } else { "pending" };
+
+                sqlx::query(
+                    "UPDATE grade_passback_deliveries SET
+                        status = $1::delivery_status, attempts = $2, last_error = $3, next_attempt_at = $4
+                     WHERE id = $5"
+                )
+                .bind(status)
+                .bind(attempts)
+                .bind(e.to_string())
+                .bind(Utc::now() + backoff)
+                .bind(delivery.id)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    Ok(due.len())
+}
+
+async fn send(client: &HttpClient, delivery: &DueDelivery) -> anyhow::Result<()> {
+    let uri = http_client::parse_endpoint(&delivery.endpoint_url)?;
+
+    let body = serde_json::to_vec(&delivery.payload)?;
+
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-signature", format!("sha256={}", encoding::hmac_sha256_hex(&delivery.secret, &body)))
+        .header("x-delivery-id", delivery.id.to_string())
+        .body(Full::new(Bytes::from(body)))?;
+
+    let response = client.send(request).await?;
+    if !response.status.is_success() {
+        bail!("{} returned {}", delivery.endpoint_url, response.status);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use sqlx::{prelude::FromRow, types::Json, PgPool};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::analytics;
+use crate::models::{ReadingStats, RebuildTarget};
+
+/// Indexes behind full-text search and search suggestions
+const SEARCH_INDEXES: [&str; 3] = ["idx_questions_search", "idx_topics_search", "idx_topics_name_trgm"];
+
+/// Questions whose reading stats are checked per batch
+const BATCH_SIZE: i64 = 500;
+
+#[derive(FromRow)]
+struct QuestionText {
+    id: Uuid,
+    question: String,
+    options: Json<Vec<String>>,
+    explanation: String,
+    word_count: i32,
+    estimated_secs: i32,
+}
+
+/// Run a rebuild job to completion, recording its progress and outcome on the job row
+pub async fn run(pool: PgPool, job_id: Uuid, target: RebuildTarget) {
+    info!("Starting rebuild job {} for {}", job_id, target.as_str());
+
+    let result = rebuild(&pool, job_id, target).await;
+
+    let update = match &result {
+        Ok(()) => sqlx::query(
+            "UPDATE rebuild_jobs SET status = 'completed', completed_at = NOW() WHERE id = $1"
+        )
+        .bind(job_id)
+        .execute(&pool)
+        .await,
+        Err(e) => sqlx::query(
+            "UPDATE rebuild_jobs SET status = 'failed', error = $1, completed_at = NOW() WHERE id = $2"
+        )
+        .bind(e.to_string())
+        .bind(job_id)
+        .execute(&pool)
+        .await,
+    };
+
+    match (result, update) {
+        (Ok(()), Ok(_)) => info!("Rebuild job {} for {} completed", job_id, target.as_str()),
+        (Err(e), _) => error!("Rebuild job {} for {} failed: {}", job_id, target.as_str(), e),
+        (_, Err(e)) => error!("Failed to record status of rebuild job {}: {}", job_id, e),
+    }
+}
+
+async fn rebuild(pool: &PgPool, job_id: Uuid, target: RebuildTarget) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE rebuild_jobs SET status = 'running', started_at = NOW() WHERE id = $1")
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    match target {
+        RebuildTarget::SearchIndex => rebuild_search_index(pool, job_id).await,
+        RebuildTarget::Stats => rebuild_stats(pool, job_id).await,
+        RebuildTarget::Counts => rebuild_counts(pool, job_id).await,
+    }
+}
+
+/// Rebuild the search indexes without locking out writes, then the suggestion terms
+async fn rebuild_search_index(pool: &PgPool, job_id: Uuid) -> Result<(), sqlx::Error> {
+    set_total(pool, job_id, SEARCH_INDEXES.len() as i64 + 1).await?;
+
+    for index in SEARCH_INDEXES {
+        sqlx::query(&format!("REINDEX INDEX CONCURRENTLY {}", index))
+            .execute(pool)
+            .await?;
+        advance(pool, job_id, 1, 0).await?;
+    }
+
+    analytics::refresh_view(pool, analytics::TERMS_VIEW).await?;
+    advance(pool, job_id, 1, 0).await
+}
+
+async fn rebuild_stats(pool: &PgPool, job_id: Uuid) -> Result<(), sqlx::Error> {
+    set_total(pool, job_id, analytics::STATS_VIEWS.len() as i64).await?;
+
+    for view in analytics::STATS_VIEWS {
+        analytics::refresh_view(pool, view).await?;
+        advance(pool, job_id, 1, 0).await?;
+    }
+
+    Ok(())
+}
+
+/// Recount the vote tallies from the votes, then walk the questions in id order and
+/// correct reading stats that no longer match the text. Neither bumps `updated_at`.
+async fn rebuild_counts(pool: &PgPool, job_id: Uuid) -> Result<(), sqlx::Error> {
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM questions").fetch_one(pool).await?;
+    set_total(pool, job_id, total).await?;
+
+    let questions = sqlx::query(
+        "UPDATE questions q SET explanation_upvotes = v.up, explanation_downvotes = v.down
+         FROM (
+            SELECT q2.id,
+                COUNT(e.voter) FILTER (WHERE e.direction = 'up')::int AS up,
+                COUNT(e.voter) FILTER (WHERE e.direction = 'down')::int AS down
+            FROM questions q2
+            LEFT JOIN explanation_votes e ON e.question_id = q2.id
+            GROUP BY q2.id
+         ) v
+         WHERE v.id = q.id AND (q.explanation_upvotes, q.explanation_downvotes) IS DISTINCT FROM (v.up, v.down)"
+    )
+    .execute(pool)
+    .await?;
+
+    let explanations = sqlx::query(
+        "UPDATE question_explanations x SET upvotes = v.up, downvotes = v.down
+         FROM (
+            SELECT x2.id,
+                COUNT(e.voter) FILTER (WHERE e.direction = 'up')::int AS up,
+                COUNT(e.voter) FILTER (WHERE e.direction = 'down')::int AS down
+            FROM question_explanations x2
+            LEFT JOIN question_explanation_votes e ON e.explanation_id = x2.id
+            GROUP BY x2.id
+         ) v
+         WHERE v.id = x.id AND (x.upvotes, x.downvotes) IS DISTINCT FROM (v.up, v.down)"
+    )
+    .execute(pool)
+    .await?;
+
+    advance(pool, job_id, 0, (questions.rows_affected() + explanations.rows_affected()) as i64).await?;
+
+    let mut after = Uuid::nil();
+    loop {
+        let batch = sqlx::query_as::<_, QuestionText>(
+            "SELECT id, question, options, explanation, word_count, estimated_secs
+             FROM questions WHERE id > $1 ORDER BY id LIMIT $2"
+        )
+        .bind(after)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+
+        let Some(last) = batch.last() else {
+            return Ok(());
+        };
+        after = last.id;
+
+        let mut ids = Vec::new();
+        let mut word_counts = Vec::new();
+        let mut estimated_secs = Vec::new();
+        for q in &batch {
+            let stats = ReadingStats::of(&q.question, &q.options, &q.explanation);
+            if (stats.word_count, stats.estimated_secs) != (q.word_count, q.estimated_secs) {
+                ids.push(q.id);
+                word_counts.push(stats.word_count);
+                estimated_secs.push(stats.estimated_secs);
+            }
+        }
+
+        if !ids.is_empty() {
+            sqlx::query(
+                "UPDATE questions q SET word_count = u.word_count, estimated_secs = u.estimated_secs
+                 FROM UNNEST($1::uuid[], $2::int[], $3::int[]) AS u(id, word_count, estimated_secs)
+                 WHERE q.id = u.id"
+            )
+            .bind(&ids)
+            .bind(&word_counts)
+            .bind(&estimated_secs)
+            .execute(pool)
+            .await?;
+        }
+
+        advance(pool, job_id, batch.len() as i64, ids.len() as i64).await?;
+    }
+}
+
+async fn set_total(pool: &PgPool, job_id: Uuid, total: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE rebuild_jobs SET items_total = $1 WHERE id = $2")
+        .bind(total as i32)
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+async fn advance(pool: &PgPool, job_id: Uuid, done: i64, fixed: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE rebuild_jobs SET items_done = items_done + $1, items_fixed = items_fixed + $2 WHERE id = $3")
+        .bind(done as i32)
+        .bind(fixed as i32)
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
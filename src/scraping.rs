@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use sqlx::PgPool;
+use std::env;
+use std::sync::LazyLock;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Detection thresholds for public API reads, read once from the environment
+struct Limits {
+    // SCRAPE_WINDOW_SECS: how far back read patterns are looked at
+    window_secs: i64,
+    // SCRAPE_COVERAGE_PERCENT: share of a topic read within the window that counts as a scrape
+    coverage_percent: i64,
+    // SCRAPE_MIN_QUESTIONS: smaller topics are never flagged as scraped
+    min_questions: i64,
+    // ANSWER_READ_LIMIT: questions served with answers within the window
+    answer_reads: i64,
+    // PUBLIC_READS_PER_MINUTE: question pages per key and minute
+    reads_per_minute: i64,
+    // SCRAPE_BLOCK_SECS: how long a flagged key stays blocked
+    block_secs: i64,
+}
+
+static LIMITS: LazyLock<Limits> = LazyLock::new(|| Limits {
+    window_secs: limit("SCRAPE_WINDOW_SECS", 3600),
+    coverage_percent: limit("SCRAPE_COVERAGE_PERCENT", 80),
+    min_questions: limit("SCRAPE_MIN_QUESTIONS", 50),
+    answer_reads: limit("ANSWER_READ_LIMIT", 500),
+    reads_per_minute: limit("PUBLIC_READS_PER_MINUTE", 60),
+    block_secs: limit("SCRAPE_BLOCK_SECS", 3600),
+});
+
+fn limit(name: &str, default: i64) -> i64 {
+    env::var(name)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Whether the key has used up its question pages for the current minute
+pub async fn throttled(pool: &PgPool, key_id: Uuid) -> Result<bool, sqlx::Error> {
+    let reads: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_key_reads WHERE key_id = $1 AND read_at > $2")
+        .bind(key_id)
+        .bind(Utc::now() - ChronoDuration::minutes(1))
+        .fetch_one(pool)
+        .await?;
+
+    Ok(reads >= LIMITS.reads_per_minute)
+}
+
+/// A page of questions served to a key
+pub struct Read {
+    pub key_id: Uuid,
+    pub topic_id: Uuid,
+    pub topic_size: i64,
+    pub first_position: i64,
+    pub served: i64,
+    pub with_answers: bool,
+}
+
+/// Log a served page and look for scraping in the key's recent reads: most of a topic
+/// read within the window, or more answers read than the limit allows. A key that trips
+/// either is blocked and an alert is recorded; the block end is returned.
+pub async fn record_read(pool: &PgPool, read: &Read) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let since = Utc::now() - ChronoDuration::seconds(LIMITS.window_secs);
+
+    sqlx::query("DELETE FROM api_key_reads WHERE key_id = $1 AND read_at <= $2")
+        .bind(read.key_id)
+        .bind(since)
+        .execute(pool)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO api_key_reads (key_id, topic_id, first_position, served, with_answers)
+         VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(read.key_id)
+    .bind(read.topic_id)
+    .bind(read.first_position as i32)
+    .bind(read.served as i32)
+    .bind(read.with_answers)
+    .execute(pool)
+    .await?;
+
+    let covered: i64 = sqlx::query_scalar(
+        "SELECT COUNT(DISTINCT position)
+         FROM api_key_reads r, generate_series(r.first_position, r.first_position + r.served - 1) AS position
+         WHERE r.key_id = $1 AND r.topic_id = $2"
+    )
+    .bind(read.key_id)
+    .bind(read.topic_id)
+    .fetch_one(pool)
+    .await?;
+
+    let detection = if read.topic_size >= LIMITS.min_questions
+        && covered * 100 >= read.topic_size * LIMITS.coverage_percent
+    {
+        Some((
+            "bank_scrape",
+            format!("Read {} of {} questions in topic {} within {}s", covered, read.topic_size, read.topic_id, LIMITS.window_secs),
+        ))
+    } else if read.with_answers {
+        let answers: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(served), 0) FROM api_key_reads WHERE key_id = $1 AND with_answers"
+        )
+        .bind(read.key_id)
+        .fetch_one(pool)
+        .await?;
+
+        (answers > LIMITS.answer_reads).then(|| (
+            "answer_harvest",
+            format!("Read {} correct answers within {}s", answers, LIMITS.window_secs),
+        ))
+    } else {
+        None
+    };
+
+    let Some((kind, detail)) = detection else {
+        return Ok(None);
+    };
+
+    let blocked_until = Utc::now() + ChronoDuration::seconds(LIMITS.block_secs);
+    warn!("Blocking API key {} until {}: {}", read.key_id, blocked_until, detail);
+
+    let mut transaction = pool.begin().await?;
+
+    sqlx::query("UPDATE api_keys SET blocked_until = $1 WHERE id = $2")
+        .bind(blocked_until)
+        .bind(read.key_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("INSERT INTO scraping_alerts (key_id, kind, detail, blocked_until) VALUES ($1, $2, $3, $4)")
+        .bind(read.key_id)
+        .bind(kind)
+        .bind(&detail)
+        .bind(blocked_until)
+        .execute(&mut *transaction)
+        .await?;
+
+    // Start the next window from scratch once the block ends
+    sqlx::query("DELETE FROM api_key_reads WHERE key_id = $1")
+        .bind(read.key_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+
+    Ok(Some(blocked_until))
+}
@@ -0,0 +1,191 @@
+use anyhow::{anyhow, bail};
+use axum::{body::Bytes, http::Request};
+use http_body_util::Full;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sqlx::{PgPool, types::Json as SqlxJson};
+use std::collections::HashSet;
+use std::env;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::catalog;
+use crate::http_client::{self, HttpClient};
+use crate::handlers::admin::build_manifest;
+use crate::handlers::freeze;
+use crate::models::{Difficulty, ExportManifest, ReadingStats, TopicExport};
+
+/// Envelope returned by the upstream instance's API
+#[derive(Debug, Deserialize)]
+struct Upstream<T> {
+    data: T,
+}
+
+/// Start the periodic pull from `SYNC_UPSTREAM_URL` (e.g. `http://staging:3000`).
+/// Does nothing when no upstream is configured.
+pub fn spawn(pool: PgPool) {
+    let Ok(upstream) = env::var("SYNC_UPSTREAM_URL") else {
+        return;
+    };
+
+    let interval = env::var("SYNC_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(300);
+
+    info!("Syncing content from {} every {}s", upstream, interval);
+
+    tokio::spawn(async move {
+        let client = HttpClient::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+
+        loop {
+            ticker.tick().await;
+
+            match pull(&pool, &client, &upstream).await {
+                Ok(0) => info!("Content is up to date with upstream"),
+                Ok(pulled) => info!("Pulled {} questions from upstream", pulled),
+                Err(e) => error!("Content sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Pull every question that is missing locally or differs from upstream.
+/// Local-only questions are left untouched.
+async fn pull(pool: &PgPool, client: &HttpClient, upstream: &str) -> anyhow::Result<usize> {
+    let base = upstream.trim_end_matches('/');
+
+    let remote: ExportManifest = fetch(client, &format!("{}/api/admin/manifest", base), None).await?;
+    let local = build_manifest(pool)
+        .await
+        .map_err(|(_, body)| anyhow!(body.0.message.unwrap_or_default()))?;
+
+    let diff = local.diff(&remote);
+    let mut pulled = 0;
+
+    for topic in diff.topics {
+        let wanted: HashSet<i32> = topic.removed.iter().chain(&topic.changed).copied().collect();
+        if wanted.is_empty() {
+            continue;
+        }
+
+        // Frozen topics are left as they are; the next tick after the freeze picks them up
+        let topic_id: Option<uuid::Uuid> = sqlx::query_scalar("SELECT id FROM topics WHERE slug = $1")
+            .bind(&topic.slug)
+            .fetch_optional(pool)
+            .await?;
+        if let Some(topic_id) = topic_id
+            && let Some(freeze) = freeze::active_freeze(pool, &[topic_id]).await?
+        {
+            warn!("Skipping sync of frozen topic {} until {}", topic.slug, freeze.ends_at.to_rfc3339());
+            continue;
+        }
+
+        let export: TopicExport = fetch(client, &format!("{}/api/admin/export/{}", base, topic.slug), None).await?;
+        pulled += apply_topic(pool, &export, &wanted).await?;
+    }
+
+    Ok(pulled)
+}
+
+/// GET `url` from another instance and unwrap the `data` of its response, sending `api_key`
+/// as `X-Api-Key` when given
+pub async fn fetch<T: DeserializeOwned>(client: &HttpClient, url: &str, api_key: Option<&str>) -> anyhow::Result<T> {
+    let uri = http_client::parse_endpoint(url)?;
+    let mut request = Request::get(uri);
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    let request = request.body(Full::new(Bytes::new()))?;
+
+    let response = client.send(request).await?;
+    if !response.status.is_success() {
+        bail!("{} returned {}", url, response.status);
+    }
+
+    let parsed: Upstream<T> = serde_json::from_slice(&response.body)?;
+
+    Ok(parsed.data)
+}
+
+/// Upsert the topic and the wanted questions in one transaction
+async fn apply_topic(pool: &PgPool, export: &TopicExport, wanted: &HashSet<i32>) -> anyhow::Result<usize> {
+    let mut transaction = pool.begin().await?;
+
+    let (topic_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO topics (name, slug, description) VALUES ($1, $2, $3)
+         ON CONFLICT (slug) DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description, updated_at = NOW()
+         RETURNING id"
+    )
+    .bind(&export.name)
+    .bind(&export.slug)
+    .bind(&export.description)
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    let mut applied = 0;
+
+    for question in export.questions.iter().filter(|q| wanted.contains(&q.question_number)) {
+        let stats = ReadingStats::of(&question.question, &question.options, &question.explanation);
+        sqlx::query(
+            "INSERT INTO questions (
+                topic_id, question_number, question, options, correct_answer,
+                explanation, question_type, difficulty, tags, option_explanations, hints, metadata, variables, code_blocks,
+                cognitive_level, word_count, estimated_secs
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (topic_id, question_number) DO UPDATE SET
+                question = EXCLUDED.question,
+                options = EXCLUDED.options,
+                correct_answer = EXCLUDED.correct_answer,
+                explanation = EXCLUDED.explanation,
+                question_type = EXCLUDED.question_type,
+                difficulty = EXCLUDED.difficulty,
+                tags = EXCLUDED.tags,
+                option_explanations = EXCLUDED.option_explanations,
+                hints = EXCLUDED.hints,
+                metadata = EXCLUDED.metadata,
+                variables = EXCLUDED.variables,
+                code_blocks = EXCLUDED.code_blocks,
+                cognitive_level = EXCLUDED.cognitive_level,
+                word_count = EXCLUDED.word_count,
+                estimated_secs = EXCLUDED.estimated_secs,
+                updated_at = NOW()"
+        )
+        .bind(topic_id)
+        .bind(question.question_number)
+        .bind(&question.question)
+        .bind(SqlxJson(&question.options))
+        .bind(SqlxJson(&question.correct_answer))
+        .bind(&question.explanation)
+        .bind(&question.question_type)
+        .bind(question.difficulty.as_ref().unwrap_or(&Difficulty::Medium))
+        .bind(question.tags.as_ref().map(SqlxJson))
+        .bind(question.option_explanations.as_ref().map(SqlxJson))
+        .bind(question.hints.as_ref().map(SqlxJson))
+        .bind(SqlxJson(question.metadata.clone().unwrap_or_default()))
+        .bind(question.variables.as_ref().map(SqlxJson))
+        .bind(question.code_blocks.as_ref().map(SqlxJson))
+        .bind(question.cognitive_level)
+        .bind(stats.word_count)
+        .bind(stats.estimated_secs)
+        .execute(&mut *transaction)
+        .await?;
+
+        applied += 1;
+    }
+
+    if applied < wanted.len() {
+        warn!(
+            "Topic '{}': upstream export had {} of {} expected questions",
+            export.slug,
+            applied,
+            wanted.len()
+        );
+    }
+
+    transaction.commit().await?;
+    catalog::invalidate();
+
+    Ok(applied)
+}
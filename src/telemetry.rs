@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{LazyLock, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::{filter_fn, LevelFilter};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+/// How many slow queries are kept in memory
+const SLOW_QUERY_CAPACITY: usize = 100;
+
+/// Slow queries reported by sqlx, newest last
+static SLOW_QUERIES: LazyLock<Mutex<VecDeque<SlowQuery>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(SLOW_QUERY_CAPACITY)));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowQuery {
+    pub summary: String,
+    pub statement: String,
+    pub elapsed_ms: f64,
+    pub rows_returned: u64,
+    pub rows_affected: u64,
+    // "METHOD /path" of the request that ran the query, if any
+    pub request: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Install the global subscriber: formatted logs at INFO plus the slow query recorder
+pub fn init() {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+        .with(SlowQueryLayer.with_filter(filter_fn(|meta| meta.is_span() || meta.target() == "sqlx::query")))
+        .init();
+}
+
+/// Most recent slow queries first
+pub fn recent_slow_queries() -> Vec<SlowQuery> {
+    let log = SLOW_QUERIES.lock().unwrap_or_else(|e| e.into_inner());
+    log.iter().rev().cloned().collect()
+}
+
+fn record_slow_query(query: SlowQuery) {
+    let mut log = SLOW_QUERIES.lock().unwrap_or_else(|e| e.into_inner());
+    if log.len() == SLOW_QUERY_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(query);
+}
+
+/// Captures sqlx's "slow statement" events and tags them with the surrounding HTTP request span
+struct SlowQueryLayer;
+
+struct RequestLabel(String);
+
+impl<S> Layer<S> for SlowQueryLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = RequestVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let (Some(method), Some(uri), Some(span)) = (visitor.method, visitor.uri, ctx.span(id)) {
+            span.extensions_mut().insert(RequestLabel(format!("{} {}", method, uri)));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = QueryVisitor::default();
+        event.record(&mut visitor);
+
+        if !visitor.slow {
+            return;
+        }
+
+        let request = ctx.event_scope(event).and_then(|scope| {
+            scope
+                .from_root()
+                .find_map(|span| span.extensions().get::<RequestLabel>().map(|l| l.0.clone()))
+        });
+
+        let statement = match visitor.statement.trim() {
+            "" => visitor.summary.clone(),
+            sql => sql.to_string(),
+        };
+
+        record_slow_query(SlowQuery {
+            summary: visitor.summary,
+            statement,
+            elapsed_ms: visitor.elapsed_secs * 1000.0,
+            rows_returned: visitor.rows_returned,
+            rows_affected: visitor.rows_affected,
+            request,
+            recorded_at: Utc::now(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct RequestVisitor {
+    method: Option<String>,
+    uri: Option<String>,
+}
+
+impl Visit for RequestVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "method" => self.method = Some(format!("{:?}", value)),
+            "uri" => self.uri = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct QueryVisitor {
+    slow: bool,
+    summary: String,
+    statement: String,
+    elapsed_secs: f64,
+    rows_returned: u64,
+    rows_affected: u64,
+}
+
+impl Visit for QueryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.statement = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_returned" => self.rows_returned = value,
+            "rows_affected" => self.rows_affected = value,
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            // sqlx only includes the threshold on slow statement events
+            "slow_threshold" => self.slow = true,
+            "message" => self.slow |= format!("{:?}", value).starts_with("slow statement"),
+            "summary" => self.summary = format!("{:?}", value),
+            "db.statement" => self.statement = format!("{:?}", value),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::bail;
+use sqlx::{Executor, PgConnection, PgPool};
+use std::time::Instant;
+use tracing::info;
+
+use crate::catalog;
+
+/// Statements prepared on every new connection. The text must match the handlers'
+/// queries exactly, since the per-connection statement cache is keyed by SQL text.
+const HOT_QUERIES: &[&str] = &[
+    "SELECT * FROM topics WHERE id = $1",
+    "SELECT * FROM topics WHERE LOWER(slug) = LOWER($1)",
+    "SELECT * FROM questions WHERE id = $1",
+    "SELECT * FROM questions WHERE id = ANY($1)",
+    "SELECT * FROM questions WHERE topic_id = $1 ORDER BY question_number",
+    "SELECT * FROM quiz_sessions WHERE id = $1",
+];
+
+/// Postgres enum types the models decode into, with the labels they expect
+const REQUIRED_ENUMS: &[(&str, &[&str])] = &[
+    ("question_type", &["single", "multiple"]),
+    ("difficulty_level", &["easy", "medium", "hard"]),
+];
+
+/// Prepare the hot statements on a fresh connection, so the first requests it serves
+/// skip the parse and plan round trip
+pub async fn prepare_connection(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    for sql in HOT_QUERIES {
+        conn.prepare(sql).await?;
+    }
+
+    Ok(())
+}
+
+/// Run once on boot, before serving: check the schema the models rely on and build the
+/// catalog cache
+pub async fn run(pool: &PgPool) -> anyhow::Result<()> {
+    let started = Instant::now();
+
+    verify_enums(pool).await?;
+
+    let catalog = catalog::load(pool).await?;
+
+    info!(
+        "Warmup finished in {:?} ({} topics cached)",
+        started.elapsed(),
+        catalog.catalog.topic_count
+    );
+
+    Ok(())
+}
+
+async fn verify_enums(pool: &PgPool) -> anyhow::Result<()> {
+    for &(name, expected) in REQUIRED_ENUMS {
+        let labels: Vec<String> = sqlx::query_scalar(
+            "SELECT e.enumlabel::text FROM pg_enum e
+             JOIN pg_type t ON t.oid = e.enumtypid
+             WHERE t.typname = $1
+             ORDER BY e.enumsortorder"
+        )
+        .bind(name)
+        .fetch_all(pool)
+        .await?;
+
+        if labels.is_empty() {
+            bail!("Database enum type '{}' is missing; run `sqlx migrate run` against this database", name);
+        }
+
+        if let Some(missing) = expected.iter().find(|label| !labels.iter().any(|l| l == *label)) {
+            bail!(
+                "Database enum type '{}' lacks the value '{}' (found: {}); the schema is out of date",
+                name,
+                missing,
+                labels.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// Zero-width characters: a mark is START followed by 32 bits, most significant first
+const START: char = '\u{2060}';
+const ZERO: char = '\u{200B}';
+const ONE: char = '\u{200C}';
+const BITS: usize = 32;
+
+/// The tag embedded in content served to an API key, derived from its id
+pub fn tag(key_id: Uuid) -> u32 {
+    let digest = Sha256::digest(key_id.as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Embed `tag` invisibly after the first word of `text` (or at its end for one word)
+pub fn apply(text: &str, tag: u32) -> String {
+    let mark: String = std::iter::once(START)
+        .chain((0..BITS).rev().map(|bit| if (tag >> bit) & 1 == 1 { ONE } else { ZERO }))
+        .collect();
+
+    match text.find(char::is_whitespace) {
+        Some(at) => format!("{}{}{}", &text[..at], mark, &text[at..]),
+        None => format!("{}{}", text, mark),
+    }
+}
+
+/// Every complete mark found in `text`, in order. Marks cut short by editing are skipped.
+pub fn extract(text: &str) -> Vec<u32> {
+    let mut tags = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != START {
+            continue;
+        }
+
+        let mut tag = 0u32;
+        let mut read = 0;
+        while read < BITS {
+            match chars.peek() {
+                Some(&ZERO) => tag <<= 1,
+                Some(&ONE) => tag = (tag << 1) | 1,
+                _ => break,
+            }
+            chars.next();
+            read += 1;
+        }
+
+        if read == BITS {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+/// `text` with any marks removed
+pub fn strip(text: &str) -> String {
+    text.chars().filter(|c| !matches!(*c, START | ZERO | ONE)).collect()
+}
@@ -0,0 +1,39 @@
+//! The outgoing HTTP client: accepted endpoint URLs and the request deadline.
+
+use axum::{body::Bytes, http::Request};
+use beep_rust::http_client::{parse_endpoint, HttpClient};
+use http_body_util::Full;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+#[test]
+fn endpoints_must_be_http_or_https_with_a_host() {
+    assert!(parse_endpoint("https://lms.example.edu/grades").is_ok());
+    assert!(parse_endpoint("http://lms.internal:8080/grades").is_ok());
+
+    assert!(parse_endpoint("ftp://lms.example.edu/grades").is_err());
+    assert!(parse_endpoint("/grades").is_err());
+    assert!(parse_endpoint("not a url").is_err());
+}
+
+#[tokio::test]
+async fn a_server_that_never_answers_times_out() {
+    // Accepts the connection and then says nothing
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+
+    let client = HttpClient::with_timeout(Duration::from_millis(200));
+    let request = Request::get(format!("http://{}/", address)).body(Full::new(Bytes::new())).unwrap();
+
+    let started = Instant::now();
+    let error = client.send(request).await.err().expect("the request should time out");
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(error.to_string().contains("did not respond"), "{}", error);
+}
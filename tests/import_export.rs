@@ -0,0 +1,88 @@
+//! Reading question file uploads: multipart forms as browsers and curl send them, and
+//! files sent as the raw request body.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+};
+use beep_rust::{handlers::import_export::read_question_upload, models::QuestionFileFormat};
+
+const BOUNDARY: &str = "------------------------beep0123456789";
+
+const CSV: &str = "question,options,correct_answer\r\nWhich service provides object storage?,Amazon EBS|Amazon S3,B\r\n";
+
+fn multipart_request(body: String) -> Request {
+    Request::builder()
+        .method("POST")
+        .uri("/api/questions/import?topic_slug=aws-storage")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn file_part(name: &str, filename: &str, content_type: &str, content: &str) -> String {
+    format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n{}\r\n",
+        BOUNDARY, name, filename, content_type, content
+    )
+}
+
+fn text_part(name: &str, value: &str) -> String {
+    format!("--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n", BOUNDARY, name, value)
+}
+
+fn end() -> String {
+    format!("--{}--\r\n", BOUNDARY)
+}
+
+#[tokio::test]
+async fn multipart_file_is_read_with_its_name_and_format_from_the_extension() {
+    // curl -F file=@storage.csv sends an unknown type for .csv
+    let body = text_part("note", "spring refresh")
+        + &file_part("file", "storage.csv", "application/octet-stream", CSV)
+        + &end();
+
+    let upload = read_question_upload(multipart_request(body)).await.unwrap();
+
+    assert_eq!(upload.body, CSV);
+    assert_eq!(upload.format, Some(QuestionFileFormat::Csv));
+    assert_eq!(upload.filename.as_deref(), Some("storage.csv"));
+}
+
+#[tokio::test]
+async fn multipart_file_content_type_wins_over_the_extension() {
+    let json = r#"[{"question": "Which service provides object storage?", "options": "Amazon EBS|Amazon S3", "correct_answer": "B"}]"#;
+    let body = file_part("upload", "questions.txt", "application/json", json) + &end();
+
+    let upload = read_question_upload(multipart_request(body)).await.unwrap();
+
+    assert_eq!(upload.body, json);
+    assert_eq!(upload.format, Some(QuestionFileFormat::Json));
+    assert_eq!(upload.filename.as_deref(), Some("questions.txt"));
+}
+
+#[tokio::test]
+async fn multipart_form_without_a_file_is_rejected() {
+    let body = text_part("note", "no file here") + &end();
+
+    let (status, _) = read_question_upload(multipart_request(body)).await.unwrap_err();
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn raw_body_is_the_file_itself() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/questions/import?topic_slug=aws-storage")
+        .header("content-type", "text/csv; charset=utf-8")
+        .body(Body::from(CSV))
+        .unwrap();
+
+    let upload = read_question_upload(request).await.unwrap();
+
+    assert_eq!(upload.body, CSV);
+    assert_eq!(upload.format, Some(QuestionFileFormat::Csv));
+    assert_eq!(upload.filename, None);
+}
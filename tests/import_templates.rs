@@ -0,0 +1,74 @@
+//! Import template routes against a real database. Template names are matched ignoring
+//! case, and reach the handler through path normalization as sent.
+//! Skipped unless `DATABASE_URL` points at a migrated database.
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use beep_rust::{handlers::import, middleware::normalize_path};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::{Layer, ServiceExt};
+use uuid::Uuid;
+
+async fn pool() -> Option<PgPool> {
+    let url = std::env::var("DATABASE_URL").ok()?;
+    Some(PgPool::connect(&url).await.expect("DATABASE_URL is not reachable"))
+}
+
+async fn send(pool: &PgPool, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let api = Router::new()
+        .route("/api/import/templates", get(import::get_templates).post(import::save_template))
+        .route("/api/import/templates/{name}", get(import::get_template).delete(import::delete_template))
+        .with_state(pool.clone());
+    let app = axum::middleware::map_request(normalize_path).layer(api);
+
+    let request = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request.header("content-type", "application/json").body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn mixed_case_template_names_are_found_through_normalized_paths() {
+    let Some(pool) = pool().await else {
+        eprintln!("DATABASE_URL not set; skipping");
+        return;
+    };
+
+    let name = format!("Vendor-X-{}", Uuid::new_v4().simple());
+    let path = format!("/api/import/templates/{}", name);
+
+    let (status, saved) = send(&pool, "POST", "/api/import/templates", Some(json!({ "name": name, "mapping": {} }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", saved);
+    assert_eq!(saved["data"]["name"], json!(name));
+
+    let (status, found) = send(&pool, "GET", &path, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", found);
+    assert_eq!(found["data"]["id"], saved["data"]["id"]);
+
+    // Saving under another case replaces the template instead of adding a second one
+    let renamed = name.to_uppercase();
+    let (status, replaced) = send(&pool, "POST", "/api/import/templates", Some(json!({ "name": renamed, "mapping": {} }))).await;
+    assert_eq!(status, StatusCode::OK, "{}", replaced);
+    assert_eq!(replaced["data"]["id"], saved["data"]["id"]);
+    assert_eq!(replaced["data"]["name"], json!(renamed));
+
+    let (status, _) = send(&pool, "DELETE", &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, missing) = send(&pool, "GET", &path, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(missing["error_code"], json!("TEMPLATE_NOT_FOUND"));
+}
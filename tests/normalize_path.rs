@@ -0,0 +1,39 @@
+//! Path normalization before routing: fixed route segments are matched ignoring case
+//! and trailing slashes, while path parameters are passed through as sent.
+
+use axum::{body::Body, extract::Request};
+use beep_rust::middleware::normalize_path;
+
+async fn normalized(uri: &str) -> String {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    normalize_path(request).await.uri().to_string()
+}
+
+#[tokio::test]
+async fn fixed_segments_are_lowercased_and_trailing_slashes_trimmed() {
+    assert_eq!(normalized("/api/Topics/").await, "/api/topics");
+    assert_eq!(normalized("/API/Search?q=S3").await, "/api/search?q=S3");
+}
+
+#[tokio::test]
+async fn path_parameters_keep_their_case() {
+    assert_eq!(normalized("/api/Import/Templates/Vendor-X").await, "/api/import/templates/Vendor-X");
+    assert_eq!(normalized("/api/Share/AbC123").await, "/api/share/AbC123");
+    assert_eq!(normalized("/api/Topics/Slug/AWS-Storage/").await, "/api/topics/slug/AWS-Storage");
+}
+
+#[tokio::test]
+async fn fixed_segments_win_over_parameters() {
+    assert_eq!(normalized("/api/Topics/Bulk").await, "/api/topics/bulk");
+    assert_eq!(normalized("/api/Topics/Slug/Full").await, "/api/topics/slug/Full");
+}
+
+#[tokio::test]
+async fn unknown_paths_keep_their_case() {
+    assert_eq!(normalized("/api/No/Such/Route/").await, "/api/No/Such/Route");
+}
+
+#[tokio::test]
+async fn paths_outside_api_are_untouched() {
+    assert_eq!(normalized("/s/AbC123/").await, "/s/AbC123/");
+}